@@ -0,0 +1,365 @@
+//! # CLI
+//! Handles the command line arguments and environment variables for the game.
+use crate::game::color::ColorMode;
+use crate::game::config::Difficulty;
+
+/// The environment variable that sets the database path.
+pub const DB_ENV: &str = "RETRIBUTION_DB";
+/// The environment variable that sets the plugin output path.
+pub const PLUGIN_PATH_ENV: &str = "RETRIBUTION_PLUGIN_PATH";
+/// The environment variable that sets the difficulty.
+pub const DIFFICULTY_ENV: &str = "RETRIBUTION_DIFFICULTY";
+/// The environment variable that sets the seed of the dice.
+pub const SEED_ENV: &str = "RETRIBUTION_SEED";
+/// The environment variable that sets whether the database uses write-ahead logging.
+pub const WAL_ENV: &str = "RETRIBUTION_WAL";
+
+/// A struct that holds the parsed command line arguments.
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    /// The path to the database, set with `--db <path>`.
+    pub db: Option<String>,
+    /// The path to the plugin output file, set with `--plugin-out <path>`.
+    pub plugin_out: Option<String>,
+    /// Whether the debug commands are turned on, set with `--debug`.
+    pub debug: bool,
+    /// When the output is colored, set with `--color <always|never|auto>`.
+    pub color: ColorMode,
+    /// How hard the enemies are, set with `--difficulty <easy|normal|hard>`.
+    pub difficulty: Option<Difficulty>,
+    /// The seed of the dice, set with `--seed <n>`.
+    pub seed: Option<u64>,
+    /// Whether the database uses write-ahead logging, set with `--wal <on|off>`.
+    pub wal: Option<bool>,
+    /// Whether to print the version of the game and exit, set with `--version`.
+    pub version: bool,
+}
+
+impl Args {
+    /// A function that parses the command line arguments, skipping the program name.
+    ///
+    /// # Arguments
+    /// * `args` - An iterator of the command line arguments, including the program name.
+    ///
+    /// # Returns
+    /// * `Result<Args, String>` - The parsed arguments, or an error message.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::cli;
+    ///
+    /// let args = vec!["retribution", "--db", "world.db"].into_iter().map(String::from);
+    /// let args = cli::Args::parse(args).unwrap();
+    /// assert_eq!(args.db, Some(String::from("world.db")));
+    /// ```
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--db" => {
+                    let path = args.next().ok_or("Missing path for --db.")?;
+                    parsed.db = Some(path);
+                }
+                "--plugin-out" => {
+                    let path = args.next().ok_or("Missing path for --plugin-out.")?;
+                    parsed.plugin_out = Some(path);
+                }
+                "--debug" => parsed.debug = true,
+                "--version" => parsed.version = true,
+                "--color" => {
+                    let mode = args.next().ok_or("Missing mode for --color.")?;
+                    parsed.color = mode.parse()?;
+                }
+                "--difficulty" => {
+                    let level = args.next().ok_or("Missing level for --difficulty.")?;
+                    parsed.difficulty = Some(level.parse()?);
+                }
+                "--seed" => {
+                    let seed = args.next().ok_or("Missing number for --seed.")?;
+                    parsed.seed = Some(parse_seed(seed.as_str())?);
+                }
+                "--wal" => {
+                    let wal = args.next().ok_or("Missing on or off for --wal.")?;
+                    parsed.wal = Some(parse_wal(wal.as_str())?);
+                }
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Resolves the database path from the `--db` flag, the `RETRIBUTION_DB` environment
+    /// variable, or the default path, in that order.
+    ///
+    /// # Returns
+    /// * `String` - The path to the database.
+    pub fn db_path(&self) -> String {
+        resolve_db_path(self.db.clone(), std::env::var(DB_ENV).ok())
+    }
+
+    /// Resolves the plugin output path from the `--plugin-out` flag or the
+    /// `RETRIBUTION_PLUGIN_PATH` environment variable, in that order.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The path to the plugin file, or None to use the default path.
+    pub fn plugin_path(&self) -> Option<String> {
+        resolve_plugin_path(self.plugin_out.clone(), std::env::var(PLUGIN_PATH_ENV).ok())
+    }
+
+    /// Resolves the difficulty from the `--difficulty` flag, the `RETRIBUTION_DIFFICULTY`
+    /// environment variable, or Normal, in that order.
+    ///
+    /// # Returns
+    /// * `Result<Difficulty, String>` - The difficulty, or an error message if the environment
+    ///   variable isn't a difficulty.
+    pub fn difficulty(&self) -> Result<Difficulty, String> {
+        resolve_difficulty(self.difficulty, std::env::var(DIFFICULTY_ENV).ok())
+    }
+
+    /// Resolves the seed of the dice from the `--seed` flag or the `RETRIBUTION_SEED`
+    /// environment variable, in that order.
+    ///
+    /// # Returns
+    /// * `Result<Option<u64>, String>` - The seed, None to seed from the clock, or an error
+    ///   message if the environment variable isn't a number.
+    pub fn seed(&self) -> Result<Option<u64>, String> {
+        match (self.seed, std::env::var(SEED_ENV).ok()) {
+            (Some(seed), _) => Ok(Some(seed)),
+            (None, Some(env)) => parse_seed(env.as_str()).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Resolves whether the database uses write-ahead logging from the `--wal` flag, the
+    /// `RETRIBUTION_WAL` environment variable, or on, in that order.
+    ///
+    /// # Returns
+    /// * `Result<bool, String>` - Whether write-ahead logging is on, or an error message if the
+    ///   environment variable isn't on or off.
+    pub fn wal(&self) -> Result<bool, String> {
+        resolve_wal(self.wal, std::env::var(WAL_ENV).ok())
+    }
+}
+
+/// A function that reads whether write-ahead logging is on.
+///
+/// # Arguments
+/// * `wal` - A string slice that is on or off, given by the player.
+///
+/// # Returns
+/// * `Result<bool, String>` - Whether write-ahead logging is on, or an error message.
+pub fn parse_wal(wal: &str) -> Result<bool, String> {
+    match wal.to_lowercase().as_str() {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        _ => Err(format!("Write-ahead logging must be on or off: {}", wal)),
+    }
+}
+
+/// A function that reads the seed of the dice.
+///
+/// # Arguments
+/// * `seed` - A string slice that is the seed given by the player.
+///
+/// # Returns
+/// * `Result<u64, String>` - The seed, or an error message if it isn't a number.
+pub fn parse_seed(seed: &str) -> Result<u64, String> {
+    seed.parse()
+        .map_err(|_| format!("The seed must be a whole number: {}", seed))
+}
+
+/// A function that picks the database path, preferring the flag over the environment variable
+/// over the default path.
+///
+/// # Arguments
+/// * `flag` - The path given with `--db`, if any.
+/// * `env` - The path given with `RETRIBUTION_DB`, if any.
+///
+/// # Returns
+/// * `String` - The path to the database.
+pub fn resolve_db_path(flag: Option<String>, env: Option<String>) -> String {
+    flag.or(env).unwrap_or_else(|| String::from(crate::DB_PATH))
+}
+
+/// A function that picks the plugin output path, preferring the flag over the environment
+/// variable. None means the StateWriter's default path is used.
+///
+/// # Arguments
+/// * `flag` - The path given with `--plugin-out`, if any.
+/// * `env` - The path given with `RETRIBUTION_PLUGIN_PATH`, if any.
+///
+/// # Returns
+/// * `Option<String>` - The path to the plugin file, if one was given.
+pub fn resolve_plugin_path(flag: Option<String>, env: Option<String>) -> Option<String> {
+    flag.or(env)
+}
+
+/// A function that picks the difficulty, preferring the flag over the environment variable over
+/// Normal.
+///
+/// # Arguments
+/// * `flag` - The difficulty given with `--difficulty`, if any.
+/// * `env` - The difficulty given with `RETRIBUTION_DIFFICULTY`, if any.
+///
+/// # Returns
+/// * `Result<Difficulty, String>` - The difficulty, or an error message if the environment
+///   variable isn't a difficulty.
+pub fn resolve_difficulty(
+    flag: Option<Difficulty>,
+    env: Option<String>,
+) -> Result<Difficulty, String> {
+    match (flag, env) {
+        (Some(difficulty), _) => Ok(difficulty),
+        (None, Some(env)) => env.parse(),
+        (None, None) => Ok(Difficulty::Normal),
+    }
+}
+
+/// A function that picks whether the database uses write-ahead logging, preferring the flag over
+/// the environment variable over on.
+///
+/// # Arguments
+/// * `flag` - Whether it was turned on or off with `--wal`, if at all.
+/// * `env` - Whether it was turned on or off with `RETRIBUTION_WAL`, if at all.
+///
+/// # Returns
+/// * `Result<bool, String>` - Whether write-ahead logging is on, or an error message if the
+///   environment variable isn't on or off.
+pub fn resolve_wal(flag: Option<bool>, env: Option<String>) -> Result<bool, String> {
+    match (flag, env) {
+        (Some(wal), _) => Ok(wal),
+        (None, Some(env)) => parse_wal(env.as_str()),
+        (None, None) => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Turn a list of string slices into arguments.
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|a| String::from(*a))
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    /// Test parsing the db flag.
+    #[test]
+    fn parse_db_test() {
+        let parsed = Args::parse(args(&["retribution", "--db", "world.db"])).unwrap();
+        assert_eq!(parsed.db, Some(String::from("world.db")));
+        let parsed = Args::parse(args(&["retribution"])).unwrap();
+        assert_eq!(parsed.db, None);
+    }
+
+    /// Test parsing the debug flag.
+    #[test]
+    fn parse_debug_test() {
+        let parsed = Args::parse(args(&["retribution", "--debug"])).unwrap();
+        assert!(parsed.debug);
+        let parsed = Args::parse(args(&["retribution"])).unwrap();
+        assert!(!parsed.debug);
+    }
+
+    /// Test parsing the version flag.
+    #[test]
+    fn parse_version_test() {
+        let parsed = Args::parse(args(&["retribution", "--version"])).unwrap();
+        assert!(parsed.version);
+        let parsed = Args::parse(args(&["retribution"])).unwrap();
+        assert!(!parsed.version);
+    }
+
+    /// Test parsing the color flag.
+    #[test]
+    fn parse_color_test() {
+        let parsed = Args::parse(args(&["retribution", "--color", "never"])).unwrap();
+        assert_eq!(parsed.color, ColorMode::Never);
+        let parsed = Args::parse(args(&["retribution"])).unwrap();
+        assert_eq!(parsed.color, ColorMode::Auto);
+        assert!(Args::parse(args(&["retribution", "--color", "rainbow"])).is_err());
+    }
+
+    /// Test parsing the difficulty flag and falling back to the environment variable.
+    #[test]
+    fn difficulty_test() {
+        let parsed = Args::parse(args(&["retribution", "--difficulty", "hard"])).unwrap();
+        assert_eq!(parsed.difficulty, Some(Difficulty::Hard));
+        assert!(Args::parse(args(&["retribution", "--difficulty", "brutal"])).is_err());
+        let env = Some(String::from("easy"));
+        assert_eq!(
+            resolve_difficulty(parsed.difficulty, env.clone()),
+            Ok(Difficulty::Hard)
+        );
+        assert_eq!(resolve_difficulty(None, env), Ok(Difficulty::Easy));
+        assert_eq!(resolve_difficulty(None, None), Ok(Difficulty::Normal));
+        assert!(resolve_difficulty(None, Some(String::from("brutal"))).is_err());
+    }
+
+    /// Test parsing the seed flag.
+    #[test]
+    fn parse_seed_test() {
+        let parsed = Args::parse(args(&["retribution", "--seed", "1234"])).unwrap();
+        assert_eq!(parsed.seed, Some(1234));
+        assert!(Args::parse(args(&["retribution", "--seed", "abc"])).is_err());
+        assert!(Args::parse(args(&["retribution", "--seed"])).is_err());
+    }
+
+    /// Test parsing the wal flag and falling back to the environment variable.
+    #[test]
+    fn wal_test() {
+        let parsed = Args::parse(args(&["retribution", "--wal", "off"])).unwrap();
+        assert_eq!(parsed.wal, Some(false));
+        assert!(Args::parse(args(&["retribution", "--wal", "maybe"])).is_err());
+        assert!(Args::parse(args(&["retribution", "--wal"])).is_err());
+        let env = Some(String::from("on"));
+        assert_eq!(resolve_wal(parsed.wal, env.clone()), Ok(false));
+        assert_eq!(resolve_wal(None, env), Ok(true));
+        assert_eq!(resolve_wal(None, Some(String::from("off"))), Ok(false));
+        assert_eq!(resolve_wal(None, None), Ok(true));
+        assert!(resolve_wal(None, Some(String::from("maybe"))).is_err());
+    }
+
+    /// Test parsing bad arguments.
+    #[test]
+    fn parse_error_test() {
+        assert!(Args::parse(args(&["retribution", "--db"])).is_err());
+        assert!(Args::parse(args(&["retribution", "--nope"])).is_err());
+    }
+
+    /// Test the precedence of the database path.
+    #[test]
+    fn resolve_db_path_test() {
+        let flag = Some(String::from("flag.db"));
+        let env = Some(String::from("env.db"));
+        assert_eq!(resolve_db_path(flag, env.clone()), "flag.db");
+        assert_eq!(resolve_db_path(None, env), "env.db");
+        assert_eq!(resolve_db_path(None, None), crate::DB_PATH);
+    }
+
+    /// Test parsing the plugin output flag.
+    #[test]
+    fn parse_plugin_out_test() {
+        let parsed = Args::parse(args(&["retribution", "--plugin-out", "overlay.json"])).unwrap();
+        assert_eq!(parsed.plugin_out, Some(String::from("overlay.json")));
+        assert!(Args::parse(args(&["retribution", "--plugin-out"])).is_err());
+    }
+
+    /// Test the environment variable overriding the plugin output path.
+    #[test]
+    fn resolve_plugin_path_test() {
+        let env = Some(String::from("/tmp/overlay.json"));
+        let path = resolve_plugin_path(None, env.clone());
+        let state_writer = crate::plugin::StateWriter::new(path);
+        assert_eq!(state_writer.output_file, "/tmp/overlay.json");
+        let flag = Some(String::from("flag.json"));
+        assert_eq!(
+            resolve_plugin_path(flag, env),
+            Some(String::from("flag.json"))
+        );
+        assert_eq!(resolve_plugin_path(None, None), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! # Database
+//! Opens the SQLite database the maps, items, and saves are stored in. Another connection, such
+//! as another copy of the game, can hold the database for a moment, so the statements that read
+//! and write it are retried with a short backoff before giving up.
+use rusqlite::{Connection, ErrorCode, Transaction, TransactionBehavior};
+use std::thread;
+use std::time::Duration;
+
+/// The number of times database access is tried before giving up.
+pub const ATTEMPTS: u32 = 3;
+/// The wait before the first retry, doubled after each one.
+const BACKOFF: Duration = Duration::from_millis(20);
+/// How long a connection waits for another to let go of the database before reporting it busy.
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A function that checks if a database error might go away when tried again, such as the
+/// database being locked by another connection.
+///
+/// # Arguments
+/// * `error` - A reference to the error to check.
+///
+/// # Returns
+/// * `bool` - True if the access is worth trying again.
+pub fn is_transient(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// A function that runs database access, trying it again with a growing wait while it fails
+/// because the database is busy. Other errors are returned straight away.
+///
+/// # Arguments
+/// * `access` - The database access to run.
+///
+/// # Returns
+/// * `rusqlite::Result<T>` - The result of the access, or the last error once every attempt has
+///   failed.
+///
+/// # Examples
+/// ```
+/// use retribution::db;
+///
+/// let mut tries = 0;
+/// let result = db::with_retry(|| {
+///     tries += 1;
+///     match tries {
+///         1 => Err(rusqlite::Error::SqliteFailure(
+///             rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+///             None,
+///         )),
+///         _ => Ok(tries),
+///     }
+/// });
+/// assert_eq!(result, Ok(2));
+/// ```
+pub fn with_retry<T>(mut access: impl FnMut() -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+    let mut backoff = BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match access() {
+            Err(e) if attempt < ATTEMPTS && is_transient(&e) => {
+                thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// A function that opens the database. Opening doesn't lock it, so it isn't retried, but the
+/// connection waits up to `BUSY_TIMEOUT` for a lock before reporting the database busy.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `rusqlite::Result<Connection>` - The open connection, or an error.
+pub fn open(path: &str) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+/// A function that starts a transaction that takes the write lock up front, retrying while
+/// another connection holds it. The statements in the transaction can't then find the database
+/// busy.
+///
+/// # Arguments
+/// * `conn` - A reference to the open database connection.
+///
+/// # Returns
+/// * `rusqlite::Result<Transaction>` - The transaction, or the error from the last attempt.
+pub fn transaction(conn: &Connection) -> rusqlite::Result<Transaction<'_>> {
+    with_retry(|| Transaction::new_unchecked(conn, TransactionBehavior::Immediate))
+}
+
+/// A function that checks the schema of the database for a table, such as to tell a database
+/// that hasn't been migrated from one that failed another way.
+///
+/// # Arguments
+/// * `conn` - A reference to the open database connection.
+/// * `name` - A string slice that is the name of the table.
+///
+/// # Returns
+/// * `rusqlite::Result<bool>` - Whether the table is there, or an error.
+pub fn has_table(conn: &Connection, name: &str) -> rusqlite::Result<bool> {
+    with_retry(|| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |row| row.get::<_, i64>(0),
+        )
+    })
+    .map(|count| count > 0)
+}
+
+/// A function that sets how the database journals its writes. With write-ahead logging, saving
+/// the game doesn't block loading a map, and the other way around. The mode is stored in the
+/// database, so it only needs to be set once.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+/// * `wal` - Whether to use write-ahead logging, rather than SQLite's default rollback journal.
+///
+/// # Returns
+/// * `rusqlite::Result<String>` - The journal mode the database is now in, such as "wal".
+pub fn set_journal_mode(path: &str, wal: bool) -> rusqlite::Result<String> {
+    let conn = open(path)?;
+    let mode = if wal { "WAL" } else { "DELETE" };
+    with_retry(|| {
+        conn.query_row(&format!("PRAGMA journal_mode = {}", mode), [], |row| {
+            row.get(0)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A database error saying the database is locked.
+    fn busy() -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY), None)
+    }
+
+    /// Test that a busy database is tried every attempt before the error is returned.
+    #[test]
+    fn with_retry_busy_test() {
+        let mut tries = 0;
+        let result: rusqlite::Result<()> = with_retry(|| {
+            tries += 1;
+            Err(busy())
+        });
+        assert_eq!(tries, ATTEMPTS);
+        assert_eq!(
+            result.unwrap_err().sqlite_error_code(),
+            Some(ErrorCode::DatabaseBusy)
+        );
+    }
+
+    /// Test that a busy or locked database is worth trying again, and one that can't be opened
+    /// isn't.
+    #[test]
+    fn is_transient_test() {
+        let error = |code| rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), None);
+        assert!(is_transient(&busy()));
+        assert!(is_transient(&error(rusqlite::ffi::SQLITE_LOCKED)));
+        assert!(!is_transient(&error(rusqlite::ffi::SQLITE_CANTOPEN)));
+    }
+
+    /// Test that a transaction waits out another connection holding the write lock.
+    #[test]
+    fn transaction_locked_database_test() {
+        let path = "transaction_locked_database_test.db";
+        let holder = open(path).unwrap();
+        holder
+            .execute_batch("CREATE TABLE things (name TEXT); BEGIN IMMEDIATE;")
+            .unwrap();
+        let waiter = open(path).unwrap();
+        waiter.busy_timeout(Duration::ZERO).unwrap();
+        let release = thread::spawn(move || {
+            thread::sleep(BACKOFF);
+            holder.execute_batch("COMMIT;").unwrap();
+        });
+        let tx = transaction(&waiter).unwrap();
+        tx.execute("INSERT INTO things (name) VALUES ('rope')", [])
+            .unwrap();
+        tx.commit().unwrap();
+        release.join().unwrap();
+        drop(waiter);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    /// Test finding a table in the schema.
+    #[test]
+    fn has_table_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(has_table(&conn, "things"), Ok(false));
+        conn.execute_batch("CREATE TABLE things (name TEXT);")
+            .unwrap();
+        assert_eq!(has_table(&conn, "things"), Ok(true));
+    }
+
+    /// Test that an error that won't go away isn't retried.
+    #[test]
+    fn with_retry_lasting_error_test() {
+        let mut tries = 0;
+        let result: rusqlite::Result<()> = with_retry(|| {
+            tries += 1;
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        });
+        assert_eq!(tries, 1);
+        assert_eq!(result, Err(rusqlite::Error::QueryReturnedNoRows));
+    }
+
+    /// Test that a query waits out another connection holding the database.
+    #[test]
+    fn with_retry_locked_database_test() {
+        let path = "with_retry_locked_database_test.db";
+        let holder = open(path).unwrap();
+        holder
+            .execute_batch("CREATE TABLE things (name TEXT); BEGIN EXCLUSIVE;")
+            .unwrap();
+        let waiter = open(path).unwrap();
+        waiter.busy_timeout(Duration::ZERO).unwrap();
+        let release = thread::spawn(move || {
+            thread::sleep(BACKOFF);
+            holder.execute_batch("COMMIT;").unwrap();
+        });
+        let result = with_retry(|| {
+            waiter.query_row("SELECT COUNT(*) FROM things", [], |row| {
+                row.get::<_, i64>(0)
+            })
+        });
+        release.join().unwrap();
+        drop(waiter);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result, Ok(0));
+    }
+}
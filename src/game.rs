@@ -1,14 +1,28 @@
-use crate::{migration, plugin::PLUGIN_OUTPUT};
+use crate::{db, migration, plugin, ret_lang};
 use std::io;
+use std::io::BufRead;
 
+pub mod actor;
+pub mod ally;
+pub mod color;
+pub mod combat;
+pub mod config;
+pub mod dice;
+pub mod enemy;
+pub mod error;
+pub mod event;
 pub mod interpreter;
+pub mod item;
 pub mod map;
+pub mod player;
+pub mod save;
+pub mod spell;
 pub mod state;
 
-/// Prompt error message.
-const PROMPT_ERROR: &str = "Try command again.";
-/// Prompt message.
-const HERO_PROMPT: &str = "What do you do hero?";
+/// Message welcoming the hero to a new game.
+pub const WELCOME_MESSAGE: &str = "Welcome, hero. Your retribution begins here.";
+/// Message pointing a new hero to the help.
+pub const HELP_HINT_MESSAGE: &str = "Type 'help' at any time to see what you can do.";
 
 /// A trait that defines a function to read a line.
 pub trait LineReader {
@@ -29,38 +43,292 @@ impl LineReader for io::Stdin {
     }
 }
 
-/// A function that ask the user for input and collects it.
+/// Lets the game read commands from any buffered source, such as a script file or socket.
+impl<R: io::Read> LineReader for io::BufReader<R> {
+    fn read_line(&mut self, buf: &mut String) -> Result<usize, io::Error> {
+        BufRead::read_line(self, buf)
+    }
+}
+
+/// A function that ask the user for input and collects it. The prompt isn't shown when the config
+/// is quiet.
 ///
 /// # Arguments
 /// * `reader` - A mutable reference to a `io::Stdin`.
+/// * `writer` - A mutable reference to where the prompt is written.
+/// * `config` - A reference to the game config holding the prompt messages.
 ///
 /// # Returns
 /// * `Result<String, &'a str>` - A string that is the user's input, or an error message.
-pub fn prompt<'a>(reader: &'a mut dyn LineReader) -> Result<String, &'a str> {
-    println!("{}", HERO_PROMPT);
+pub fn prompt<'a>(
+    reader: &mut dyn LineReader,
+    writer: &mut dyn io::Write,
+    config: &'a config::GameConfig,
+) -> Result<String, &'a str> {
+    if !config.quiet {
+        writeln!(writer, "{}", config.prompt).map_err(|_| config.prompt_error.as_str())?;
+    }
     let mut input = String::new();
-    reader.read_line(&mut input).map_err(|_| PROMPT_ERROR)?;
+    reader
+        .read_line(&mut input)
+        .map_err(|_| config.prompt_error.as_str())?;
     Ok(input)
 }
 
-/// Function to run before the game initializes.
+/// A function that builds the intro shown at the start of a new game: a welcome, the room the
+/// hero starts in, and a hint to ask for help. A game that has been played before, having taken
+/// a turn or left an autosave, gets no intro.
+///
+/// # Arguments
+/// * `state` - A reference to the GameState about to be played.
+/// * `config` - A reference to the game config holding the database path.
+///
+/// # Returns
+/// * `Vec<String>` - The lines of the intro, which are empty for a resumed game.
+pub fn intro(state: &state::GameState, config: &config::GameConfig) -> Vec<String> {
+    if state.turn > 0 || save::load_game(save::AUTOSAVE_SLOT, config.db_path.as_str()).is_ok() {
+        return vec![];
+    }
+    let mut lines = vec![String::from(WELCOME_MESSAGE)];
+    lines.extend(state.surroundings());
+    lines.push(String::from(HELP_HINT_MESSAGE));
+    lines
+}
+
+/// A function that runs the main game loop until the hero exits or the input runs out. A line can
+/// hold several commands separated by `.` or `;`, which run in order until one fails. The game
+/// opens by telling the hero where they are, or with the intro for a new game when the config
+/// asks for it.
+///
+/// # Arguments
+/// * `reader` - A mutable reference to where the commands are read from.
+/// * `writer` - A mutable reference to where the prompt and command output is written.
+/// * `state` - A mutable reference to the GameState being played.
+/// * `config` - A reference to the game config holding the messages.
+/// * `state_writer` - An optional reference to the StateWriter that shares the state with plugins.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok once the game ends, or an error message.
+///
+/// # Examples
+/// ```
+/// use retribution::game;
+/// use retribution::game::config;
+/// use retribution::game::state;
+/// use std::io;
+///
+/// let mut reader = io::BufReader::new("exit\n".as_bytes());
+/// let mut writer = Vec::new();
+/// let mut game_state = state::GameState::new();
+/// let game_config = config::GameConfig::new();
+/// game::run(&mut reader, &mut writer, &mut game_state, &game_config, None).unwrap();
+/// ```
+pub fn run(
+    reader: &mut dyn LineReader,
+    writer: &mut dyn io::Write,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+    state_writer: Option<&plugin::StateWriter>,
+) -> Result<(), &'static str> {
+    let write_error = |_| "Unable to write output.";
+    let mut opening = if config.intro {
+        intro(state, config)
+    } else {
+        vec![]
+    };
+    if opening.is_empty() {
+        opening = state.surroundings();
+    }
+    for line in opening {
+        writeln!(writer, "{}", line).map_err(write_error)?;
+    }
+    loop {
+        if let Some(state_writer) = state_writer {
+            // We don't care if the state writer fails as the game will continue
+            // to function as normal.
+            let _ = state_writer.write_state(state.clone());
+        }
+        let input = match prompt(reader, writer, config) {
+            Ok(i) => i,
+            Err(e) => {
+                writeln!(writer, "{}", e).map_err(write_error)?;
+                continue;
+            }
+        };
+        // Nothing left to read, so the session is over.
+        if input.is_empty() {
+            return Ok(());
+        }
+        let commands = match ret_lang::parse_inputs_with(&input[..], &config.stop_words) {
+            Ok(c) => c,
+            Err(e) => {
+                writeln!(writer, "{} is not a valid command.", e.input).map_err(write_error)?;
+                writeln!(writer, "{}", e.error).map_err(write_error)?;
+                continue;
+            }
+        };
+        // Only the events of the latest line are sent to plugins.
+        state.events.clear();
+        let before = autosave_key(state);
+        for command in commands {
+            match interpreter::interpret(&command, state, config) {
+                Ok(o) => {
+                    for line in color::render(&o, config.color) {
+                        writeln!(writer, "{}", line).map_err(write_error)?;
+                    }
+                    if let interpreter::Outcome::GameOver(_) = o {
+                        return Ok(());
+                    }
+                }
+                Err(e) => {
+                    // The rest of the line counted on this command, so it isn't run.
+                    writeln!(writer, "{}", config.error_message(&e)).map_err(write_error)?;
+                    break;
+                }
+            }
+        }
+        if config.autosave && autosave_key(state) != before {
+            // Like the plugin file, a failed autosave shouldn't stop the game.
+            let _ = save::autosave(state, config.db_path.as_str());
+        }
+    }
+}
+
+/// A function that runs the commands in a script file, one line at a time, and collects their
+/// output. A line can hold several commands separated by `.` or `;`. A command that fails has its
+/// error added to the output and the script carries on with the next line, but the script stops
+/// once the game is over. Blank lines are skipped. Unlike `run`, nothing is
+/// autosaved or shared with plugins.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the script.
+/// * `state` - A mutable reference to the GameState the script plays.
+/// * `config` - A reference to the game config holding the messages.
+///
+/// # Returns
+/// * `Result<Vec<String>, &'static str>` - The lines of output, or an error message when the
+///   script can't be read.
+pub fn run_script(
+    path: &str,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<Vec<String>, &'static str> {
+    let file = std::fs::File::open(path).map_err(|_| "Unable to open script.")?;
+    let mut reader = io::BufReader::new(file);
+    let mut output = vec![];
+    loop {
+        let mut input = String::new();
+        if LineReader::read_line(&mut reader, &mut input).map_err(|_| "Unable to read script.")?
+            == 0
+        {
+            return Ok(output);
+        }
+        if ret_lang::normalize(&input).is_empty() {
+            continue;
+        }
+        let commands = match ret_lang::parse_inputs_with(&input[..], &config.stop_words) {
+            Ok(c) => c,
+            Err(e) => {
+                output.push(format!("{} is not a valid command.", e.input));
+                output.push(e.error);
+                continue;
+            }
+        };
+        state.events.clear();
+        for command in commands {
+            match interpreter::interpret(&command, state, config) {
+                Ok(o) => {
+                    let game_over = matches!(o, interpreter::Outcome::GameOver(_));
+                    output.extend(o.into_output().lines);
+                    if game_over {
+                        return Ok(output);
+                    }
+                }
+                Err(e) => {
+                    output.push(config.error_message(&e));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// A function that gets the parts of the state that trigger an autosave when they change.
+///
+/// # Arguments
+/// * `state` - A reference to the GameState being played.
+///
+/// # Returns
+/// * `(state::Mode, Option<String>, Option<map::Coord>)` - The mode, map name, and room.
+fn autosave_key(state: &state::GameState) -> (state::Mode, Option<String>, Option<map::Coord>) {
+    (
+        state.mode.clone(),
+        state.map.as_ref().map(|m| m.name.clone()),
+        state.room,
+    )
+}
+
+/// Function to run before the game initializes. The database uses write-ahead logging.
+///
+/// # Arguments
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is either Ok or Err.
+pub fn init(path: Option<String>) -> Result<(), &'static str> {
+    init_with(path, true)
+}
+
+/// Function to run before the game initializes, choosing the journal mode of the database.
+///
+/// # Arguments
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+/// * `wal` - Whether the database uses write-ahead logging, rather than the rollback journal.
 ///
 /// # Returns
 /// * `Result<(), &'static str>` - A result that is either Ok or Err.
-pub fn init() -> Result<(), &'static str> {
+pub fn init_with(path: Option<String>, wal: bool) -> Result<(), &'static str> {
     // Set up the database.
-    migration::map::migrate_up(None)
+    migration::migrate_up(path.clone())?;
+    let path = path.unwrap_or_else(|| String::from(crate::DB_PATH));
+    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+    db::set_journal_mode(path.as_str(), wal).map_err(|_| "Unable to set the journal mode.")?;
+    Ok(())
 }
 
 /// Function to run after the game ends.
 ///
+/// # Arguments
+/// * `plugin_path` - A string slice that is the path to the plugin file to remove.
+///
 /// # Returns
 /// * `Result<(), &'static str>` - A result that is either Ok or Err.
-pub fn tear_down() -> Result<(), &'static str> {
-    std::fs::remove_file(PLUGIN_OUTPUT).map_err(|_| "Failed to remove plugin file.")?;
+pub fn tear_down(plugin_path: &str) -> Result<(), &'static str> {
+    std::fs::remove_file(plugin_path).map_err(|_| "Failed to remove plugin file.")?;
     Ok(())
 }
 
+/// Function to run when the game is interrupted, such as by Ctrl-C. The game is saved to the
+/// autosave slot, when autosaving is on, before the game is torn down.
+///
+/// # Arguments
+/// * `state` - An optional reference to the GameState being played, if there is one to save.
+/// * `config` - A reference to the game config holding the database path.
+/// * `plugin_path` - A string slice that is the path to the plugin file to remove.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is either Ok or Err.
+pub fn shutdown(
+    state: Option<&state::GameState>,
+    config: &config::GameConfig,
+    plugin_path: &str,
+) -> Result<(), &'static str> {
+    if let (Some(state), true) = (state, config.autosave) {
+        save::save_game(state, save::AUTOSAVE_SLOT, config.db_path.as_str())?;
+    }
+    tear_down(plugin_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,15 +352,344 @@ mod tests {
         let mut reader = MockReader {
             input: String::from("go north\n"),
         };
-        let input = prompt(&mut reader).unwrap_or_else(|e| panic!("{}", e));
+        let mut writer = Vec::new();
+        let game_config = config::GameConfig::new();
+        let input =
+            prompt(&mut reader, &mut writer, &game_config).unwrap_or_else(|e| panic!("{}", e));
         assert_eq!(input, "go north\n");
+        assert_eq!(String::from_utf8(writer).unwrap(), "What do you do hero?\n");
+    }
+
+    /// Test the prompt function with a custom prompt message.
+    #[test]
+    fn prompt_custom_message_test() {
+        let mut reader = MockReader {
+            input: String::from("go north\n"),
+        };
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.prompt = String::from("What now, champion?");
+        prompt(&mut reader, &mut writer, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(String::from_utf8(writer).unwrap(), "What now, champion?\n");
+    }
+
+    /// Test that a quiet config reads the input without showing the prompt.
+    #[test]
+    fn prompt_quiet_test() {
+        let mut reader = MockReader {
+            input: String::from("look"),
+        };
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.quiet = true;
+        let input =
+            prompt(&mut reader, &mut writer, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(input, "look");
+        assert!(writer.is_empty());
+    }
+
+    /// Test that moving sends a PlayerMoved event to plugins.
+    #[test]
+    fn run_player_moved_event_test() {
+        let path = "run_player_moved_event_test.json";
+        let state_writer = plugin::StateWriter::new(Some(String::from(path)));
+        let mut reader = io::BufReader::new("go north\n".as_bytes());
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.autosave = false;
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+        run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            Some(&state_writer),
+        )
+        .unwrap();
+        let json = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let output: serde_json::Value = serde_json::from_str(json.as_str()).unwrap();
+        assert_eq!(
+            output["events"],
+            serde_json::json!([{"type": "PlayerMoved", "map": "Test Area", "to": [0, 1]}])
+        );
+    }
+
+    /// Test that a single exit doesn't end the game, but two in a row do.
+    #[test]
+    fn run_confirm_exit_test() {
+        let mut reader = io::BufReader::new("exit\ngo north\nexit\nexit\ngo south\n".as_bytes());
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.autosave = false;
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+        run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            None,
+        )
+        .unwrap();
+        let output = String::from_utf8(writer).unwrap();
+        assert!(output.contains("Hero went north."));
+        assert!(output.ends_with("Farewell hero.\n"));
+        assert_eq!(game_state.room, Some(map::Coord::new(0, 1)));
+    }
+
+    /// Test that the game opens by describing the room the hero starts in.
+    #[test]
+    fn run_starting_room_test() {
+        let mut reader = io::BufReader::new("".as_bytes());
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.autosave = false;
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 2));
+        run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            None,
+        )
+        .unwrap();
+        let output = String::from_utf8(writer).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("Room 3: This is room 3."));
+        assert_eq!(lines.next(), Some("Exits: west."));
+        assert_eq!(lines.next(), Some(game_config.prompt.as_str()));
+    }
+
+    /// Test that the commands of a line run in order, and the rest of the line is skipped once one
+    /// fails.
+    #[test]
+    fn run_several_commands_test() {
+        let mut reader =
+            io::BufReader::new("go north. go north; go south\nexit; exit; look\n".as_bytes());
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.autosave = false;
+        game_config.quiet = true;
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+        run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            None,
+        )
+        .unwrap();
+        let output = String::from_utf8(writer).unwrap();
+        let lines: Vec<&str> = output.lines().skip(2).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Hero went north. This is room 4.",
+                "You can't go that way.",
+                "Are you sure? Type 'exit' again to quit.",
+                "Farewell hero.",
+            ]
+        );
+        assert_eq!(game_state.room, Some(map::Coord::new(0, 1)));
+    }
+
+    /// Test that a new game opens with the intro, and a resumed one doesn't.
+    #[test]
+    fn run_intro_test() {
+        let path = "run_intro_test.db";
+        migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        game_config.autosave = false;
+        game_config.intro = true;
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+        let fresh = intro(&game_state, &game_config);
+        let mut writer = Vec::new();
+        let mut reader = io::BufReader::new("".as_bytes());
+        let result = run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            None,
+        );
+        let mut played = game_state.clone();
+        played.turn = 3;
+        let resumed = intro(&played, &game_config);
+        save::save_game(&game_state, save::AUTOSAVE_SLOT, path).unwrap();
+        let autosaved = intro(&game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+        result.unwrap();
+        assert_eq!(
+            fresh,
+            vec![
+                WELCOME_MESSAGE,
+                "Room 1: This is room 1.",
+                "Exits: north, east, west.",
+                HELP_HINT_MESSAGE
+            ]
+        );
+        assert!(String::from_utf8(writer)
+            .unwrap()
+            .starts_with(WELCOME_MESSAGE));
+        assert!(resumed.is_empty());
+        assert!(autosaved.is_empty());
+    }
+
+    /// Test that moving to another room saves the game to the autosave slot.
+    #[test]
+    fn run_autosave_test() {
+        let path = "run_autosave_test.db";
+        migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut reader = io::BufReader::new("go north\n".as_bytes());
+        let mut writer = Vec::new();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+        let result = run(
+            &mut reader,
+            &mut writer,
+            &mut game_state,
+            &game_config,
+            None,
+        );
+        let saved = save::load_game(save::AUTOSAVE_SLOT, path);
+        std::fs::remove_file(path).unwrap();
+        result.unwrap();
+        assert_eq!(saved.unwrap().room, Some(map::Coord::new(0, 1)));
+    }
+
+    /// Test that init turns write-ahead logging on, or leaves the rollback journal when it's off.
+    #[test]
+    fn init_journal_mode_test() {
+        let path = "init_journal_mode_test.db";
+        let journal_mode = || -> String {
+            let conn = rusqlite::Connection::open(path).unwrap();
+            conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))
+                .unwrap()
+        };
+        init(Some(String::from(path))).unwrap();
+        let wal = journal_mode();
+        init_with(Some(String::from(path)), false).unwrap();
+        let rollback = journal_mode();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(wal, "wal");
+        assert_eq!(rollback, "delete");
+    }
+
+    /// Test that shutting down saves the game and removes the plugin file.
+    #[test]
+    fn shutdown_test() {
+        let path = "shutdown_test.db";
+        let plugin_path = "shutdown_test.json";
+        migration::migrate_up(Some(String::from(path))).unwrap();
+        std::fs::write(plugin_path, "{}").unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(0, 1));
+        let result = shutdown(Some(&game_state), &game_config, plugin_path);
+        let saved = save::load_game(save::AUTOSAVE_SLOT, path);
+        let plugin_removed = !std::path::Path::new(plugin_path).exists();
+        std::fs::remove_file(path).unwrap();
+        result.unwrap();
+        assert_eq!(saved.unwrap().room, Some(map::Coord::new(0, 1)));
+        assert!(plugin_removed);
+    }
+
+    /// Test a script that moves, takes an item, and stops at the confirmed exit.
+    #[test]
+    fn run_script_test() {
+        let path = "run_script_test.txt";
+        std::fs::write(
+            path,
+            "go east\n\ntake sword\ndance\ntake shield\nexit\nexit\ngo west\n",
+        )
+        .unwrap();
+        let game_config = config::GameConfig::new();
+        let mut game_state = state::GameState::new();
+        let mut map = migration::map::test_area();
+        if let Some(map::GridSquare::Room(room)) = map.get_grid_square_mut(map::Coord::new(1, 2)) {
+            room.items.push(item::Item::new(
+                String::from("sword"),
+                String::from("Sharp."),
+                2,
+            ));
+        }
+        game_state.map = Some(map);
+        game_state.room = Some(map::Coord::new(1, 1));
+        let output = run_script(path, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+        let output = output.unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output[0], "Hero went east. This is room 3.");
+        assert_eq!(output[1], "You take the sword.");
+        assert_eq!(output[2], "dance is not a valid command.");
+        assert_eq!(output[4], "You don't see that here.");
+        assert_eq!(output.last().unwrap(), "Farewell hero.");
+        assert_eq!(game_state.room, Some(map::Coord::new(1, 2)));
+        assert_eq!(game_state.inventory.len(), 1);
+        assert!(run_script(path, &mut game_state, &game_config).is_err());
+    }
+
+    /// Test reading several commands from an in-memory buffer.
+    #[test]
+    fn buf_reader_commands_test() {
+        let input = "go north\ngo south\ngo west\n";
+        let mut reader = io::BufReader::new(input.as_bytes());
+        let mut writer = Vec::new();
+        let game_config = config::GameConfig::new();
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(migration::map::test_area());
+        game_state.room = Some(map::Coord::new(1, 1));
+
+        // Run each command until the buffer runs dry, like the main game loop.
+        let mut outputs = vec![];
+        loop {
+            let input =
+                prompt(&mut reader, &mut writer, &game_config).unwrap_or_else(|e| panic!("{}", e));
+            if input.is_empty() {
+                break;
+            }
+            let command =
+                crate::ret_lang::parse_input(&input[..]).unwrap_or_else(|e| panic!("{}", e));
+            let output = interpreter::interpreter(&command, &mut game_state, &game_config)
+                .unwrap_or_else(|e| panic!("{}", e));
+            outputs.push(output);
+        }
+
+        assert_eq!(
+            outputs,
+            vec![
+                "Hero went north. This is room 4.",
+                "Hero went south. This is room 1.",
+                "Hero went west. This is room 2.",
+            ]
+        );
+        assert_eq!(
+            String::from_utf8(writer).unwrap().lines().count(),
+            4,
+            "The prompt should be written once per read."
+        );
     }
 
     struct ErrMockReader {}
 
     impl LineReader for ErrMockReader {
         fn read_line(&mut self, _buf: &mut String) -> Result<usize, io::Error> {
-            Err(io::Error::new(io::ErrorKind::Other, "Mock Error"))
+            Err(io::Error::other("Mock Error"))
         }
     }
 
@@ -100,7 +697,9 @@ mod tests {
     #[test]
     fn prompt_error_test() {
         let mut reader = ErrMockReader {};
-        let input = prompt(&mut reader);
-        assert_eq!(input, Err(PROMPT_ERROR));
+        let mut writer = Vec::new();
+        let game_config = config::GameConfig::new();
+        let input = prompt(&mut reader, &mut writer, &game_config);
+        assert_eq!(input, Err(config::PROMPT_ERROR));
     }
 }
@@ -0,0 +1,111 @@
+//! # Actor
+//! Module that holds what the hero, their allies, and their enemies have in common, so combat can
+//! treat attackers and defenders the same way.
+use crate::game::ally::Ally;
+use crate::game::enemy::Enemy;
+use crate::game::player::{Player, DEFAULT_STAT};
+pub use crate::ret_lang::Stat;
+
+/// A trait for anything that has hit points and stats and can fight.
+pub trait Actor {
+    /// The current hit points of the actor.
+    fn hp(&self) -> i32;
+
+    /// A mutable reference to the current hit points of the actor.
+    fn hp_mut(&mut self) -> &mut i32;
+
+    /// The score of one of the actor's stats. Actors without stats have average scores.
+    ///
+    /// # Arguments
+    /// * `_stat` - The stat to get the score of.
+    fn stat(&self, _stat: Stat) -> i32 {
+        DEFAULT_STAT
+    }
+
+    /// Deals damage to the actor. Hit points never drop below zero.
+    ///
+    /// # Arguments
+    /// * `damage` - An i32 that is the damage dealt.
+    fn take_damage(&mut self, damage: i32) {
+        let hp = self.hp_mut();
+        *hp = (*hp - damage).max(0);
+    }
+
+    /// Whether the actor still has hit points left.
+    fn is_alive(&self) -> bool {
+        self.hp() > 0
+    }
+}
+
+impl Actor for Player {
+    fn hp(&self) -> i32 {
+        self.hp
+    }
+
+    fn hp_mut(&mut self) -> &mut i32 {
+        &mut self.hp
+    }
+
+    fn stat(&self, stat: Stat) -> i32 {
+        match stat {
+            Stat::Strength => self.strength,
+            Stat::Dexterity => self.dexterity,
+            Stat::Constitution => self.constitution,
+            Stat::Intelligence => self.intelligence,
+            Stat::Wisdom => self.wisdom,
+            Stat::Charisma => self.charisma,
+        }
+    }
+}
+
+impl Actor for Enemy {
+    fn hp(&self) -> i32 {
+        self.hp
+    }
+
+    fn hp_mut(&mut self) -> &mut i32 {
+        &mut self.hp
+    }
+}
+
+impl Actor for Ally {
+    fn hp(&self) -> i32 {
+        self.hp
+    }
+
+    fn hp_mut(&mut self) -> &mut i32 {
+        &mut self.hp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that damage stops at zero and leaves every kind of actor dead.
+    #[test]
+    fn take_damage_to_zero_test() {
+        let mut actors: Vec<Box<dyn Actor>> = vec![
+            Box::new(Player::new(String::from("Hero"))),
+            Box::new(Enemy::new(String::from("goblin"), String::new(), 6)),
+            Box::new(Ally::new(String::from("squire"), String::new(), 8)),
+        ];
+        for actor in actors.iter_mut() {
+            actor.take_damage(5);
+            assert!(actor.is_alive());
+            actor.take_damage(100);
+            assert_eq!(actor.hp(), 0);
+            assert!(!actor.is_alive());
+        }
+    }
+
+    /// Test that the hero's stats come from the hero, and others have average stats.
+    #[test]
+    fn stat_test() {
+        let mut hero = Player::new(String::from("Hero"));
+        hero.strength = 16;
+        assert_eq!(hero.stat(Stat::Strength), 16);
+        let goblin = Enemy::new(String::from("goblin"), String::new(), 6);
+        assert_eq!(goblin.stat(Stat::Wisdom), DEFAULT_STAT);
+    }
+}
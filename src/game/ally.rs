@@ -0,0 +1,58 @@
+//! # Ally
+//! Module that represents the companions who travel with the hero.
+use crate::game::item::Item;
+use serde::{Deserialize, Serialize};
+
+/// A struct that represents an ally in the hero's party.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Ally {
+    /// The name of the ally.
+    pub name: String,
+    /// The description of the ally.
+    pub description: String,
+    /// The current hit points of the ally.
+    pub hp: i32,
+    /// The most hit points the ally can have.
+    pub max_hp: i32,
+    /// The bonus the ally lends the hero's next roll, from being aided.
+    #[serde(default)]
+    pub bonus: i32,
+    /// The hold the hero has to take blows meant for the ally, from defending them.
+    #[serde(default)]
+    pub hold: u32,
+    /// The items the hero has given the ally.
+    #[serde(default)]
+    pub inventory: Vec<Item>,
+}
+
+impl Ally {
+    /// Constructor for the Ally struct.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the ally.
+    /// * `description` - A string that is the description of the ally.
+    /// * `hp` - An i32 that is the hit points the ally starts with.
+    ///
+    /// # Returns
+    /// * `Ally` - A new Ally at full hit points, with no bonus, hold, or items.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::ally;
+    ///
+    /// let squire = ally::Ally::new(String::from("squire"), String::from("A nervous squire."), 8);
+    /// assert_eq!(squire.hp, squire.max_hp);
+    /// assert_eq!(squire.bonus, 0);
+    /// ```
+    pub fn new(name: String, description: String, hp: i32) -> Ally {
+        Ally {
+            name,
+            description,
+            hp,
+            max_hp: hp,
+            bonus: 0,
+            hold: 0,
+            inventory: vec![],
+        }
+    }
+}
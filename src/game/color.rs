@@ -0,0 +1,202 @@
+//! # Color
+//! This module holds the optional ANSI coloring of the output shown in a terminal. Only the text
+//! written to the player is colored, never the plugin file.
+use crate::game::dice;
+use crate::game::interpreter::Outcome;
+use std::str::FromStr;
+
+/// The escape code that makes text bold.
+pub const BOLD: &str = "\x1b[1m";
+/// The escape code that makes text red.
+pub const RED: &str = "\x1b[31m";
+/// The escape code that makes text green.
+pub const GREEN: &str = "\x1b[32m";
+/// The escape code that turns the styling back off.
+pub const RESET: &str = "\x1b[0m";
+
+/// An enum that represents when the output is colored.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorMode {
+    /// Always color the output.
+    Always,
+    /// Never color the output.
+    Never,
+    /// Color the output when it is written to a terminal.
+    #[default]
+    Auto,
+}
+
+impl ColorMode {
+    /// A function that settles Auto into Always or Never, once it is known if the output is a
+    /// terminal. Any other mode is kept as it is.
+    ///
+    /// # Arguments
+    /// * `is_terminal` - Whether the output is written to a terminal.
+    ///
+    /// # Returns
+    /// * `ColorMode` - Always or Never.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::color::ColorMode;
+    ///
+    /// assert_eq!(ColorMode::Auto.resolve(true), ColorMode::Always);
+    /// assert_eq!(ColorMode::Auto.resolve(false), ColorMode::Never);
+    /// assert_eq!(ColorMode::Never.resolve(true), ColorMode::Never);
+    /// ```
+    pub fn resolve(self, is_terminal: bool) -> ColorMode {
+        match self {
+            ColorMode::Auto if is_terminal => ColorMode::Always,
+            ColorMode::Auto => ColorMode::Never,
+            mode => mode,
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            "auto" => Ok(ColorMode::Auto),
+            _ => Err(format!("Unknown color mode: {}", s)),
+        }
+    }
+}
+
+/// A function that wraps text in an escape code, turning the styling off after it.
+///
+/// # Arguments
+/// * `text` - A string slice that is the text to style.
+/// * `code` - A string slice that is the escape code to use.
+///
+/// # Returns
+/// * `String` - The styled text.
+pub fn paint(text: &str, code: &str) -> String {
+    format!("{}{}{}", code, text, RESET)
+}
+
+/// A function that colors the lines of an outcome. Arriving in a room is bold, the lines pushed as
+/// damage are red, and rolls are green on a hit and red on a miss. Only Always colors the lines, as Auto should
+/// be resolved against the terminal before the game starts.
+///
+/// # Arguments
+/// * `outcome` - A reference to the outcome to color.
+/// * `mode` - When to color the output.
+///
+/// # Returns
+/// * `Vec<String>` - The lines to show the player.
+///
+/// # Examples
+/// ```
+/// use retribution::game::color::{self, ColorMode};
+/// use retribution::game::interpreter::{CommandOutput, Outcome};
+///
+/// let mut output = CommandOutput::new();
+/// output.push_damage(String::from("The goblin attacks and deals 2 damage."));
+/// output.push(String::from("missile: Deals 2d4 damage to an enemy."));
+/// let outcome = Outcome::from(output);
+/// assert_eq!(
+///     color::render(&outcome, ColorMode::Always),
+///     vec![
+///         "\x1b[31mThe goblin attacks and deals 2 damage.\x1b[0m",
+///         "missile: Deals 2d4 damage to an enemy."
+///     ]
+/// );
+/// ```
+pub fn render(outcome: &Outcome, mode: ColorMode) -> Vec<String> {
+    let output = outcome.output();
+    let lines = &output.lines;
+    if mode != ColorMode::Always {
+        return lines.clone();
+    }
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match outcome {
+            Outcome::Moved { .. } if i == 0 => paint(line, BOLD),
+            Outcome::Combat { roll, .. } if *line == roll.to_string() => match roll.band {
+                dice::Outcome::Miss => paint(line, RED),
+                _ => paint(line, GREEN),
+            },
+            _ if output.is_damage(i) => paint(line, RED),
+            _ => line.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::interpreter::CommandOutput;
+    use crate::game::map::Coord;
+
+    /// Build the output of a command from its lines.
+    fn output(lines: &[&str]) -> CommandOutput {
+        CommandOutput {
+            lines: lines.iter().map(|l| String::from(*l)).collect(),
+            damage: vec![],
+        }
+    }
+
+    /// Test that Never leaves the output plain and Always wraps the known segments.
+    #[test]
+    fn render_test() {
+        let roll = dice::RollResult {
+            dice: [6, 5],
+            modifier: 0,
+            stat: None,
+            total: 11,
+            band: dice::Outcome::StrongHit,
+        };
+        let mut hit = output(&[roll.to_string().as_str()]);
+        hit.push_damage(String::from("You hit the goblin and deal 4 damage."));
+        hit.push(String::from("The goblin is defeated."));
+        let combat = Outcome::Combat {
+            output: hit,
+            roll: roll.clone(),
+        };
+        let moved = Outcome::Moved {
+            from: Coord::new(1, 1),
+            to: Coord::new(0, 1),
+            output: output(&[
+                "Hero went north. This is room 4.",
+                "A goblin appears!",
+                "The walls show damage from a fire.",
+            ]),
+        };
+        for outcome in [&combat, &moved] {
+            let plain = render(outcome, ColorMode::Never);
+            assert_eq!(plain, outcome.output().lines);
+            assert!(plain.iter().all(|l| !l.contains('\x1b')));
+        }
+
+        assert_eq!(
+            render(&combat, ColorMode::Always),
+            vec![
+                paint(roll.to_string().as_str(), GREEN),
+                paint("You hit the goblin and deal 4 damage.", RED),
+                String::from("The goblin is defeated."),
+            ]
+        );
+        assert_eq!(
+            render(&moved, ColorMode::Always),
+            vec![
+                paint("Hero went north. This is room 4.", BOLD),
+                String::from("A goblin appears!"),
+                String::from("The walls show damage from a fire."),
+            ]
+        );
+    }
+
+    /// Test reading a color mode from a flag.
+    #[test]
+    fn from_str_test() {
+        assert_eq!("Always".parse(), Ok(ColorMode::Always));
+        assert_eq!("never".parse(), Ok(ColorMode::Never));
+        assert_eq!("auto".parse(), Ok(ColorMode::Auto));
+        assert!("rainbow".parse::<ColorMode>().is_err());
+    }
+}
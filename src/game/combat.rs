@@ -0,0 +1,1568 @@
+//! # Combat
+//! A module that resolves the hero's fighting moves.
+use crate::game::actor::{Actor, Stat};
+use crate::game::ally::Ally;
+use crate::game::dice;
+use crate::game::enemy::{Disposition, Enemy};
+use crate::game::error::GameError;
+use crate::game::event::Event;
+use crate::game::interpreter;
+use crate::game::interpreter::CommandOutput;
+use crate::game::item;
+use crate::game::item::WeaponType;
+use crate::game::map;
+use crate::game::spell::SpellEffect;
+use crate::game::state;
+use crate::logging;
+use crate::ret_lang;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Message shown when there is no enemy by the given name.
+pub const NO_ENEMY_MESSAGE: &str = "There's no one here by that name.";
+/// Message shown when there is no ally by the given name.
+pub const NO_ALLY_MESSAGE: &str = "There's no ally by that name.";
+/// Message shown when attacking up close while wielding a ranged weapon.
+pub const NOT_MELEE_MESSAGE: &str = "You need a melee weapon or your fists to attack.";
+/// Message shown when volleying without a ranged weapon.
+pub const NOTHING_TO_SHOOT_MESSAGE: &str = "You have nothing to shoot with.";
+/// Message shown when volleying with a ranged weapon that has no ammo left.
+pub const OUT_OF_AMMO_MESSAGE: &str = "You're out of arrows.";
+/// Message shown when casting a spell the hero doesn't know.
+pub const UNKNOWN_SPELL_MESSAGE: &str = "You don't know that spell.";
+/// Message shown when the hero doesn't have the mana to cast a spell.
+pub const NOT_ENOUGH_MANA_MESSAGE: &str = "Not enough mana.";
+/// Message shown when the hero marks XP from a miss.
+pub const MARK_XP_MESSAGE: &str = "You mark XP.";
+/// Message shown when the hero levels up.
+pub const LEVEL_UP_MESSAGE: &str = "You leveled up!";
+/// Message shown when fleeing from a room with no open exits.
+pub const NOWHERE_TO_RUN_MESSAGE: &str = "There's nowhere to run.";
+
+/// A struct that holds the bookkeeping of a fight, kept in the GameState while the fight lasts.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct CombatState {
+    /// The places of the enemies in the hero's room, in the order they take their turns.
+    pub order: Vec<usize>,
+    /// The number of rounds fought, each being a move by the hero and the enemies' turns.
+    pub round: u32,
+    /// The penalties on the enemies' next attacks by their place in the hero's room, such as from
+    /// the hero standing in defense. A penalty is cleared once the enemy attacks.
+    pub penalties: BTreeMap<usize, i32>,
+    /// The places in the hero's room of the enemies that have already attacked this round, such
+    /// as by striking back, so they skip their turn. Cleared once the enemies have had their turns.
+    #[serde(skip)]
+    pub struck: Vec<usize>,
+}
+
+impl CombatState {
+    /// Constructor for the CombatState struct, ordering the enemies by initiative. Enemies with
+    /// the same initiative keep the order they are in.
+    ///
+    /// # Arguments
+    /// * `enemies` - A slice of the enemies in the fight.
+    ///
+    /// # Returns
+    /// * `CombatState` - A new CombatState.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::combat::CombatState;
+    /// use retribution::game::enemy::Enemy;
+    ///
+    /// let goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+    /// let mut wolf = Enemy::new(String::from("wolf"), String::from("A wolf."), 8);
+    /// wolf.initiative = 2;
+    /// let combat = CombatState::new(&[goblin, wolf]);
+    /// assert_eq!(combat.order, vec![1, 0]);
+    /// ```
+    pub fn new(enemies: &[Enemy]) -> CombatState {
+        let mut combat = CombatState::default();
+        combat.update(enemies);
+        combat
+    }
+
+    /// Brings the fight up to date with the enemies in the room. Enemies that aren't in the turn
+    /// order yet are added to the end of it, ordered by initiative among themselves, so enemies
+    /// joining a fight act after those already in it.
+    ///
+    /// # Arguments
+    /// * `enemies` - A slice of the enemies in the fight.
+    pub fn update(&mut self, enemies: &[Enemy]) {
+        self.order.retain(|i| *i < enemies.len());
+        let mut joining: Vec<usize> = (0..enemies.len())
+            .filter(|i| !self.order.contains(i))
+            .collect();
+        joining.sort_by_key(|i| std::cmp::Reverse(enemies[*i].initiative));
+        self.order.extend(joining);
+    }
+
+    /// Takes an enemy that left the room out of the fight. The enemies after it in the room move
+    /// up a place, so their places in the turn order and penalties do too.
+    ///
+    /// # Arguments
+    /// * `index` - A usize that is the place the enemy had in the room.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::combat::CombatState;
+    /// use retribution::game::enemy::Enemy;
+    ///
+    /// let goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+    /// let mut wolf = Enemy::new(String::from("wolf"), String::from("A wolf."), 8);
+    /// wolf.initiative = 2;
+    /// let mut combat = CombatState::new(&[goblin, wolf]);
+    /// combat.remove(0);
+    /// assert_eq!(combat.order, vec![0]);
+    /// ```
+    pub fn remove(&mut self, index: usize) {
+        let shift = |i: usize| if i > index { i - 1 } else { i };
+        self.order = self
+            .order
+            .iter()
+            .filter(|i| **i != index)
+            .map(|i| shift(*i))
+            .collect();
+        self.penalties = std::mem::take(&mut self.penalties)
+            .into_iter()
+            .filter(|(i, _)| *i != index)
+            .map(|(i, penalty)| (shift(i), penalty))
+            .collect();
+        self.struck = self
+            .struck
+            .iter()
+            .filter(|i| **i != index)
+            .map(|i| shift(*i))
+            .collect();
+    }
+}
+
+/// A function that marks XP for the hero after a miss, as Dungeon World does, and tells them if
+/// they leveled up.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+fn mark_xp(state: &mut state::GameState, output: &mut CommandOutput) {
+    output.push(String::from(MARK_XP_MESSAGE));
+    if state.player.gain_xp(1) {
+        output.push(String::from(LEVEL_UP_MESSAGE));
+    }
+}
+
+/// A function that rolls a move for the hero, adding the modifier of the stat it is rolled with.
+/// The bonus allies took forward from being aided is added too, and used up.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+/// * `stat` - The stat the move is rolled with, if any.
+/// * `output` - A mutable reference to the output of the move, which gets the roll.
+///
+/// # Returns
+/// * `dice::RollResult` - The roll.
+fn roll_move(
+    state: &mut state::GameState,
+    stat: Option<Stat>,
+    output: &mut CommandOutput,
+) -> dice::RollResult {
+    let mut modifier = stat.map_or(0, |s| dice::modifier(state.player.stat(s)));
+    for ally in state.allies.iter_mut().filter(|a| a.bonus != 0) {
+        output.push(format!("The {} helps you, {:+}.", ally.name, ally.bonus));
+        modifier += std::mem::take(&mut ally.bonus);
+    }
+    let roll = dice::roll_move(state.roller.as_mut(), stat, modifier);
+    output.push(roll.to_string());
+    roll
+}
+
+/// A function that finds the enemy to attack from the targets the hero named. The first target
+/// that is in the room is the enemy, and the others are reported: targets that aren't in the room
+/// as missing, and targets that are as ignored.
+///
+/// # Arguments
+/// * `targets` - A slice of the names the hero attacked.
+/// * `state` - A reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+///
+/// # Returns
+/// * `Result<(usize, String, u32), GameError>` - The place of the enemy in the room, its name,
+///   and its damage die, or an error message.
+fn find_enemy(
+    targets: &[String],
+    state: &state::GameState,
+    output: &mut CommandOutput,
+) -> Result<(usize, String, u32), GameError> {
+    let room = state
+        .current_room()
+        .ok_or(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))?;
+    let find = |target: &String| {
+        room.enemies
+            .iter()
+            .position(|e| e.name.eq_ignore_ascii_case(target))
+    };
+    let index = targets
+        .iter()
+        .find_map(find)
+        .ok_or(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))?;
+    let enemy = &room.enemies[index];
+    for target in targets.iter() {
+        match find(target) {
+            None => output.push(format!("There's no {} here.", target)),
+            Some(other) if other != index => output.push(format!(
+                "You ignore the {} for now.",
+                room.enemies[other].name
+            )),
+            Some(_) => (),
+        }
+    }
+    Ok((index, enemy.name.clone(), enemy.damage))
+}
+
+/// A function that may spawn a random enemy in the hero's room and start a fight with it. A d100
+/// roll at or under the chance spawns an enemy picked from the table. A chance of 0 never rolls.
+///
+/// # Arguments
+/// * `chance` - A u32 that is the percent chance of an encounter.
+/// * `table` - A slice of the enemies an encounter can spawn.
+/// * `state` - A mutable reference to a GameState holding the roller.
+///
+/// # Returns
+/// * `Option<String>` - The narration of the encounter, or None if there wasn't one.
+///
+/// # Examples
+/// ```
+/// use retribution::game::combat;
+/// use retribution::game::dice::FixedRoller;
+/// use retribution::game::enemy::Enemy;
+/// use retribution::game::state;
+///
+/// let mut game_state = state::GameState::new();
+/// game_state.roller = Box::new(FixedRoller::new(vec![90]));
+/// let table = vec![Enemy::new(String::from("goblin"), String::from("A goblin."), 6)];
+/// assert_eq!(combat::encounter(10, &table, &mut game_state), None);
+/// ```
+pub fn encounter(chance: u32, table: &[Enemy], state: &mut state::GameState) -> Option<String> {
+    if chance == 0 || table.is_empty() || state.roller.roll(100) > chance {
+        return None;
+    }
+    let index = state.roller.roll(table.len() as u32) as usize - 1;
+    let enemy = table.get(index)?.clone();
+    let name = enemy.name.clone();
+    state.current_room_mut()?.enemies.push(enemy);
+    engage(name.as_str(), state);
+    Some(format!("A {} appears!", name))
+}
+
+/// A function that puts the game into combat with an enemy, if it isn't already. Every enemy in
+/// the room joins the turn order.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the enemy being attacked.
+/// * `state` - A mutable reference to a GameState.
+fn engage(name: &str, state: &mut state::GameState) {
+    if state.mode != state::Mode::Combat {
+        state.events.push(Event::CombatStarted {
+            enemy: name.to_string(),
+        });
+    }
+    state.start_combat();
+}
+
+/// A function that deals damage to an enemy in the room. Defeating the last enemy ends combat.
+///
+/// # Arguments
+/// * `index` - A usize that is the place of the enemy in the room.
+/// * `damage` - An i32 that is the damage to deal.
+/// * `state` - A mutable reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+///
+/// # Returns
+/// * `Result<bool, GameError>` - True if the enemy was defeated, or an error message.
+fn wound(
+    index: usize,
+    damage: i32,
+    state: &mut state::GameState,
+    output: &mut CommandOutput,
+) -> Result<bool, GameError> {
+    let room = state
+        .current_room_mut()
+        .ok_or(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))?;
+    let enemy = room
+        .enemies
+        .get_mut(index)
+        .ok_or(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))?;
+    enemy.take_damage(damage);
+    if enemy.is_alive() {
+        return Ok(false);
+    }
+    let name = room.enemies.remove(index).name;
+    output.push(format!("The {} is defeated.", name));
+    let cleared = room.enemies.is_empty();
+    match state.combat.as_mut() {
+        _ if cleared => state.end_combat(),
+        Some(combat) => combat.remove(index),
+        None => (),
+    }
+    state.events.push(Event::EnemyDefeated { enemy: name });
+    Ok(true)
+}
+
+/// A function that resolves the hack and slash move against an enemy in the room, using a melee
+/// weapon or the hero's fists. On a 10+ the hero deals damage, on a 7-9 the hero deals damage and
+/// the enemy strikes back, and on a 6- the hero misses and marks XP. Attacking starts combat, and
+/// defeating the last enemy ends it.
+///
+/// The hero attacks one enemy at a time. When several targets are named, the first one that is
+/// in the room is attacked and the others are reported before the roll: targets that aren't in
+/// the room as missing, and targets that are as ignored.
+///
+/// # Arguments
+/// * `command` - A reference to the hack and slash command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the attack, or an error message.
+pub fn hack_and_slash(
+    command: &ret_lang::HackAndSlashCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    if state.player.weapon_type() != WeaponType::Melee {
+        return Err(GameError::Refused(NOT_MELEE_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    let (index, name, enemy_damage_die) = find_enemy(&command.target, state, &mut output)?;
+    engage(&name, state);
+
+    let roll = roll_move(state, Some(Stat::Strength), &mut output);
+    if roll.band == dice::Outcome::Miss {
+        output.push(format!("You miss the {}.", name));
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+
+    let damage = state.roller.roll(state.player.damage_die()) as i32;
+    output.push_damage(format!("You hit the {} and deal {} damage.", name, damage));
+    let defeated = wound(index, damage, state, &mut output)?;
+    if !defeated && roll.band == dice::Outcome::PartialSuccess {
+        strike_back(index, &name, enemy_damage_die, state, &mut output);
+    }
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+/// A function that has an enemy attack the hero, which uses up the enemy's turn this round.
+///
+/// # Arguments
+/// * `index` - A usize that is the place of the enemy in the room.
+/// * `name` - A string slice that is the name of the enemy.
+/// * `damage_die` - A u32 that is the sides on the enemy's damage die.
+/// * `state` - A mutable reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+fn strike_back(
+    index: usize,
+    name: &str,
+    damage_die: u32,
+    state: &mut state::GameState,
+    output: &mut CommandOutput,
+) {
+    if let Some(combat) = state.combat.as_mut() {
+        combat.struck.push(index);
+    }
+    attack(name, "strikes back", damage_die, 0, state, output);
+}
+
+/// A function that has an enemy attack the hero or one of their allies. When the hero has living
+/// allies, a die picks who is attacked. A blow meant for an ally the hero holds to defend is
+/// taken by the hero instead, spending one hold. An ally that falls leaves the party.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the enemy.
+/// * `verb` - A string slice that is how the enemy attacks, such as "attacks".
+/// * `damage_die` - A u32 that is the sides on the enemy's damage die.
+/// * `penalty` - An i32 that is taken off the damage.
+/// * `state` - A mutable reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+fn attack(
+    name: &str,
+    verb: &str,
+    damage_die: u32,
+    penalty: i32,
+    state: &mut state::GameState,
+    output: &mut CommandOutput,
+) {
+    let living: Vec<usize> = (0..state.allies.len())
+        .filter(|i| state.allies[*i].is_alive())
+        .collect();
+    let mut target = match living.len() {
+        0 => None,
+        n => (state.roller.roll(n as u32 + 1) as usize)
+            .checked_sub(2)
+            .map(|i| living[i]),
+    };
+    if let Some(ally) = target.map(|i| &mut state.allies[i]) {
+        if ally.hold > 0 {
+            ally.hold -= 1;
+            output.push(format!("You take the blow meant for the {}.", ally.name));
+            target = None;
+        }
+    }
+    let damage = (state.roller.roll(damage_die) as i32 - penalty).max(0);
+    let Some(index) = target else {
+        state.player.take_damage(damage);
+        output.push_damage(format!(
+            "The {} {} and deals {} damage.",
+            name, verb, damage
+        ));
+        if !state.player.is_alive() {
+            output.push(String::from("You have fallen."));
+        }
+        return;
+    };
+    let ally = &mut state.allies[index];
+    ally.take_damage(damage);
+    output.push_damage(format!(
+        "The {} {}, hitting the {} for {} damage.",
+        name, verb, ally.name, damage
+    ));
+    if !ally.is_alive() {
+        output.push(format!("The {} has fallen.", ally.name));
+        state.allies.remove(index);
+    }
+}
+
+/// A function that resolves parleying with an enemy, rolling with charisma. On a 10+ the enemy
+/// yields, and on a 7-9 it yields once it is handed the first item the hero can spare, refusing
+/// if they have nothing to spare. On a 6- it won't listen and the hero marks XP. A yielding enemy leaves
+/// the fight: a hostile one flees the room and a friendly one joins the hero as an ally. Once
+/// the last enemy yields, the fight ends.
+///
+/// # Arguments
+/// * `command` - A reference to the parley command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the parley, or an error message.
+pub fn parley(
+    command: &ret_lang::ParleyCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    let mut output = CommandOutput::new();
+    let (index, name, _) = find_enemy(std::slice::from_ref(&command.target), state, &mut output)?;
+    engage(&name, state);
+
+    let roll = roll_move(state, Some(Stat::Charisma), &mut output);
+    if roll.band == dice::Outcome::Miss {
+        output.push(format!("The {} won't listen.", name));
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+    if roll.band == dice::Outcome::PartialSuccess {
+        let Some(spare) = spare_item(state) else {
+            output.push(format!(
+                "The {} demands something first, but you have nothing to give.",
+                name
+            ));
+            return Ok(interpreter::Outcome::Combat { roll, output });
+        };
+        let price = item::remove(&mut state.inventory, spare, Some(1));
+        output.push(format!(
+            "The {} demands your {} first, and you hand it over.",
+            name, price.name
+        ));
+    }
+
+    let room = state
+        .current_room_mut()
+        .ok_or(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))?;
+    if index >= room.enemies.len() {
+        return Err(GameError::NoSuchTarget(NO_ENEMY_MESSAGE));
+    }
+    let enemy = room.enemies.remove(index);
+    let cleared = room.enemies.is_empty();
+    match enemy.disposition {
+        Disposition::Hostile => output.push(format!("The {} yields and flees.", name)),
+        Disposition::Friendly => {
+            output.push(format!("The {} yields and joins you.", name));
+            let mut ally = Ally::new(enemy.name, enemy.description, enemy.max_hp);
+            ally.hp = enemy.hp;
+            state.recruit(ally);
+        }
+    }
+    match state.combat.as_mut() {
+        _ if cleared => state.end_combat(),
+        Some(combat) => combat.remove(index),
+        None => (),
+    }
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+/// A function that finds the first item in the hero's pack they can spare in a parley. Lit
+/// lights and the keys to the portals of the current map are kept. The wielded weapon isn't in
+/// the pack, so it is never offered.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Option<usize>` - The place of the item in the pack, or None if there is nothing to spare.
+fn spare_item(state: &state::GameState) -> Option<usize> {
+    let keys: Vec<&String> = state
+        .map
+        .as_ref()
+        .map(|m| {
+            m.portals()
+                .filter_map(|(_, p)| p.required_item.as_ref())
+                .collect()
+        })
+        .unwrap_or_default();
+    state
+        .inventory
+        .iter()
+        .position(|i| !i.lit && !keys.iter().any(|k| k.eq_ignore_ascii_case(&i.name)))
+}
+
+/// A function that gives every enemy still in the fight its turn, after the hero's move. The
+/// enemies act in the turn order of the fight, and any that already struck back this round skip
+/// their turn. Each enemy rolls its damage die, less any pending penalty, and deals that to the
+/// hero or one of their allies. The penalty is cleared once it is applied, and the round ends. Nothing happens if the
+/// fight is over or the hero has fallen.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+/// * `output` - A mutable reference to the output of the move.
+///
+/// # Examples
+/// ```
+/// use retribution::game::combat;
+/// use retribution::game::dice::FixedRoller;
+/// use retribution::game::enemy::Enemy;
+/// use retribution::game::interpreter::CommandOutput;
+/// use retribution::game::map::Coord;
+/// use retribution::game::state::GameState;
+/// use retribution::migration;
+///
+/// let mut game_state = GameState::new();
+/// game_state.map = Some(migration::map::test_area());
+/// game_state.room = Some(Coord::new(1, 1));
+/// let goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+/// game_state.current_room_mut().unwrap().enemies.push(goblin);
+/// game_state.start_combat();
+/// game_state.roller = Box::new(FixedRoller::new(vec![3]));
+/// let mut output = CommandOutput::new();
+/// combat::enemy_turns(&mut game_state, &mut output);
+/// assert_eq!(output.lines, vec!["The goblin attacks and deals 3 damage."]);
+/// ```
+pub fn enemy_turns(state: &mut state::GameState, output: &mut CommandOutput) {
+    if state.mode != state::Mode::Combat {
+        return;
+    }
+    let (order, struck) = state
+        .combat
+        .as_mut()
+        .map(|c| (c.order.clone(), std::mem::take(&mut c.struck)))
+        .unwrap_or_default();
+    let enemies: Vec<(usize, String, u32)> = state
+        .current_room()
+        .map(|r| {
+            order
+                .iter()
+                .filter(|i| !struck.contains(i))
+                .filter_map(|i| r.enemies.get(*i).map(|e| (*i, e)))
+                .filter(|(_, e)| e.is_alive())
+                .map(|(i, e)| (i, e.name.clone(), e.damage))
+                .collect()
+        })
+        .unwrap_or_default();
+    for (index, name, damage_die) in enemies {
+        if !state.player.is_alive() {
+            break;
+        }
+        let penalty = state
+            .combat
+            .as_mut()
+            .and_then(|c| c.penalties.remove(&index))
+            .unwrap_or(0);
+        attack(&name, "attacks", damage_die, penalty, state, output);
+    }
+    if let Some(combat) = state.combat.as_mut() {
+        combat.round += 1;
+    }
+}
+
+/// A function that finds the rooms next to the hero's room that they can move into.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Vec<(ret_lang::Direction, map::Coord)>` - The direction and coordinates of each open exit.
+fn open_exits(state: &state::GameState) -> Vec<(ret_lang::Direction, map::Coord)> {
+    let (Some(current_map), Some(coord)) = (state.map.as_ref(), state.room) else {
+        return vec![];
+    };
+    [
+        (ret_lang::Direction::North, coord.north()),
+        (ret_lang::Direction::South, coord.south()),
+        (ret_lang::Direction::East, coord.east()),
+        (ret_lang::Direction::West, coord.west()),
+    ]
+    .into_iter()
+    .filter_map(|(direction, c)| match current_map.get_grid_square(c?) {
+        Some(map::GridSquare::Room(_)) => Some((direction, c?)),
+        _ => None,
+    })
+    .collect()
+}
+
+/// A function that resolves fleeing a fight, rolling with dexterity. On a 7+ the hero escapes to
+/// a random open room next to them and the fight ends, and on a 6- an enemy gets a free attack and
+/// the hero marks XP. An escape is a move from one room to another, so plugins see it as one.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the escape, or an error message.
+pub fn flee(state: &mut state::GameState) -> Result<interpreter::Outcome, GameError> {
+    let exits = open_exits(state);
+    if exits.is_empty() {
+        return Err(GameError::Refused(NOWHERE_TO_RUN_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    let roll = roll_move(state, Some(Stat::Dexterity), &mut output);
+    if roll.band == dice::Outcome::Miss {
+        output.push(String::from("You fail to get away."));
+        let enemy = state
+            .current_room()
+            .and_then(|r| r.enemies.first())
+            .map(|e| (e.name.clone(), e.damage));
+        if let Some((name, damage_die)) = enemy {
+            strike_back(0, &name, damage_die, state, &mut output);
+        }
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+
+    let from = state
+        .room
+        .ok_or(GameError::Refused(NOWHERE_TO_RUN_MESSAGE))?;
+    let index = state.roller.roll(exits.len() as u32) as usize - 1;
+    let (direction, coord) = exits[index];
+    state.previous = state.current_map_name().map(|n| (n.to_string(), from));
+    state.room = Some(coord);
+    state.end_combat();
+    state.arrive();
+    let description = state
+        .current_room()
+        .map(|r| state.describe(r))
+        .unwrap_or_default();
+    output.push(format!("You flee {}. {}", direction, description));
+    Ok(interpreter::Outcome::Moved {
+        from,
+        to: coord,
+        output,
+    })
+}
+
+/// A function that spends one ammo from the hero's ranged weapon.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `bool` - True if there was ammo to spend.
+fn spend_ammo(state: &mut state::GameState) -> bool {
+    match state.player.weapon.as_mut() {
+        Some(weapon) if weapon.ammo > 0 => {
+            weapon.ammo -= 1;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// A function that resolves the volley move against an enemy in the room, which needs a ranged
+/// weapon with ammo. Every shot spends one ammo. On a 10+ the hero deals damage, on a 7-9 the
+/// hero deals damage but has to take several shots, spending another ammo, or move into danger
+/// when there's no ammo to spare, and on a 6- the hero misses and marks XP.
+///
+/// # Arguments
+/// * `command` - A reference to the volley command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the shot, or an error message.
+pub fn volley(
+    command: &ret_lang::VolleyCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    if state.player.weapon_type() != WeaponType::Ranged {
+        return Err(GameError::Refused(NOTHING_TO_SHOOT_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    let (index, name, _) = find_enemy(std::slice::from_ref(&command.target), state, &mut output)?;
+    if !spend_ammo(state) {
+        return Err(GameError::Refused(OUT_OF_AMMO_MESSAGE));
+    }
+    engage(&name, state);
+
+    let roll = roll_move(state, Some(Stat::Dexterity), &mut output);
+    if roll.band == dice::Outcome::Miss {
+        output.push(format!("You miss the {}.", name));
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+
+    let damage = state.roller.roll(state.player.damage_die()) as i32;
+    if roll.band == dice::Outcome::PartialSuccess {
+        if spend_ammo(state) {
+            output.push(String::from(
+                "You have to take several shots, spending extra ammo.",
+            ));
+        } else {
+            output.push(String::from(
+                "You have to move to get the shot, placing you in danger.",
+            ));
+        }
+    }
+    output.push_damage(format!(
+        "You shoot the {} and deal {} damage.",
+        name, damage
+    ));
+    wound(index, damage, state, &mut output)?;
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+/// A function that resolves the cast a spell move, rolling with intelligence. The spell's mana is
+/// spent on every cast. On a 10+ the spell takes effect, on a 7-9 it takes effect but draws
+/// unwanted attention, and on a 6- the mana is wasted and the hero marks XP.
+///
+/// # Arguments
+/// * `command` - A reference to the cast command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the spell, or an error message.
+pub fn cast(
+    command: &ret_lang::CastCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    let spell = state
+        .player
+        .known_spells()
+        .into_iter()
+        .find(|s| s.name.eq_ignore_ascii_case(command.spell_name.as_str()))
+        .ok_or(GameError::NoSuchTarget(UNKNOWN_SPELL_MESSAGE))?;
+    if state.player.mana < spell.cost {
+        return Err(GameError::Refused(NOT_ENOUGH_MANA_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    let enemy = match spell.effect {
+        SpellEffect::Damage { .. } => {
+            let targets: Vec<String> = command.target.iter().cloned().collect();
+            let (index, name, _) = find_enemy(&targets, state, &mut output)?;
+            engage(&name, state);
+            Some((index, name))
+        }
+        _ => None,
+    };
+    state.player.mana -= spell.cost;
+
+    let roll = roll_move(state, Some(Stat::Intelligence), &mut output);
+    if roll.band == dice::Outcome::Miss {
+        output.push(format!("The {} spell fizzles.", spell.name));
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+
+    match (spell.effect, enemy) {
+        (SpellEffect::Light, _) => output.push(String::from("Light fills the room.")),
+        (SpellEffect::Damage { dice, sides }, Some((index, name))) => {
+            let damage = roll_damage(state, dice, sides);
+            output.push_damage(format!(
+                "Your {} hits the {} for {} damage.",
+                spell.name, name, damage
+            ));
+            wound(index, damage, state, &mut output)?;
+        }
+        (SpellEffect::DamageAll { dice, sides }, _) => {
+            let names: Vec<String> = state
+                .current_room()
+                .map(|r| r.enemies.iter().map(|e| e.name.clone()).collect())
+                .unwrap_or_default();
+            if let Some(first) = names.first() {
+                engage(first, state);
+            }
+            let damage = roll_damage(state, dice, sides);
+            output.push_damage(format!(
+                "Your {} deals {} damage to every enemy in the room.",
+                spell.name, damage
+            ));
+            // Defeated enemies leave the room, so the next enemy moves into their place.
+            let mut index = 0;
+            for _ in names.iter() {
+                if !wound(index, damage, state, &mut output)? {
+                    index += 1;
+                }
+            }
+        }
+        (SpellEffect::Heal { sides }, _) => {
+            let roll = state.roller.roll(sides) as i32;
+            let healed = state.player.heal(roll);
+            output.push(format!("You heal {} HP.", healed));
+        }
+        (SpellEffect::Damage { .. }, None) => {
+            return Err(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))
+        }
+    }
+    if roll.band == dice::Outcome::PartialSuccess {
+        output.push(String::from("The spell draws unwanted attention."));
+    }
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+/// A function that rolls several dice and adds them together.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState holding the roller.
+/// * `dice` - A u32 that is the number of dice to roll.
+/// * `sides` - A u32 that is the number of sides on each die.
+///
+/// # Returns
+/// * `i32` - The total of the dice.
+fn roll_damage(state: &mut state::GameState, dice: u32, sides: u32) -> i32 {
+    let total = (0..dice).map(|_| state.roller.roll(sides) as i32).sum();
+    logging::trace!("Rolled {}d{} = {} damage.", dice, sides, total);
+    total
+}
+
+/// A function that resolves the aid move on an ally. On a 10+ the ally takes +1 forward, lending
+/// it to the hero's next roll, and on a 7-9 they take +1 forward but the hero is exposed to
+/// danger.
+///
+/// # Arguments
+/// * `command` - A reference to the aid command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the aid, or an error message.
+pub fn aid(
+    command: &ret_lang::AidCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    state
+        .ally_mut(command.target.as_str())
+        .ok_or(GameError::NoSuchTarget(NO_ALLY_MESSAGE))?;
+    let mut output = CommandOutput::new();
+    let roll = roll_move(state, None, &mut output);
+    let ally = state
+        .ally_mut(command.target.as_str())
+        .ok_or(GameError::NoSuchTarget(NO_ALLY_MESSAGE))?;
+    if roll.band == dice::Outcome::Miss {
+        output.push(format!("You fail to aid the {}.", ally.name));
+        mark_xp(state, &mut output);
+        return Ok(interpreter::Outcome::Combat { roll, output });
+    }
+    ally.bonus += 1;
+    output.push(format!("You aid the {}. They take +1 forward.", ally.name));
+    if roll.band == dice::Outcome::PartialSuccess {
+        output.push(String::from("You are exposed to danger."));
+    }
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+/// A function that resolves the defend move on an ally, rolling with constitution. On a 10+ the
+/// hero holds 3, and on a 7-9 they hold 1. Each hold is spent taking a blow meant for the ally.
+/// Standing in defense also gives every enemy in the room -1 to its next attack.
+///
+/// # Arguments
+/// * `command` - A reference to the defend command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<interpreter::Outcome, GameError>` - The narration of the defense, or an error message.
+pub fn defend(
+    command: &ret_lang::DefendCommand,
+    state: &mut state::GameState,
+) -> Result<interpreter::Outcome, GameError> {
+    state
+        .ally_mut(command.target.as_str())
+        .ok_or(GameError::NoSuchTarget(NO_ALLY_MESSAGE))?;
+    let mut output = CommandOutput::new();
+    let roll = roll_move(state, Some(Stat::Constitution), &mut output);
+    let ally = state
+        .ally_mut(command.target.as_str())
+        .ok_or(GameError::NoSuchTarget(NO_ALLY_MESSAGE))?;
+    let hold = match roll.band {
+        dice::Outcome::Miss => {
+            output.push(format!("You fail to defend the {}.", ally.name));
+            mark_xp(state, &mut output);
+            return Ok(interpreter::Outcome::Combat { roll, output });
+        }
+        dice::Outcome::PartialSuccess => 1,
+        dice::Outcome::StrongHit => 3,
+    };
+    ally.hold += hold;
+    output.push(format!(
+        "You stand ready to defend the {}. Hold {}.",
+        ally.name, ally.hold
+    ));
+    let count = state.current_room().map_or(0, |r| r.enemies.len());
+    if let Some(combat) = state.combat.as_mut() {
+        combat.penalties.extend((0..count).map(|i| (i, 1)));
+    }
+    Ok(interpreter::Outcome::Combat { roll, output })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::dice::FixedRoller;
+    use crate::game::item::Item;
+    use crate::game::map::Coord;
+    use crate::migration::map;
+
+    /// Create a game state in room 1 of the test area with a goblin, and a fixed roller.
+    fn test_state(rolls: Vec<u32>) -> state::GameState {
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(map::test_area());
+        game_state.room = Some(Coord::new(1, 1));
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        game_state.roller = Box::new(FixedRoller::new(rolls));
+        game_state
+    }
+
+    /// Test that a low encounter roll spawns an enemy and starts a fight, and a high one doesn't.
+    #[test]
+    fn encounter_test() {
+        let table = vec![
+            Enemy::new(String::from("goblin"), String::from("A goblin."), 6),
+            Enemy::new(String::from("wolf"), String::from("A wolf."), 8),
+        ];
+        let mut game_state = test_state(vec![11]);
+        game_state.current_room_mut().unwrap().enemies.clear();
+        assert_eq!(encounter(10, &table, &mut game_state), None);
+        assert_eq!(game_state.mode, state::Mode::Travel);
+        assert!(game_state.current_room().unwrap().enemies.is_empty());
+
+        game_state.roller = Box::new(FixedRoller::new(vec![10, 1]));
+        assert_eq!(
+            encounter(10, &table, &mut game_state),
+            Some(String::from("A goblin appears!"))
+        );
+        assert_eq!(game_state.mode, state::Mode::Combat);
+        assert_eq!(game_state.current_room().unwrap().enemies[0].name, "goblin");
+    }
+
+    /// Test that a chance of 0 never spawns an enemy, and doesn't use up a roll.
+    #[test]
+    fn encounter_no_chance_test() {
+        let table = vec![Enemy::new(
+            String::from("goblin"),
+            String::from("A goblin."),
+            6,
+        )];
+        let mut game_state = test_state(vec![1, 1]);
+        game_state.current_room_mut().unwrap().enemies.clear();
+        assert_eq!(encounter(0, &table, &mut game_state), None);
+        assert!(game_state.current_room().unwrap().enemies.is_empty());
+        assert_eq!(
+            encounter(10, &table, &mut game_state),
+            Some(String::from("A goblin appears!"))
+        );
+    }
+
+    /// Test that starting a fight fills in its bookkeeping and ending it clears it.
+    #[test]
+    fn combat_state_test() {
+        let mut game_state = test_state(vec![]);
+        assert!(game_state.combat.is_none());
+        engage("goblin", &mut game_state);
+        let combat = game_state.combat.as_ref().unwrap();
+        assert_eq!(combat.order, vec![0]);
+        assert_eq!(combat.round, 0);
+
+        let mut output = CommandOutput::new();
+        wound(0, 4, &mut game_state, &mut output).unwrap();
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 6);
+        wound(0, 6, &mut game_state, &mut output).unwrap();
+        assert_eq!(game_state.mode, state::Mode::Travel);
+        assert!(game_state.combat.is_none());
+    }
+
+    /// Test that enemies sharing a name keep their own turns and penalties, and that defeating
+    /// one moves the others up a place.
+    #[test]
+    fn combat_state_same_names_test() {
+        let mut game_state = test_state(vec![]);
+        let mut quick = Enemy::new(String::from("goblin"), String::from("A goblin."), 10);
+        quick.initiative = 2;
+        game_state.current_room_mut().unwrap().enemies.push(quick);
+        engage("goblin", &mut game_state);
+        let combat = game_state.combat.as_mut().unwrap();
+        assert_eq!(combat.order, vec![1, 0]);
+        combat.penalties.insert(1, 2);
+
+        let mut output = CommandOutput::new();
+        assert!(wound(0, 10, &mut game_state, &mut output).unwrap());
+        let combat = game_state.combat.as_ref().unwrap();
+        assert_eq!(combat.order, vec![0]);
+        assert_eq!(combat.penalties[&0], 2);
+        let goblin = &game_state.current_room().unwrap().enemies[0];
+        assert_eq!(goblin.initiative, 2);
+    }
+
+    /// Test that an enemy's penalty is taken off its attack and then cleared.
+    #[test]
+    fn enemy_turns_penalty_test() {
+        let mut game_state = test_state(vec![3]);
+        game_state.start_combat();
+        let combat = game_state.combat.as_mut().unwrap();
+        combat.penalties.insert(0, 2);
+        let hp = game_state.player.hp;
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(output.lines, vec!["The goblin attacks and deals 1 damage."]);
+        assert!(game_state.combat.as_ref().unwrap().penalties.is_empty());
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(output.lines[1], "The goblin attacks and deals 3 damage.");
+        assert_eq!(game_state.player.hp, hp - 4);
+        assert_eq!(game_state.combat.as_ref().unwrap().round, 2);
+
+        // Once the fight is over, no one attacks.
+        game_state.end_combat();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(output.lines.len(), 2);
+    }
+
+    /// Test that enemies take their turns in initiative order, skipping the dead.
+    #[test]
+    fn enemy_turns_initiative_test() {
+        let mut game_state = test_state(vec![2, 4]);
+        let mut wolf = Enemy::new(String::from("wolf"), String::from("A wolf."), 8);
+        wolf.initiative = 3;
+        game_state.current_room_mut().unwrap().enemies.push(wolf);
+        engage("goblin", &mut game_state);
+        assert_eq!(game_state.combat.as_ref().unwrap().order, vec![1, 0]);
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(
+            output.lines,
+            vec![
+                "The wolf attacks and deals 2 damage.",
+                "The goblin attacks and deals 4 damage."
+            ]
+        );
+
+        game_state.current_room_mut().unwrap().enemies[1].hp = 0;
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(output.lines, vec!["The goblin attacks and deals 2 damage."]);
+    }
+
+    /// Test fleeing a fight into the only open room.
+    #[test]
+    fn flee_success_test() {
+        let mut game_state = test_state(vec![4, 3, 1]);
+        game_state.mode = state::Mode::Combat;
+        let output = flee(&mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 4+3 (+0 DEX) = 7: partial success.",
+                "You flee north. This is room 4."
+            ]
+        );
+        assert!(matches!(
+            output,
+            interpreter::Outcome::Moved { from, to, .. }
+                if from == Coord::new(1, 1) && to == Coord::new(0, 1)
+        ));
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+        assert_eq!(
+            game_state.previous,
+            Some((String::from("Test Area"), Coord::new(1, 1)))
+        );
+        assert_eq!(game_state.mode, state::Mode::Travel);
+    }
+
+    /// Test failing to flee, which gives the enemy a free attack.
+    #[test]
+    fn flee_failure_test() {
+        let mut game_state = test_state(vec![1, 2, 5]);
+        game_state.mode = state::Mode::Combat;
+        let output = flee(&mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 1+2 (+0 DEX) = 3: miss.",
+                "You fail to get away.",
+                "The goblin strikes back and deals 5 damage.",
+                MARK_XP_MESSAGE
+            ]
+        );
+        assert_eq!(game_state.room, Some(Coord::new(1, 1)));
+        assert_eq!(game_state.mode, state::Mode::Combat);
+        assert_eq!(game_state.player.hp, game_state.player.max_hp - 5);
+    }
+
+    /// Test fleeing from a room with no open exits.
+    #[test]
+    fn flee_nowhere_test() {
+        let mut game_state = test_state(vec![6, 6]);
+        // The only way out of test area 2 is through a portal.
+        game_state.map = Some(map::test_area_2());
+        game_state.room = Some(Coord::new(1, 0));
+        assert_eq!(
+            flee(&mut game_state).err(),
+            Some(GameError::Refused(NOWHERE_TO_RUN_MESSAGE))
+        );
+    }
+
+    /// Test a strong hit that doesn't finish the enemy.
+    #[test]
+    fn hack_and_slash_strong_hit_test() {
+        let mut game_state = test_state(vec![5, 5, 3]);
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 5+5 (+0 STR) = 10: strong hit.",
+                "You hit the goblin and deal 3 damage."
+            ]
+        );
+        assert!(matches!(
+            output,
+            interpreter::Outcome::Combat { roll, .. } if roll.band == dice::Outcome::StrongHit
+        ));
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 7);
+        assert!(matches!(game_state.mode, state::Mode::Combat));
+    }
+
+    /// Test a weak hit where the enemy strikes back.
+    #[test]
+    fn hack_and_slash_weak_hit_test() {
+        let mut game_state = test_state(vec![3, 4, 2, 5]);
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 3+4 (+0 STR) = 7: partial success.",
+                "You hit the goblin and deal 2 damage.",
+                "The goblin strikes back and deals 5 damage."
+            ]
+        );
+        assert_eq!(game_state.player.hp, game_state.player.max_hp - 5);
+    }
+
+    /// Test a miss and defeating the last enemy.
+    #[test]
+    fn hack_and_slash_miss_and_defeat_test() {
+        let mut game_state = test_state(vec![1, 1, 6, 6, 4]);
+        game_state.current_room_mut().unwrap().enemies[0].hp = 4;
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 1+1 (+0 STR) = 2: miss.",
+                "You miss the goblin.",
+                "You mark XP."
+            ]
+        );
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines[2], "The goblin is defeated.");
+        assert!(game_state.current_room().unwrap().enemies.is_empty());
+        assert!(matches!(game_state.mode, state::Mode::Travel));
+    }
+
+    /// Test attacking two targets, where only one is in the room.
+    #[test]
+    fn hack_and_slash_two_targets_test() {
+        let mut game_state = test_state(vec![5, 5, 3]);
+        let command =
+            ret_lang::HackAndSlashCommand::build(vec!["attack", "orc", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "There's no orc here.",
+                "You rolled 5+5 (+0 STR) = 10: strong hit.",
+                "You hit the goblin and deal 3 damage."
+            ]
+        );
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 7);
+    }
+
+    /// Test attacking two enemies that are both in the room, which only attacks the first.
+    #[test]
+    fn hack_and_slash_ignored_target_test() {
+        let mut game_state = test_state(vec![5, 5, 3]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(String::from("orc"), String::from("An orc."), 12));
+        let command =
+            ret_lang::HackAndSlashCommand::build(vec!["attack", "orc", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines[0], "You ignore the goblin for now.");
+        assert_eq!(
+            output.output().lines[2],
+            "You hit the orc and deal 3 damage."
+        );
+        let enemies = &game_state.current_room().unwrap().enemies;
+        assert_eq!((enemies[0].hp, enemies[1].hp), (10, 9));
+    }
+
+    /// Test attacking an enemy that isn't there.
+    #[test]
+    fn hack_and_slash_no_enemy_test() {
+        let mut game_state = test_state(vec![6]);
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "orc"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state);
+        assert_eq!(
+            output.err(),
+            Some(GameError::NoSuchTarget(NO_ENEMY_MESSAGE))
+        );
+    }
+
+    /// Test that volley needs a ranged weapon, and hack and slash a melee one.
+    #[test]
+    fn volley_ranged_weapon_test() {
+        let mut game_state = test_state(vec![5, 5, 4]);
+        let volley_command = ret_lang::VolleyCommand::build(vec!["volley", "goblin"]).unwrap();
+        assert_eq!(
+            volley(&volley_command, &mut game_state).err(),
+            Some(GameError::Refused(NOTHING_TO_SHOOT_MESSAGE))
+        );
+        assert!(matches!(game_state.mode, state::Mode::Travel));
+
+        game_state.player.weapon = Some(Item::ranged_weapon(
+            String::from("bow"),
+            String::from("A bow."),
+            1,
+            6,
+        ));
+        let output = volley(&volley_command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 5+5 (+0 DEX) = 10: strong hit.",
+                "You shoot the goblin and deal 4 damage."
+            ]
+        );
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 6);
+
+        let attack_command =
+            ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        assert_eq!(
+            hack_and_slash(&attack_command, &mut game_state).err(),
+            Some(GameError::Refused(NOT_MELEE_MESSAGE))
+        );
+    }
+
+    /// Test that each volley spends ammo until the hero runs out.
+    #[test]
+    fn volley_out_of_ammo_test() {
+        let mut game_state = test_state(vec![1, 1, 4, 4, 1]);
+        game_state.current_room_mut().unwrap().enemies[0].hp = 100;
+        game_state.player.weapon = Some(Item::ranged_weapon(
+            String::from("bow"),
+            String::from("A bow."),
+            1,
+            6,
+        ));
+        let command = ret_lang::VolleyCommand::build(vec!["volley", "goblin"]).unwrap();
+
+        // A miss spends the one arrow that was shot.
+        volley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(game_state.player.weapon.as_ref().unwrap().ammo, 2);
+
+        // A 7-9 spends an extra arrow.
+        let output = volley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1],
+            "You have to take several shots, spending extra ammo."
+        );
+        assert_eq!(game_state.player.weapon.as_ref().unwrap().ammo, 0);
+
+        assert_eq!(
+            volley(&command, &mut game_state).err(),
+            Some(GameError::Refused(OUT_OF_AMMO_MESSAGE))
+        );
+    }
+
+    /// Test casting a spell with enough mana, and without.
+    #[test]
+    fn cast_mana_test() {
+        let mut game_state = test_state(vec![5, 5, 3, 4, 1, 1]);
+        game_state.player.learn_spell("missile").unwrap();
+        let command = ret_lang::CastCommand::build(vec!["cast", "missile", "goblin"]).unwrap();
+        let output = cast(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 5+5 (+0 INT) = 10: strong hit.",
+                "Your missile hits the goblin for 7 damage."
+            ]
+        );
+        assert_eq!(game_state.player.mana, 8);
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 3);
+
+        // A miss still spends the mana.
+        let output = cast(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines[1], "The missile spell fizzles.");
+        assert_eq!(game_state.player.mana, 6);
+
+        game_state.player.mana = 1;
+        assert_eq!(
+            cast(&command, &mut game_state).err(),
+            Some(GameError::Refused(NOT_ENOUGH_MANA_MESSAGE))
+        );
+        assert_eq!(game_state.player.mana, 1);
+
+        let command = ret_lang::CastCommand::build(vec!["cast", "fireball"]).unwrap();
+        assert_eq!(
+            cast(&command, &mut game_state).err(),
+            Some(GameError::NoSuchTarget(UNKNOWN_SPELL_MESSAGE))
+        );
+    }
+
+    /// Test that aiding a named ally stores the bonus on them.
+    #[test]
+    fn aid_ally_test() {
+        let mut game_state = test_state(vec![4, 4]);
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            8,
+        ));
+        let command = ret_lang::AidCommand::build(vec!["aid", "Squire"]).unwrap();
+        let output = aid(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 4+4 (+0) = 8: partial success.",
+                "You aid the squire. They take +1 forward.",
+                "You are exposed to danger."
+            ]
+        );
+        assert_eq!(game_state.allies[0].bonus, 1);
+
+        let command = ret_lang::AidCommand::build(vec!["aid", "knight"]).unwrap();
+        assert_eq!(
+            aid(&command, &mut game_state).err(),
+            Some(GameError::NoSuchTarget(NO_ALLY_MESSAGE))
+        );
+    }
+
+    /// Test that on a 7-9 an enemy demands an item before it yields, and a friendly one joins
+    /// the hero.
+    #[test]
+    fn parley_partial_test() {
+        let mut game_state = test_state(vec![4, 4]);
+        game_state.current_room_mut().unwrap().enemies[0].disposition = Disposition::Friendly;
+        let command = ret_lang::ParleyCommand::build(vec!["parley", "goblin"]).unwrap();
+        let output = parley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1..],
+            ["The goblin demands something first, but you have nothing to give."]
+        );
+        assert_eq!(game_state.mode, state::Mode::Combat);
+
+        game_state.inventory.push(Item::new(
+            String::from("torch"),
+            String::from("A torch."),
+            1,
+        ));
+        let output = parley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1..],
+            [
+                "The goblin demands your torch first, and you hand it over.",
+                "The goblin yields and joins you."
+            ]
+        );
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.allies[0].name, "goblin");
+        assert!(game_state.combat.is_none());
+    }
+
+    /// Test that on a 7-9 the hero keeps their keys and lit lights, and hands over something
+    /// they can spare.
+    #[test]
+    fn parley_partial_spare_test() {
+        let mut game_state = test_state(vec![4, 4]);
+        if let Some(crate::game::map::GridSquare::Portal(portal)) = game_state
+            .map
+            .as_mut()
+            .unwrap()
+            .get_grid_square_mut(Coord::new(2, 1))
+        {
+            portal.required_item = Some(String::from("key"));
+        }
+        let mut torch = Item::new(String::from("torch"), String::from("A torch."), 1);
+        torch.light = true;
+        torch.lit = true;
+        let key = Item::new(String::from("key"), String::from("A key."), 0);
+        game_state.inventory = vec![key, torch];
+        let command = ret_lang::ParleyCommand::build(vec!["parley", "goblin"]).unwrap();
+        let output = parley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1..],
+            ["The goblin demands something first, but you have nothing to give."]
+        );
+        assert_eq!(game_state.inventory.len(), 2);
+
+        let bread = Item::new(String::from("bread"), String::from("Some bread."), 1);
+        game_state.inventory.push(bread);
+        let output = parley(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1],
+            "The goblin demands your bread first, and you hand it over."
+        );
+        assert_eq!(game_state.inventory.len(), 2);
+    }
+
+    /// Test that defending an ally gives the hero hold to protect them.
+    #[test]
+    fn defend_ally_test() {
+        let mut game_state = test_state(vec![6, 5, 1, 2]);
+        game_state.start_combat();
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            8,
+        ));
+        let command = ret_lang::DefendCommand::build(vec!["defend", "squire"]).unwrap();
+        let output = defend(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 6+5 (+0 CON) = 11: strong hit.",
+                "You stand ready to defend the squire. Hold 3."
+            ]
+        );
+        assert_eq!(game_state.combat.as_ref().unwrap().penalties[&0], 1);
+        let output = defend(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "You rolled 1+2 (+0 CON) = 3: miss.",
+                "You fail to defend the squire.",
+                "You mark XP."
+            ]
+        );
+        assert_eq!(game_state.allies[0].hold, 3);
+    }
+
+    /// Test that the bonus from aiding an ally is added to the hero's next roll and used up.
+    #[test]
+    fn aid_bonus_test() {
+        let mut game_state = test_state(vec![4, 4, 5, 4, 2]);
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            8,
+        ));
+        let command = ret_lang::AidCommand::build(vec!["aid", "squire"]).unwrap();
+        aid(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "The squire helps you, +1.",
+                "You rolled 5+4 (+1 STR) = 10: strong hit.",
+                "You hit the goblin and deal 2 damage."
+            ]
+        );
+        assert_eq!(game_state.allies[0].bonus, 0);
+    }
+
+    /// Test that the hero spends hold to take a blow meant for the ally they defend.
+    #[test]
+    fn defend_hold_test() {
+        let mut game_state = test_state(vec![6, 5, 2, 4]);
+        game_state.start_combat();
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            8,
+        ));
+        let command = ret_lang::DefendCommand::build(vec!["defend", "squire"]).unwrap();
+        defend(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        let hp = game_state.player.hp;
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(
+            output.lines,
+            vec![
+                "You take the blow meant for the squire.",
+                "The goblin attacks and deals 3 damage."
+            ]
+        );
+        assert_eq!(game_state.player.hp, hp - 3);
+        assert_eq!(game_state.allies[0].hp, 8);
+        assert_eq!(game_state.allies[0].hold, 2);
+    }
+
+    /// Test that enemies can attack allies, and an ally that falls leaves the party.
+    #[test]
+    fn ally_takes_damage_test() {
+        let mut game_state = test_state(vec![2, 4, 2, 6]);
+        game_state.start_combat();
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            8,
+        ));
+        let hp = game_state.player.hp;
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(
+            output.lines,
+            vec!["The goblin attacks, hitting the squire for 4 damage."]
+        );
+        assert_eq!(game_state.allies[0].hp, 4);
+        let mut output = CommandOutput::new();
+        enemy_turns(&mut game_state, &mut output);
+        assert_eq!(
+            output.lines,
+            vec![
+                "The goblin attacks, hitting the squire for 6 damage.",
+                "The squire has fallen."
+            ]
+        );
+        assert!(game_state.allies.is_empty());
+        assert_eq!(game_state.player.hp, hp);
+    }
+
+    /// Test that missing enough rolls marks enough XP to level up.
+    #[test]
+    fn miss_level_up_test() {
+        let mut game_state = test_state(vec![1, 1]);
+        let command = ret_lang::HackAndSlashCommand::build(vec!["attack", "goblin"]).unwrap();
+        for _ in 0..7 {
+            let output =
+                hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+            assert_eq!(output.output().lines.last().unwrap(), MARK_XP_MESSAGE);
+        }
+        assert_eq!(game_state.player.xp, 7);
+        let output = hack_and_slash(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines.last().unwrap(), LEVEL_UP_MESSAGE);
+        assert_eq!(game_state.player.level, 2);
+        assert_eq!(game_state.player.xp, 0);
+        assert_eq!(game_state.player.max_hp, 22);
+    }
+}
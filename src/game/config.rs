@@ -0,0 +1,238 @@
+//! # Config
+//! This module contains the configurable settings and messages of the game.
+use crate::game::color::ColorMode;
+use crate::game::enemy::Enemy;
+use crate::game::error::GameError;
+use std::str::FromStr;
+
+/// Prompt error message.
+pub const PROMPT_ERROR: &str = "Try command again.";
+/// Prompt message.
+pub const HERO_PROMPT: &str = "What do you do hero?";
+/// Message shown when an action can't be taken.
+pub const NOT_ABLE_MESSAGE: &str = "Not able to do that action right now.";
+
+/// The enemies a random encounter can spawn, unless the config sets its own.
+fn default_encounters() -> Vec<Enemy> {
+    vec![
+        Enemy::new(
+            String::from("goblin"),
+            String::from("A sneering goblin."),
+            6,
+        ),
+        Enemy::new(String::from("wolf"), String::from("A hungry grey wolf."), 8),
+    ]
+}
+
+/// An enum that represents how hard the enemies are to fight.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Difficulty {
+    /// Enemies have half their hit points and damage.
+    Easy,
+    /// Enemies are as they are written.
+    #[default]
+    Normal,
+    /// Enemies have half again their hit points and damage.
+    Hard,
+}
+
+impl Difficulty {
+    /// The percent the enemies' hit points and damage are scaled to.
+    ///
+    /// # Returns
+    /// * `u32` - The percent, where 100 leaves the enemies as they are.
+    pub fn percent(self) -> u32 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+        }
+    }
+
+    /// A function that scales an enemy to the difficulty, rounding up so every enemy keeps at
+    /// least 1 hit point and a damage die of at least 1.
+    ///
+    /// # Arguments
+    /// * `enemy` - A reference to the enemy as it is written.
+    ///
+    /// # Returns
+    /// * `Enemy` - The enemy at full hit points for the difficulty.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::config::Difficulty;
+    /// use retribution::game::enemy::Enemy;
+    ///
+    /// let goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+    /// assert_eq!(Difficulty::Easy.apply(&goblin).hp, 3);
+    /// assert_eq!(Difficulty::Normal.apply(&goblin), goblin);
+    /// assert_eq!(Difficulty::Hard.apply(&goblin).damage, 9);
+    /// ```
+    pub fn apply(self, enemy: &Enemy) -> Enemy {
+        let scale = |value: u32| (value * self.percent()).div_ceil(100).max(1);
+        let mut scaled = enemy.clone();
+        scaled.max_hp = scale(enemy.max_hp.max(0) as u32) as i32;
+        scaled.hp = scaled.max_hp;
+        scaled.damage = scale(enemy.damage);
+        scaled
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("Unknown difficulty: {}", s)),
+        }
+    }
+}
+
+/// A struct that holds the settings of the game and the strings shown to the player.
+#[derive(Clone, Debug)]
+pub struct GameConfig {
+    /// The message shown when asking the player for input.
+    pub prompt: String,
+    /// The message shown when reading the player's input fails.
+    pub prompt_error: String,
+    /// The message shown when an action can't be taken.
+    pub not_able_message: String,
+    /// The path to the database the maps are loaded from.
+    pub db_path: String,
+    /// Whether the debug commands, such as goto and warp, can be used.
+    pub debug: bool,
+    /// Whether the game is saved to the autosave slot when the hero changes rooms or modes.
+    pub autosave: bool,
+    /// The percent chance, from 0 to 100, that moving into a room spawns an enemy. 0 turns random
+    /// encounters off.
+    pub encounter_chance: u32,
+    /// The enemies a random encounter picks from.
+    pub encounters: Vec<Enemy>,
+    /// When the output shown to the player is colored. Auto should be resolved against the
+    /// terminal before the game starts, otherwise the output is left plain.
+    pub color: ColorMode,
+    /// How hard the enemies spawned by random encounters are.
+    pub difficulty: Difficulty,
+    /// Whether a new game opens with a welcome and a hint to ask for help.
+    pub intro: bool,
+    /// Whether the prompt is left out, such as when the commands are piped in rather than typed.
+    pub quiet: bool,
+    /// Whether the database uses write-ahead logging, so saving doesn't block loading maps.
+    pub wal: bool,
+    /// The filler words dropped from commands before they are built, such as "the" in
+    /// `take the sword`.
+    pub stop_words: Vec<String>,
+}
+
+impl GameConfig {
+    /// A function that creates a new GameConfig with the default messages.
+    ///
+    /// # Returns
+    /// * `GameConfig` - A new GameConfig.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::config;
+    ///
+    /// let mut game_config = config::GameConfig::new();
+    /// assert_eq!(game_config.prompt, "What do you do hero?");
+    /// game_config.prompt = String::from("Quelle est votre action, héros ?");
+    /// ```
+    pub fn new() -> GameConfig {
+        GameConfig {
+            prompt: String::from(HERO_PROMPT),
+            prompt_error: String::from(PROMPT_ERROR),
+            not_able_message: String::from(NOT_ABLE_MESSAGE),
+            db_path: String::from(crate::DB_PATH),
+            debug: false,
+            autosave: true,
+            encounter_chance: 0,
+            encounters: default_encounters(),
+            color: ColorMode::Auto,
+            difficulty: Difficulty::Normal,
+            intro: false,
+            quiet: false,
+            wal: true,
+            stop_words: crate::ret_lang::parser::STOP_WORDS
+                .iter()
+                .map(|w| w.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// A function that finds the message shown to the player for an error, using the configured
+    /// message in place of the default one when the action isn't allowed.
+    ///
+    /// # Arguments
+    /// * `error` - A reference to the error to describe.
+    ///
+    /// # Returns
+    /// * `String` - The message to show the player.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::config;
+    /// use retribution::game::error::GameError;
+    ///
+    /// let mut game_config = config::GameConfig::new();
+    /// game_config.not_able_message = String::from("You can't do that.");
+    /// assert_eq!(
+    ///     game_config.error_message(&GameError::NotAllowed(config::NOT_ABLE_MESSAGE)),
+    ///     "You can't do that."
+    /// );
+    /// assert_eq!(
+    ///     game_config.error_message(&GameError::NotAllowed("Not while fighting.")),
+    ///     "Not while fighting."
+    /// );
+    /// assert_eq!(
+    ///     game_config.error_message(&GameError::InvalidDirection),
+    ///     "You can't go that way."
+    /// );
+    /// ```
+    pub fn error_message(&self, error: &GameError) -> String {
+        match error {
+            GameError::NotAllowed(message) if *message == NOT_ABLE_MESSAGE => {
+                self.not_able_message.clone()
+            }
+            _ => error.to_string(),
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the same enemy is weaker on Easy and stronger on Hard.
+    #[test]
+    fn difficulty_apply_test() {
+        let wolf = Enemy::new(String::from("wolf"), String::from("A wolf."), 8);
+        let easy = Difficulty::Easy.apply(&wolf);
+        let hard = Difficulty::Hard.apply(&wolf);
+        assert_eq!((easy.hp, easy.max_hp, easy.damage), (4, 4, 3));
+        assert_eq!((hard.hp, hard.max_hp, hard.damage), (12, 12, 9));
+        assert_eq!(Difficulty::Normal.apply(&wolf), wolf);
+
+        let rat = Enemy::new(String::from("rat"), String::from("A rat."), 1);
+        assert_eq!(Difficulty::Easy.apply(&rat).hp, 1);
+    }
+
+    /// Test reading a difficulty from a flag.
+    #[test]
+    fn difficulty_from_str_test() {
+        assert_eq!("Easy".parse(), Ok(Difficulty::Easy));
+        assert_eq!("hard".parse(), Ok(Difficulty::Hard));
+        assert!("brutal".parse::<Difficulty>().is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! # Dice
+//! Module that rolls the dice used to resolve the hero's moves.
+use crate::logging;
+use crate::ret_lang::Stat;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A trait that rolls dice. The game holds one so tests can swap in predictable rolls.
+pub trait Roller: Send {
+    /// Rolls a single die.
+    ///
+    /// # Arguments
+    /// * `sides` - A u32 that is the number of sides on the die.
+    ///
+    /// # Returns
+    /// * `u32` - The result of the roll, from 1 to `sides`.
+    fn roll(&mut self, sides: u32) -> u32;
+
+    /// Clones the roller into a new box.
+    fn box_clone(&self) -> Box<dyn Roller>;
+}
+
+impl Clone for Box<dyn Roller> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// A roller that produces pseudo random rolls.
+#[derive(Clone)]
+pub struct RandomRoller {
+    /// The state of the xorshift generator. Never zero.
+    state: u64,
+}
+
+impl RandomRoller {
+    /// Constructor for the RandomRoller struct, seeded from the clock.
+    ///
+    /// # Returns
+    /// * `RandomRoller` - A new RandomRoller.
+    pub fn new() -> RandomRoller {
+        RandomRoller::seeded(clock_seed())
+    }
+
+    /// Constructor for the RandomRoller struct from a seed. Rollers with the same seed roll the
+    /// same dice, so a playthrough can be repeated.
+    ///
+    /// # Arguments
+    /// * `seed` - A u64 that is the seed of the rolls.
+    ///
+    /// # Returns
+    /// * `RandomRoller` - A new RandomRoller.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::dice::{RandomRoller, Roller};
+    ///
+    /// let mut roller = RandomRoller::seeded(42);
+    /// let mut again = RandomRoller::seeded(42);
+    /// assert_eq!(roller.roll(20), again.roll(20));
+    /// ```
+    pub fn seeded(seed: u64) -> RandomRoller {
+        // Mix the seed with splitmix64, so small seeds don't start the generator near zero.
+        let mut state = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        state ^= state >> 31;
+        RandomRoller { state: state | 1 }
+    }
+}
+
+/// A function that picks a seed from the clock, for when the player doesn't give one.
+///
+/// # Returns
+/// * `u64` - The seed.
+pub fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+impl Default for RandomRoller {
+    fn default() -> Self {
+        RandomRoller::new()
+    }
+}
+
+impl Roller for RandomRoller {
+    fn roll(&mut self, sides: u32) -> u32 {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state % sides.max(1) as u64) as u32 + 1
+    }
+
+    fn box_clone(&self) -> Box<dyn Roller> {
+        Box::new(self.clone())
+    }
+}
+
+/// A roller that returns a fixed list of rolls in order, starting over when it runs out. A roll
+/// is clamped to the die it is made on, so a 6 rolled on a d4 comes up 4.
+#[derive(Clone)]
+pub struct FixedRoller {
+    /// The rolls to return.
+    rolls: Vec<u32>,
+    /// The index of the next roll to return.
+    index: usize,
+}
+
+impl FixedRoller {
+    /// Constructor for the FixedRoller struct.
+    ///
+    /// # Arguments
+    /// * `rolls` - A vector of the rolls to return, in order.
+    ///
+    /// # Returns
+    /// * `FixedRoller` - A new FixedRoller.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::dice::{FixedRoller, Roller};
+    ///
+    /// let mut roller = FixedRoller::new(vec![6, 1]);
+    /// assert_eq!(roller.roll(6), 6);
+    /// assert_eq!(roller.roll(6), 1);
+    /// assert_eq!(roller.roll(4), 4);
+    /// ```
+    pub fn new(rolls: Vec<u32>) -> FixedRoller {
+        FixedRoller { rolls, index: 0 }
+    }
+}
+
+impl Roller for FixedRoller {
+    fn roll(&mut self, sides: u32) -> u32 {
+        if self.rolls.is_empty() {
+            return 1;
+        }
+        let roll = self.rolls[self.index % self.rolls.len()];
+        self.index += 1;
+        roll.clamp(1, sides.max(1))
+    }
+
+    fn box_clone(&self) -> Box<dyn Roller> {
+        Box::new(self.clone())
+    }
+}
+
+/// A function that creates the roller a new game uses.
+///
+/// # Returns
+/// * `Box<dyn Roller>` - A random roller.
+pub fn default_roller() -> Box<dyn Roller> {
+    Box::new(RandomRoller::new())
+}
+
+/// Rolls two six sided dice and adds them together, as every move does.
+///
+/// # Arguments
+/// * `roller` - A mutable reference to the roller.
+///
+/// # Returns
+/// * `u32` - The total of the two dice.
+pub fn roll_2d6(roller: &mut dyn Roller) -> u32 {
+    roller.roll(6) + roller.roll(6)
+}
+
+/// An enum that represents the band a move's roll lands in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// A total of 6 or less.
+    Miss,
+    /// A total of 7 to 9.
+    PartialSuccess,
+    /// A total of 10 or more.
+    StrongHit,
+}
+
+impl Outcome {
+    /// A function that finds the band a total lands in.
+    ///
+    /// # Arguments
+    /// * `total` - An i32 that is the total of the roll.
+    ///
+    /// # Returns
+    /// * `Outcome` - The band of the total.
+    pub fn from_total(total: i32) -> Outcome {
+        match total {
+            i32::MIN..=6 => Outcome::Miss,
+            7..=9 => Outcome::PartialSuccess,
+            _ => Outcome::StrongHit,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let outcome = match self {
+            Outcome::Miss => "miss",
+            Outcome::PartialSuccess => "partial success",
+            Outcome::StrongHit => "strong hit",
+        };
+        write!(f, "{}", outcome)
+    }
+}
+
+/// A struct that holds the dice, modifier, and total of a move's roll, so the math can be shown
+/// to the player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollResult {
+    /// The two dice that were rolled.
+    pub dice: [u32; 2],
+    /// The modifier added to the dice.
+    pub modifier: i32,
+    /// The stat the modifier comes from, if any.
+    pub stat: Option<Stat>,
+    /// The dice and modifier added together.
+    pub total: i32,
+    /// The band the total lands in.
+    pub band: Outcome,
+}
+
+impl fmt::Display for RollResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "You rolled {}+{} ({:+}",
+            self.dice[0], self.dice[1], self.modifier
+        )?;
+        if let Some(stat) = self.stat {
+            write!(f, " {}", stat.abbreviation())?;
+        }
+        write!(f, ") = {}: {}.", self.total, self.band)
+    }
+}
+
+/// Rolls a move: two six sided dice plus a modifier, keeping each part of the roll.
+///
+/// # Arguments
+/// * `roller` - A mutable reference to the roller.
+/// * `stat` - The stat the move is rolled with, if any.
+/// * `modifier` - An i32 that is the modifier added to the dice.
+///
+/// # Returns
+/// * `RollResult` - The dice, modifier, total, and band of the roll.
+///
+/// # Examples
+/// ```
+/// use retribution::game::dice;
+/// use retribution::ret_lang::Stat;
+///
+/// let mut roller = dice::FixedRoller::new(vec![4, 3]);
+/// let result = dice::roll_move(&mut roller, Some(Stat::Dexterity), 2);
+/// assert_eq!(result.total, 9);
+/// assert_eq!(result.band, dice::Outcome::PartialSuccess);
+/// assert_eq!(result.to_string(), "You rolled 4+3 (+2 DEX) = 9: partial success.");
+/// ```
+pub fn roll_move(roller: &mut dyn Roller, stat: Option<Stat>, modifier: i32) -> RollResult {
+    let dice = [roller.roll(6), roller.roll(6)];
+    let total = (dice[0] + dice[1]) as i32 + modifier;
+    logging::trace!("Rolled {}+{} {:+} = {}.", dice[0], dice[1], modifier, total);
+    RollResult {
+        dice,
+        modifier,
+        stat,
+        total,
+        band: Outcome::from_total(total),
+    }
+}
+
+/// A function that turns a stat score into the modifier added to a roll.
+///
+/// # Arguments
+/// * `score` - An i32 that is the stat score.
+///
+/// # Returns
+/// * `i32` - The modifier for the score.
+///
+/// # Examples
+/// ```
+/// use retribution::game::dice;
+///
+/// assert_eq!(dice::modifier(10), 0);
+/// assert_eq!(dice::modifier(16), 2);
+/// assert_eq!(dice::modifier(5), -2);
+/// ```
+pub fn modifier(score: i32) -> i32 {
+    match score {
+        i32::MIN..=3 => -3,
+        4..=5 => -2,
+        6..=8 => -1,
+        9..=12 => 0,
+        13..=15 => 1,
+        16..=17 => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that rollers with the same seed roll the same dice, and a different seed doesn't.
+    #[test]
+    fn seeded_roller_test() {
+        let rolls = |seed: u64| -> Vec<u32> {
+            let mut roller = RandomRoller::seeded(seed);
+            (0..50).map(|_| roller.roll(20)).collect()
+        };
+        assert_eq!(rolls(7), rolls(7));
+        assert_ne!(rolls(7), rolls(8));
+        assert!(rolls(0).iter().all(|r| (1..=20).contains(r)));
+    }
+
+    /// Test that the random roller stays within the sides of the die.
+    #[test]
+    fn random_roller_range_test() {
+        let mut roller = RandomRoller::new();
+        for _ in 0..1000 {
+            let roll = roller.roll(6);
+            assert!((1..=6).contains(&roll));
+        }
+    }
+
+    /// Test rolling two dice with a fixed roller.
+    #[test]
+    fn roll_2d6_test() {
+        let mut roller = FixedRoller::new(vec![3, 4]);
+        assert_eq!(roll_2d6(&mut roller), 7);
+    }
+
+    /// Test that the breakdown of a move matches the forced dice.
+    #[test]
+    fn roll_move_breakdown_test() {
+        let mut roller = FixedRoller::new(vec![4, 3, 1, 2, 6, 6]);
+        let result = roll_move(&mut roller, Some(Stat::Dexterity), 2);
+        assert_eq!(result.dice, [4, 3]);
+        assert_eq!(
+            result.to_string(),
+            "You rolled 4+3 (+2 DEX) = 9: partial success."
+        );
+        let result = roll_move(&mut roller, Some(Stat::Strength), -1);
+        assert_eq!(result.to_string(), "You rolled 1+2 (-1 STR) = 2: miss.");
+        let result = roll_move(&mut roller, None, 0);
+        assert_eq!(result.to_string(), "You rolled 6+6 (+0) = 12: strong hit.");
+    }
+}
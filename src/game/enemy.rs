@@ -0,0 +1,74 @@
+//! # Enemy
+//! Module that represents the foes a hero can face.
+use serde::{Deserialize, Serialize};
+
+/// The sides on the damage die of an enemy.
+const DEFAULT_DAMAGE: u32 = 6;
+
+/// A struct that represents an enemy in the game world.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Enemy {
+    /// The name of the enemy.
+    pub name: String,
+    /// The description of the enemy.
+    pub description: String,
+    /// The current hit points of the enemy.
+    pub hp: i32,
+    /// The most hit points the enemy can have.
+    pub max_hp: i32,
+    /// The number of sides on the enemy's damage die.
+    #[serde(default = "default_damage")]
+    pub damage: u32,
+    /// How quick the enemy is to act. Enemies with more initiative take their turns first.
+    #[serde(default)]
+    pub initiative: i32,
+    /// How the enemy takes to the hero once it yields to a parley.
+    #[serde(default)]
+    pub disposition: Disposition,
+}
+
+/// An enum that represents what an enemy does once it yields to the hero.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Disposition {
+    /// The enemy only yields to get away, and leaves the room.
+    #[default]
+    Hostile,
+    /// The enemy is won over, and joins the hero as an ally.
+    Friendly,
+}
+
+/// The damage die used for enemies that don't set one.
+fn default_damage() -> u32 {
+    DEFAULT_DAMAGE
+}
+
+impl Enemy {
+    /// Constructor for the Enemy struct.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the enemy.
+    /// * `description` - A string that is the description of the enemy.
+    /// * `hp` - An i32 that is the hit points the enemy starts with.
+    ///
+    /// # Returns
+    /// * `Enemy` - A new Enemy at full hit points.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::enemy;
+    ///
+    /// let goblin = enemy::Enemy::new(String::from("goblin"), String::from("A sneering goblin."), 6);
+    /// assert_eq!(goblin.hp, goblin.max_hp);
+    /// ```
+    pub fn new(name: String, description: String, hp: i32) -> Enemy {
+        Enemy {
+            name,
+            description,
+            hp,
+            max_hp: hp,
+            damage: DEFAULT_DAMAGE,
+            initiative: 0,
+            disposition: Disposition::Hostile,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! # Error
+//! This module contains the errors the interpreter returns when a command can't be carried out.
+use std::fmt;
+
+/// Message shown when moving somewhere there's no room.
+pub const CANT_GO_MESSAGE: &str = "You can't go that way.";
+
+/// An enum that represents why a command couldn't be carried out. Every error displays as the
+/// message shown to the player.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GameError {
+    /// The command can't be used right now, such as `config::NOT_ABLE_MESSAGE`.
+    NotAllowed(&'static str),
+    /// There's no room in the direction the hero tried to move.
+    InvalidDirection,
+    /// The item, creature, spell, or place the hero named couldn't be found.
+    NoSuchTarget(&'static str),
+    /// The command breaks one of the rules of the game, such as carrying too much.
+    Refused(&'static str),
+    /// The game couldn't be saved or loaded.
+    Save(&'static str),
+    /// The way is locked until the hero holds the named key.
+    Locked(String),
+    /// The name the hero gave matches more than one thing, which are listed.
+    Ambiguous(Vec<String>),
+}
+
+impl fmt::Display for GameError {
+    /// Displays the message shown to the player.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::config::NOT_ABLE_MESSAGE;
+    /// use retribution::game::error::GameError;
+    ///
+    /// assert_eq!(
+    ///     GameError::NotAllowed(NOT_ABLE_MESSAGE).to_string(),
+    ///     "Not able to do that action right now."
+    /// );
+    /// assert_eq!(
+    ///     GameError::NotAllowed("You can't do that while fighting.").to_string(),
+    ///     "You can't do that while fighting."
+    /// );
+    /// assert_eq!(
+    ///     GameError::NoSuchTarget("You don't see that here.").to_string(),
+    ///     "You don't see that here."
+    /// );
+    /// assert_eq!(
+    ///     GameError::Locked(String::from("brass key")).to_string(),
+    ///     "The door is locked; you need the brass key."
+    /// );
+    /// assert_eq!(
+    ///     GameError::Ambiguous(vec![String::from("rusty sword"), String::from("rusty shield")])
+    ///         .to_string(),
+    ///     "Which do you mean: rusty sword, rusty shield?"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::InvalidDirection => write!(f, "{}", CANT_GO_MESSAGE),
+            GameError::NotAllowed(message)
+            | GameError::NoSuchTarget(message)
+            | GameError::Refused(message)
+            | GameError::Save(message) => write!(f, "{}", message),
+            GameError::Locked(key) => write!(f, "The door is locked; you need the {}.", key),
+            GameError::Ambiguous(names) => write!(f, "Which do you mean: {}?", names.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
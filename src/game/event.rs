@@ -0,0 +1,37 @@
+//! # Event
+//! This module contains the events that happen as commands are run, so plugins can react to them
+//! without comparing whole game states.
+use crate::game::map::Coord;
+use serde::{Deserialize, Serialize};
+
+/// An enum that represents something that happened in the game.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum Event {
+    /// The hero moved into a room.
+    PlayerMoved { map: String, to: Coord },
+    /// The hero picked up an item.
+    ItemTaken { item: String },
+    /// The hero attacked an enemy while not already fighting.
+    CombatStarted { enemy: String },
+    /// The hero defeated an enemy.
+    EnemyDefeated { enemy: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that events are written with their type next to their fields.
+    #[test]
+    fn event_serialize_test() {
+        let event = Event::PlayerMoved {
+            map: String::from("Test Area"),
+            to: Coord::new(0, 1),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"PlayerMoved","map":"Test Area","to":[0,1]}"#
+        );
+    }
+}
@@ -1,139 +1,3003 @@
 //! # Interpreter
 //! A module that contains the interpreter for the game.
+use crate::game::actor::Actor;
+use crate::game::combat;
+use crate::game::config;
+use crate::game::dice;
+use crate::game::enemy::Enemy;
+use crate::game::error::GameError;
+use crate::game::event::Event;
+use crate::game::item::{self, Effect, WeaponType};
 use crate::game::map;
+use crate::game::player;
+use crate::game::save;
+use crate::game::spell;
 use crate::game::state;
 use crate::ret_lang;
+use std::fmt;
+use std::io;
 
-use super::tear_down;
+/// Message shown when the hero leaves the game.
+const EXIT_MESSAGE: &str = "Farewell hero.";
+/// Message shown the first time the hero asks to exit.
+const CONFIRM_EXIT_MESSAGE: &str = "Are you sure? Type 'exit' again to quit.";
+/// Message shown when an item would put the hero over their carry capacity.
+const CARRY_LIMIT_MESSAGE: &str = "You can't carry any more.";
+/// Message shown when the item to take isn't in the room.
+const NO_ITEM_MESSAGE: &str = "You don't see that here.";
+/// Message shown when the item to drop isn't in the inventory.
+const NOT_CARRIED_MESSAGE: &str = "You aren't carrying that.";
+/// Message shown when giving an item to someone who isn't in the hero's party.
+const NO_ONE_TO_GIVE_MESSAGE: &str = "There's no one here by that name.";
+/// Message shown when giving an item to an enemy instead of an ally.
+const NOT_AN_ALLY_MESSAGE: &str = "Only your allies will take what you give.";
+/// Message shown when using an item that has no use.
+const CANT_USE_MESSAGE: &str = "You can't use that.";
+/// Message shown when healing at full hit points.
+const FULL_HEALTH_MESSAGE: &str = "You're already at full health.";
+/// Message shown when learning a spell without a spellbook.
+const NO_SPELLBOOK_MESSAGE: &str = "You need a spellbook to learn spells.";
+/// Message shown when trying to rest during a fight.
+const CANT_REST_MESSAGE: &str = "You can't rest while enemies are near.";
+/// Message shown when lighting something that can't give off light.
+const NOT_A_LIGHT_MESSAGE: &str = "That won't light.";
+/// Message shown when lighting a light that has no turns left.
+const BURNED_OUT_MESSAGE: &str = "It has burned out.";
+/// Message shown when lighting a light that is already lit.
+const ALREADY_LIT_MESSAGE: &str = "It's already lit.";
+/// Message shown when putting out a light that isn't lit.
+const NOT_LIT_MESSAGE: &str = "It isn't lit.";
+/// The hit points recovered by resting.
+const REST_HP: i32 = 5;
+/// The mana recovered by resting.
+const REST_MANA: i32 = 5;
+/// Message shown when taking everything from a room with no items.
+const NOTHING_TO_TAKE_MESSAGE: &str = "There's nothing here to take.";
+/// Message shown when dropping everything with an empty inventory.
+const NOTHING_TO_DROP_MESSAGE: &str = "You aren't carrying anything.";
+/// Message shown when dropping something while not in a room.
+const NOWHERE_TO_DROP_MESSAGE: &str = "There's nowhere to drop anything.";
+/// The target of take and drop that means every item.
+const ALL: &str = "all";
+/// Message shown when asking for more of an item than there are.
+const NOT_THAT_MANY_MESSAGE: &str = "There aren't that many.";
+/// Message shown when listing an empty inventory.
+const EMPTY_INVENTORY_MESSAGE: &str = "Your pack is empty.";
+/// Message shown when there is nothing to examine by that name.
+const NOTHING_SPECIAL_MESSAGE: &str = "You see nothing special about that.";
+/// Message shown when trying to wield something that isn't a weapon.
+const NOT_A_WEAPON_MESSAGE: &str = "You can't wield that.";
+/// Message shown when unequipping without a weapon in hand.
+const NOT_WIELDING_MESSAGE: &str = "You aren't wielding anything.";
+/// Message shown when moving diagonally on a map that only allows compass movement.
+const NO_DIAGONAL_MESSAGE: &str = "You can't move diagonally here.";
+/// Message shown when there is no room to go back to, or the way back is gone.
+const CANT_GO_BACK_MESSAGE: &str = "You can't go back.";
+/// The word that stands for the item or creature the hero last named.
+const IT: &str = "it";
+/// Message shown when "it" is used before the hero has named anything.
+const UNKNOWN_IT_MESSAGE: &str = "I don't know what 'it' refers to.";
+/// Message shown when jumping to coordinates outside of the map.
+const OUTSIDE_MAP_MESSAGE: &str = "Those coordinates are outside the map.";
+/// Message shown when jumping to a square that isn't a room.
+const NO_ROOM_MESSAGE: &str = "There's no room there.";
+/// Message shown when warping to a map that can't be loaded.
+const NO_MAP_MESSAGE: &str = "There's no map by that name.";
+/// Message shown when a command can't be used from the menu.
+const NOT_IN_MENU_MESSAGE: &str = "That's not available from the menu.";
+/// Message shown when the game is paused.
+const PAUSED_MESSAGE: &str = "The game is paused. Type 'play' to resume.";
+/// Message listing the commands available from the menu.
+const MENU_HELP_MESSAGE: &str =
+    "Menu commands: play, save [slot], load [slot], validate <map>, status, help, exit.";
 
-const NOT_ABLE_MESSAGE: &str = "Not able to do that action right now.";
+/// A struct that holds the lines of output produced by a command.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandOutput {
+    /// The lines of output in the order they should be shown.
+    pub lines: Vec<String>,
+    /// The indices of the lines that tell of damage dealt, so they can be shown in red.
+    pub damage: Vec<usize>,
+}
+
+impl CommandOutput {
+    /// A function that creates a new, empty CommandOutput.
+    ///
+    /// # Returns
+    /// * `CommandOutput` - A new CommandOutput.
+    pub fn new() -> CommandOutput {
+        CommandOutput {
+            lines: vec![],
+            damage: vec![],
+        }
+    }
+
+    /// Adds a line to the end of the output.
+    ///
+    /// # Arguments
+    /// * `line` - The line to add.
+    pub fn push(&mut self, line: String) {
+        self.lines.push(line);
+    }
+
+    /// Adds a line telling of damage dealt to the end of the output.
+    ///
+    /// # Arguments
+    /// * `line` - The line to add.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::interpreter::CommandOutput;
+    ///
+    /// let mut output = CommandOutput::new();
+    /// output.push(String::from("You rolled 5+4 (+1 STR) = 10: strong hit."));
+    /// output.push_damage(String::from("You hit the goblin and deal 4 damage."));
+    /// assert_eq!(output.damage, vec![1]);
+    /// ```
+    pub fn push_damage(&mut self, line: String) {
+        self.damage.push(self.lines.len());
+        self.lines.push(line);
+    }
+
+    /// Whether a line of the output tells of damage dealt.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the line.
+    ///
+    /// # Returns
+    /// * `bool` - True if the line was added with `push_damage`.
+    pub fn is_damage(&self, index: usize) -> bool {
+        self.damage.contains(&index)
+    }
+
+    /// Writes each line of the output to a writer.
+    ///
+    /// # Arguments
+    /// * `writer` - A mutable reference to where the output is written.
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - A result that is Ok, or the error from the writer.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::interpreter::CommandOutput;
+    ///
+    /// let output = CommandOutput::from(String::from("Hero went north.\nThis is room 4."));
+    /// let mut writer = Vec::new();
+    /// output.write_to(&mut writer).unwrap();
+    /// assert_eq!(writer, b"Hero went north.\nThis is room 4.\n");
+    /// ```
+    pub fn write_to(&self, writer: &mut dyn io::Write) -> Result<(), io::Error> {
+        for line in &self.lines {
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for CommandOutput {
+    fn from(output: String) -> Self {
+        CommandOutput {
+            lines: output.lines().map(String::from).collect(),
+            damage: vec![],
+        }
+    }
+}
+
+impl fmt::Display for CommandOutput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.lines.join("\n"))
+    }
+}
+
+/// An enum that holds what a command did, along with the lines of output to show the player, so
+/// plugins and tests can check the kind of outcome without reading the text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// The command only had something to say.
+    Message(CommandOutput),
+    /// The hero moved from one room to another.
+    Moved {
+        from: map::Coord,
+        to: map::Coord,
+        output: CommandOutput,
+    },
+    /// The hero rolled a move.
+    Combat {
+        roll: dice::RollResult,
+        output: CommandOutput,
+    },
+    /// The game is over, because the hero left or fell.
+    GameOver(CommandOutput),
+}
+
+impl Outcome {
+    /// The lines of output of the outcome.
+    ///
+    /// # Returns
+    /// * `&CommandOutput` - The output to show the player.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::interpreter::{CommandOutput, Outcome};
+    ///
+    /// let outcome = Outcome::from(CommandOutput::from(String::from("Hello.")));
+    /// assert_eq!(outcome.output().lines, vec!["Hello."]);
+    /// ```
+    pub fn output(&self) -> &CommandOutput {
+        match self {
+            Outcome::Message(output)
+            | Outcome::Moved { output, .. }
+            | Outcome::Combat { output, .. }
+            | Outcome::GameOver(output) => output,
+        }
+    }
+
+    /// The lines of output of the outcome, for changing them.
+    ///
+    /// # Returns
+    /// * `&mut CommandOutput` - The output to show the player.
+    pub fn output_mut(&mut self) -> &mut CommandOutput {
+        match self {
+            Outcome::Message(output)
+            | Outcome::Moved { output, .. }
+            | Outcome::Combat { output, .. }
+            | Outcome::GameOver(output) => output,
+        }
+    }
+
+    /// Takes the lines of output out of the outcome.
+    ///
+    /// # Returns
+    /// * `CommandOutput` - The output to show the player.
+    pub fn into_output(self) -> CommandOutput {
+        match self {
+            Outcome::Message(output)
+            | Outcome::Moved { output, .. }
+            | Outcome::Combat { output, .. }
+            | Outcome::GameOver(output) => output,
+        }
+    }
+}
+
+impl From<CommandOutput> for Outcome {
+    fn from(output: CommandOutput) -> Self {
+        Outcome::Message(output)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.output())
+    }
+}
+
+/// A function that wields a weapon from the hero's inventory. A weapon already in hand is
+/// put back in the inventory.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the weapon to wield.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of wielding the weapon, or an error message.
+fn equip(name: &str, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let index = state
+        .inventory
+        .iter()
+        .position(|i| i.name.eq_ignore_ascii_case(name))
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    if state.inventory[index].damage.is_none() {
+        return Err(GameError::Refused(NOT_A_WEAPON_MESSAGE));
+    }
+    let weapon = state.inventory.remove(index);
+    let output = format!("You wield the {}.", weapon.name);
+    if let Some(old_weapon) = state.player.weapon.replace(weapon) {
+        item::add(&mut state.inventory, old_weapon);
+    }
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that puts the hero's weapon back in their inventory.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of putting the weapon away, or an error message.
+fn unequip(state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let weapon = state
+        .player
+        .weapon
+        .take()
+        .ok_or(GameError::Refused(NOT_WIELDING_MESSAGE))?;
+    let output = format!("You put away the {}.", weapon.name);
+    item::add(&mut state.inventory, weapon);
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that lists the spells the hero knows.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `CommandOutput` - A line for each known spell.
+fn spells(state: &state::GameState) -> CommandOutput {
+    let mut output = CommandOutput::from(String::from("Known spells:"));
+    for spell in state.player.known_spells() {
+        output.push(spell.to_string());
+    }
+    output
+}
+
+/// A function that teaches the hero a spell, which needs a spellbook in their inventory.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the spell to learn.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of learning the spell, or an error message.
+fn learn(name: &str, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    if !state
+        .inventory
+        .iter()
+        .any(|i| i.name.eq_ignore_ascii_case(spell::SPELLBOOK))
+    {
+        return Err(GameError::Refused(NO_SPELLBOOK_MESSAGE));
+    }
+    let output = match state.player.learn_spell(name) {
+        Ok(spell) => format!("You learn {}.", spell.name),
+        Err(player::ALREADY_KNOWN_MESSAGE) => {
+            let known = spell::find(name).map_or(name, |s| s.name);
+            format!("You already know {}.", known)
+        }
+        Err(e) => return Err(GameError::NoSuchTarget(e)),
+    };
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that rests the hero, recovering hit points and mana up to their max.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `CommandOutput` - What the hero recovered.
+fn rest(state: &mut state::GameState) -> CommandOutput {
+    let healed = state.player.heal(REST_HP);
+    let restored = state.player.restore_mana(REST_MANA);
+    CommandOutput::from(format!(
+        "You rest and recover {} HP and {} mana.",
+        healed, restored
+    ))
+}
+
+/// A function that builds the hero's character sheet.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `CommandOutput` - The hero's hit points, stats, weapon, load, and the game mode.
+fn status(state: &state::GameState) -> CommandOutput {
+    let player = &state.player;
+    let weapon = match &player.weapon {
+        Some(w) if w.weapon_type == WeaponType::Ranged => {
+            format!("{} (d{}, {} ammo)", w.name, player.damage_die(), w.ammo)
+        }
+        Some(w) => format!("{} (d{})", w.name, player.damage_die()),
+        None => format!("none (d{})", player.damage_die()),
+    };
+    let mut output = CommandOutput::new();
+    output.push(player.name.clone());
+    output.push(format!("HP: {}/{}", player.hp, player.max_hp));
+    output.push(format!("Mana: {}/{}", player.mana, player.max_mana));
+    output.push(format!(
+        "Level: {} (XP: {}/{})",
+        player.level,
+        player.xp,
+        player.xp_to_level()
+    ));
+    output.push(format!("Strength: {}", player.strength));
+    output.push(format!("Dexterity: {}", player.dexterity));
+    output.push(format!("Constitution: {}", player.constitution));
+    output.push(format!("Intelligence: {}", player.intelligence));
+    output.push(format!("Wisdom: {}", player.wisdom));
+    output.push(format!("Charisma: {}", player.charisma));
+    output.push(format!("Weapon: {}", weapon));
+    output.push(format!(
+        "Load: {}/{}",
+        state.current_weight(),
+        player.carry_capacity()
+    ));
+    output.push(format!("Mode: {}", state.mode));
+    output
+}
+
+/// A function that lists what the hero is carrying in name order, with identical items stacked
+/// into one line such as "arrow x20", followed by their load.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `CommandOutput` - The hero's items and load.
+fn inventory(state: &state::GameState) -> CommandOutput {
+    if state.inventory.is_empty() {
+        return CommandOutput::from(String::from(EMPTY_INVENTORY_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    for (item, count) in item::stack(&state.inventory) {
+        match count {
+            1 => output.push(item.name.clone()),
+            _ => output.push(format!("{} x{}", item.name, count)),
+        }
+    }
+    output.push(format!(
+        "Load: {}/{}",
+        state.current_weight(),
+        state.player.carry_capacity()
+    ));
+    output
+}
+
+/// A function that describes an item or enemy the hero can see. An exact name is looked for
+/// first among the room's items, then the inventory, then the room's enemies. Only when nothing
+/// has that name is part of an item's name enough.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the thing to examine.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The description of the thing, or an error message.
+fn examine(name: &str, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let room = state.current_room();
+    let mut items: Vec<item::Item> = room.map(|r| r.items.clone()).unwrap_or_default();
+    items.extend(state.inventory.iter().cloned());
+    let exact = items
+        .iter()
+        .find(|i| i.name.eq_ignore_ascii_case(name))
+        .map(|i| i.description.clone())
+        .or_else(|| {
+            room?
+                .enemies
+                .iter()
+                .find(|e| e.name.eq_ignore_ascii_case(name))
+                .map(|e| e.description.clone())
+        });
+    let description = match exact {
+        Some(description) => description,
+        None => item::resolve_item(name, &items)?
+            .map(|i| items[i].description.clone())
+            .ok_or(GameError::NoSuchTarget(NOTHING_SPECIAL_MESSAGE))?,
+    };
+    Ok(CommandOutput::from(description))
+}
+
+/// A function that moves the hero onto a square of the current map, going through it if it is
+/// a portal.
+///
+/// # Arguments
+/// * `new_coords` - A Coord that is the square to move onto.
+/// * `way` - A string that is how the hero went, such as "north".
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the database path.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The description of the room arrived in, or an error.
+fn step_to(
+    new_coords: map::Coord,
+    way: String,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<CommandOutput, GameError> {
+    let new_grid_square = state
+        .map
+        .as_ref()
+        .and_then(|m| m.get_grid_square(new_coords))
+        .ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+    let portal = match new_grid_square {
+        map::GridSquare::Room(r) => {
+            let output = format!("Hero went {}. {}", way, state.describe(r));
+            state.room = Some(new_coords);
+            state.arrive();
+            return Ok(CommandOutput::from(output));
+        }
+        map::GridSquare::Portal(p) => p.clone(),
+    };
+    enter_portal(&portal, Some(new_coords), way, state, config)
+}
+
+/// A function that sends the hero through a portal, either a portal square or one inside the
+/// current room.
+///
+/// # Arguments
+/// * `portal` - A reference to the portal to go through.
+/// * `portal_square` - The coordinates of the portal square, or None for a portal inside a room.
+/// * `way` - A string that is how the hero went, such as "north".
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the database path.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The description of the room arrived in, or an error.
+fn enter_portal(
+    portal: &map::Portal,
+    portal_square: Option<map::Coord>,
+    way: String,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<CommandOutput, GameError> {
+    if let Some(key) = portal.required_item.as_ref() {
+        if !state.holds(key) {
+            return Err(GameError::Locked(key.clone()));
+        }
+    }
+    let new_coords = portal.location;
+    let mut new_map = state
+        .load_map(portal.target.as_str(), Some(config.db_path.clone()))
+        .map_err(|_| GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+    let grid_square = new_map
+        .get_grid_square(new_coords)
+        .ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+    let description = match grid_square {
+        map::GridSquare::Room(r) => state.describe(r),
+        _ => return Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)),
+    };
+    if let Some(portal_square) = portal_square.filter(|_| portal.consume_on_use) {
+        let source = state.current_map_name().unwrap_or_default().to_string();
+        // A portal within one map collapses on the map being entered too.
+        if new_map.name == source {
+            new_map.clear_grid_square(portal_square);
+        }
+        state.collapsed.insert((source, portal_square));
+    }
+    state.map = Some(new_map);
+    state.room = Some(new_coords);
+    state.arrive();
+    Ok(CommandOutput::from(format!(
+        "Hero went {}. {}",
+        way, description
+    )))
+}
 
 /// A function that takes a command runs game logic based on it.
 ///
 /// # Arguments
 /// * `command` - A reference to a command from the ret_lang module.
 /// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the messages.
 ///
 /// # Returns
-/// * `Result<String, &str>` - A string that is the output of the command, or an error message.
-fn travel_interpreter<'a>(
-    command: &'a ret_lang::Command,
+/// * `Result<Outcome, GameError>` - The output of the command, or an error.
+fn travel_interpreter(
+    command: &ret_lang::Command,
     state: &mut state::GameState,
-) -> Result<String, &'a str> {
-    match command {
+    config: &config::GameConfig,
+) -> Result<Outcome, GameError> {
+    // Moves and fights have outcomes of their own, and everything else is a message.
+    let output = match command {
         ret_lang::Command::Go(command) => {
-            let (row, col) = state.room.ok_or(NOT_ABLE_MESSAGE)?;
-
-            // A function that handles updating the room and returning the output.
-            let mut handle_room_change = |new_coords: (i32, i32)| {
-                let new_grid_square = state
-                    .map
-                    .as_ref()
-                    .and_then(|m| m.get_grid_square(new_coords.0, new_coords.1))
-                    .ok_or(NOT_ABLE_MESSAGE)?;
-                let portal = match new_grid_square {
-                    map::GridSquare::Room(r) => {
-                        state.room = Some(new_coords);
-                        return Ok(format!("Hero went {}. {}", command.target, r.description));
+            let coord = state
+                .room
+                .ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+            let from = state.current_map_name().map(|n| (n.to_string(), coord));
+            // Named exits are checked before the compass, so an exit can share a direction's name.
+            let exit = state.current_room().and_then(|r| {
+                r.exits
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(command.target.as_str()))
+                    .map(|(name, c)| (format!("through the {}", name), *c))
+            });
+            // Portals inside the room are next, so stairs can sit in a room.
+            let inside = state
+                .current_room()
+                .and_then(|r| r.portal(command.target.as_str()))
+                .cloned();
+            let mut output = match (exit, inside) {
+                (Some((way, new_coords)), _) => step_to(new_coords, way, state, config)?,
+                (None, Some(portal)) => {
+                    let way = format!("through the {}", portal.name);
+                    enter_portal(&portal, None, way, state, config)?
+                }
+                (None, None) => {
+                    let direction = command.direction().ok_or(GameError::InvalidDirection)?;
+                    let allow_diagonal = state.map.as_ref().is_some_and(|m| m.allow_diagonal);
+                    if direction.is_diagonal() && !allow_diagonal {
+                        return Err(GameError::Refused(NO_DIAGONAL_MESSAGE));
                     }
-                    map::GridSquare::Portal(p) => p,
-                };
-
-                // Portal only code below here.
-                let new_coords = portal.location;
-                let new_map =
-                    map::load_map(portal.target.as_str(), None).map_err(|_| NOT_ABLE_MESSAGE)?;
-                let grid_square = new_map
-                    .get_grid_square(new_coords.0, new_coords.1)
-                    .ok_or(NOT_ABLE_MESSAGE)?;
-                let room = match grid_square {
-                    map::GridSquare::Room(r) => r,
-                    _ => return Err(NOT_ABLE_MESSAGE),
-                };
-                state.map = Some(new_map.clone());
-                state.room = Some(new_coords);
-                return Ok(format!(
-                    "Hero went {}. {}",
-                    command.target, room.description
-                ));
+                    // Moving north from the top row or west from the first column leaves the map.
+                    let new_coords = coord.toward(direction).ok_or(GameError::InvalidDirection)?;
+                    step_to(new_coords, direction.to_string(), state, config)?
+                }
             };
-            let new_coords = match command.target.to_lowercase().as_str() {
-                "north" => (row - 1, col),
-                "south" => (row + 1, col),
-                "east" => (row, col + 1),
-                "west" => (row, col - 1),
-                _ => return Err(NOT_ABLE_MESSAGE),
+            let to = state
+                .room
+                .ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+            state.previous = from;
+            let table: Vec<Enemy> = config
+                .encounters
+                .iter()
+                .map(|e| config.difficulty.apply(e))
+                .collect();
+            if let Some(line) = combat::encounter(config.encounter_chance, &table, state) {
+                output.push(line);
+            }
+            return Ok(Outcome::Moved {
+                from: coord,
+                to,
+                output,
+            });
+        }
+        ret_lang::Command::Take(command) if command.target.eq_ignore_ascii_case(ALL) => {
+            take_all(state)
+        }
+        ret_lang::Command::Take(command) => {
+            take_item(command.target.as_str(), command.count, state)
+        }
+        ret_lang::Command::Drop(command) if command.target.eq_ignore_ascii_case(ALL) => {
+            drop_all(state)
+        }
+        ret_lang::Command::Drop(command) => {
+            drop_item(command.target.as_str(), command.count, state)
+        }
+        ret_lang::Command::Give(command) => give(command, state),
+        ret_lang::Command::Use(command) => use_item(command.target.as_str(), command.count, state),
+        ret_lang::Command::Light(command) => light(command.target.as_str(), state),
+        ret_lang::Command::Extinguish(command) => extinguish(command.target.as_str(), state),
+        ret_lang::Command::Examine(command) => examine(command.target.as_str(), state),
+        ret_lang::Command::Equip(command) => equip(command.target.as_str(), state),
+        ret_lang::Command::Unequip(_) => unequip(state),
+        ret_lang::Command::Status(_) => Ok(status(state)),
+        ret_lang::Command::Inventory(_) => Ok(inventory(state)),
+        ret_lang::Command::Look(_) => {
+            look(state).ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
+        }
+        ret_lang::Command::Spells(_) => Ok(spells(state)),
+        ret_lang::Command::Learn(command) => learn(command.target.as_str(), state),
+        ret_lang::Command::Rest(_) => Ok(rest(state)),
+        ret_lang::Command::HackAndSlash(command) => return combat::hack_and_slash(command, state),
+        ret_lang::Command::Cast(command) => return combat::cast(command, state),
+        ret_lang::Command::Volley(command) => return combat::volley(command, state),
+        ret_lang::Command::Aid(command) => return combat::aid(command, state),
+        ret_lang::Command::Defend(command) => return combat::defend(command, state),
+        ret_lang::Command::Goto(command) if config.debug => goto(command.target.into(), state),
+        ret_lang::Command::Warp(command) if config.debug => {
+            let new_map = state
+                .load_map(command.target.as_str(), Some(config.db_path.clone()))
+                .map_err(|_| GameError::NoSuchTarget(NO_MAP_MESSAGE))?;
+            let coord = match command.room {
+                Some(room) => map::Coord::from(room),
+                None => new_map
+                    .first_room()
+                    .ok_or(GameError::NoSuchTarget(NO_ROOM_MESSAGE))?,
             };
-            handle_room_change(new_coords)
+            // The hero stays on their map when the room to warp to isn't there.
+            let previous = state.map.replace(new_map);
+            let output = goto(coord, state);
+            if output.is_err() {
+                state.map = previous;
+            }
+            output
+        }
+        ret_lang::Command::Back(_) => {
+            let way = way_back(state).ok_or(GameError::Refused(CANT_GO_BACK_MESSAGE))?;
+            let go = ret_lang::GoCommand::build(vec!["go", way.as_str()])
+                .map_err(|_| GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+            return travel_interpreter(&ret_lang::Command::Go(go), state, config);
+        }
+        ret_lang::Command::Dump(_) if config.debug => dump(state),
+        ret_lang::Command::Reload(_) if config.debug => reload(state, config),
+        ret_lang::Command::Map(_) => {
+            render_map(state).ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
+        }
+        ret_lang::Command::Where(_) => {
+            locate(state).ok_or(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
         }
-        ret_lang::Command::Exit(_) => {
-            let _ = tear_down();
-            std::process::exit(0);
+        ret_lang::Command::Validate(command) => validate(command.target.as_str(), config),
+        ret_lang::Command::Menu(_) => {
+            state.mode = state::Mode::Menu;
+            Ok(CommandOutput::from(String::from(PAUSED_MESSAGE)))
         }
-        _ => Err(NOT_ABLE_MESSAGE),
+        ret_lang::Command::Exit(_) => Ok(exit(state)),
+        _ => Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)),
+    };
+    output.map(Outcome::from)
+}
+
+/// A function that finds the way from the hero's room to the room they came from, checking named
+/// exits, then portals in the room, then the compass, in the same order as going.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Option<String>` - The exit, portal, or direction to go, or None if there's no way back.
+fn way_back(state: &state::GameState) -> Option<String> {
+    let (previous_map, previous_room) = state.previous.as_ref()?;
+    let current_map = state.map.as_ref()?;
+    let coord = state.room?;
+    let room = state.current_room()?;
+    let leads_back =
+        |map_name: &str, c: map::Coord| map_name == previous_map && c == *previous_room;
+    if let Some((name, _)) = room
+        .exits
+        .iter()
+        .find(|(_, c)| leads_back(current_map.name.as_str(), **c))
+    {
+        return Some(name.clone());
     }
+    if let Some(portal) = room
+        .portals
+        .iter()
+        .find(|p| leads_back(p.target.as_str(), p.location))
+    {
+        return Some(portal.name.clone());
+    }
+    ret_lang::Direction::ALL
+        .into_iter()
+        .filter(|d| current_map.allow_diagonal || !d.is_diagonal())
+        .find(|d| {
+            let Some(c) = coord.toward(*d) else {
+                return false;
+            };
+            // A portal that collapsed behind the hero is gone from the map.
+            match current_map.get_grid_square(c) {
+                Some(map::GridSquare::Room(_)) => leads_back(current_map.name.as_str(), c),
+                Some(map::GridSquare::Portal(p)) => leads_back(p.target.as_str(), p.location),
+                None => false,
+            }
+        })
+        .map(|d| d.to_string())
 }
 
-/// A function that takes a command runs game logic based on it.
+/// A function that describes the room the hero is in, unless it is too dark to see.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Option<CommandOutput>` - The description of the room, or None when the hero isn't in one.
+fn look(state: &state::GameState) -> Option<CommandOutput> {
+    let room = state.current_room()?;
+    Some(CommandOutput::from(state.describe(room)))
+}
+
+/// A function that draws the current map, hiding the rooms the hero hasn't visited.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Option<CommandOutput>` - The drawing of the map, or None when the hero isn't on a map.
+fn render_map(state: &state::GameState) -> Option<CommandOutput> {
+    let current_map = state.map.as_ref()?;
+    let lines = current_map.render(|c| state.is_visited(&current_map.name, c), state.room);
+    Some(CommandOutput {
+        lines,
+        damage: vec![],
+    })
+}
+
+/// A function that tells map authors exactly where the hero is standing.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Option<CommandOutput>` - The map name, coordinates, and room name, or None when the hero
+///   isn't in a room.
+fn locate(state: &state::GameState) -> Option<CommandOutput> {
+    let current_map = state.map.as_ref()?;
+    let coord = state.room?;
+    let room = state.current_room()?;
+    Some(CommandOutput::from(format!(
+        "{} ({}, {}): {}",
+        current_map.name, coord.row, coord.col, room.name
+    )))
+}
+
+/// A debug function that loads the current map again from the database, so a map author sees
+/// their edits without restarting. The hero stays where they are when that is still a room, and
+/// is moved to the first room of the map otherwise.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the database path.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The description of the hero's room, or an error.
+fn reload(
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<CommandOutput, GameError> {
+    let name = state
+        .current_map_name()
+        .ok_or(GameError::NoSuchTarget(NO_MAP_MESSAGE))?
+        .to_string();
+    let new_map = state
+        .load_map(name.as_str(), Some(config.db_path.clone()))
+        .map_err(|_| GameError::NoSuchTarget(NO_MAP_MESSAGE))?;
+    let coord = state
+        .room
+        .filter(|c| matches!(new_map.get_grid_square(*c), Some(map::GridSquare::Room(_))))
+        .or_else(|| new_map.first_room())
+        .ok_or(GameError::NoSuchTarget(NO_ROOM_MESSAGE))?;
+    state.map = Some(new_map);
+    state.room = Some(coord);
+    // The room the hero came from may be gone from the edited map.
+    state.previous = None;
+    state.arrive();
+    let description = state
+        .current_room()
+        .map(|r| state.describe(r))
+        .unwrap_or_default();
+    Ok(CommandOutput::from(format!(
+        "Reloaded {} ({}, {}). {}",
+        name, coord.row, coord.col, description
+    )))
+}
+
+/// A debug function that shows the game state as pretty JSON, in the shape the plugin file holds
+/// it.
+///
+/// # Arguments
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The lines of the JSON, or an error message.
+fn dump(state: &state::GameState) -> Result<CommandOutput, GameError> {
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|_| GameError::NotAllowed(config::NOT_ABLE_MESSAGE))?;
+    Ok(CommandOutput {
+        lines: json.lines().map(String::from).collect(),
+        damage: vec![],
+    })
+}
+
+/// A function that checks a map in the database for problems a map author should fix.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the map to check.
+/// * `config` - A reference to the game config holding the database path.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - Each issue found, or an error when there's no such map.
+fn validate(name: &str, config: &config::GameConfig) -> Result<CommandOutput, GameError> {
+    let issues = map::validate_map(name, Some(config.db_path.clone()))
+        .map_err(|_| GameError::NoSuchTarget(NO_MAP_MESSAGE))?;
+    if issues.is_empty() {
+        return Ok(CommandOutput::from(format!("{} has no issues.", name)));
+    }
+    let mut output = CommandOutput::new();
+    for issue in issues {
+        output.push(issue.to_string());
+    }
+    Ok(output)
+}
+
+/// A debug function that moves the hero straight to a room of the current map.
+///
+/// # Arguments
+/// * `coord` - A Coord that is the coordinates of the room to jump to.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The description of the new room, or an error message.
+fn goto(coord: map::Coord, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let current_map = state
+        .map
+        .as_ref()
+        .ok_or(GameError::NoSuchTarget(OUTSIDE_MAP_MESSAGE))?;
+    if !current_map.in_bounds(coord) {
+        return Err(GameError::NoSuchTarget(OUTSIDE_MAP_MESSAGE));
+    }
+    let description = match current_map.get_grid_square(coord) {
+        Some(map::GridSquare::Room(r)) => state.describe(r),
+        _ => return Err(GameError::NoSuchTarget(NO_ROOM_MESSAGE)),
+    };
+    let output = format!(
+        "Hero jumps to {} ({}, {}). {}",
+        current_map.name, coord.row, coord.col, description
+    );
+    state.room = Some(coord);
+    // A jump isn't a step, so there's no way back from it.
+    state.previous = None;
+    state.arrive();
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that takes a command runs combat logic based on it.
 ///
 /// # Arguments
 /// * `command` - A reference to a command from the ret_lang module.
 /// * `state` - A mutable reference to a GameState.
 ///
 /// # Returns
-/// * `Result<String, &str>` - A string that is the output of the command, or an error message.
+/// * `Result<Outcome, GameError>` - The output of the command, or an error.
+fn combat_interpreter(
+    command: &ret_lang::Command,
+    state: &mut state::GameState,
+) -> Result<Outcome, GameError> {
+    // The hero's moves are answered by the enemies, once the move is resolved.
+    let moved = match command {
+        ret_lang::Command::HackAndSlash(command) => Some(combat::hack_and_slash(command, state)),
+        ret_lang::Command::Cast(command) => Some(combat::cast(command, state)),
+        ret_lang::Command::Volley(command) => Some(combat::volley(command, state)),
+        ret_lang::Command::Aid(command) => Some(combat::aid(command, state)),
+        ret_lang::Command::Defend(command) => Some(combat::defend(command, state)),
+        ret_lang::Command::Flee(_) => Some(combat::flee(state)),
+        ret_lang::Command::Parley(command) => Some(combat::parley(command, state)),
+        _ => None,
+    };
+    if let Some(outcome) = moved {
+        let mut outcome = outcome?;
+        combat::enemy_turns(state, outcome.output_mut());
+        return Ok(outcome);
+    }
+    let output = match command {
+        ret_lang::Command::Examine(command) => examine(command.target.as_str(), state),
+        ret_lang::Command::Equip(command) => equip(command.target.as_str(), state),
+        ret_lang::Command::Unequip(_) => unequip(state),
+        ret_lang::Command::Use(command) => use_item(command.target.as_str(), command.count, state),
+        ret_lang::Command::Light(command) => light(command.target.as_str(), state),
+        ret_lang::Command::Extinguish(command) => extinguish(command.target.as_str(), state),
+        ret_lang::Command::Status(_) => Ok(status(state)),
+        ret_lang::Command::Inventory(_) => Ok(inventory(state)),
+        ret_lang::Command::Spells(_) => Ok(spells(state)),
+        ret_lang::Command::Rest(_) => Err(GameError::Refused(CANT_REST_MESSAGE)),
+        ret_lang::Command::Exit(_) => Ok(exit(state)),
+        _ => Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)),
+    };
+    output.map(Outcome::from)
+}
+
+/// A function that takes a command runs menu logic based on it.
 ///
-/// # Examples
-/// ```
-/// use retribution::game::interpreter;
-/// use retribution::game::map;
-/// use retribution::game::state;
-/// use retribution::game;
-/// use retribution::ret_lang;
+/// # Arguments
+/// * `command` - A reference to a command from the ret_lang module.
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the database path.
 ///
-/// let mut game_state = state::GameState::new();
-/// game_state.mode = state::Mode::Travel;
-/// let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
-/// let output = match interpreter::interpreter(&command, &mut game_state) {
-///   Ok(o) => o,
-///   Err(e) => e.to_string(),
-/// };
-/// assert_eq!(output, "Not able to do that action right now.");
-/// ```
-pub fn interpreter<'a>(
-    command: &'a ret_lang::Command,
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of the command, or an error message.
+fn menu_interpreter(
+    command: &ret_lang::Command,
     state: &mut state::GameState,
-) -> Result<String, &'a str> {
-    match state.mode {
-        state::Mode::Travel => travel_interpreter(command, state),
-        _ => Err("Not able to do that action right now."),
+    config: &config::GameConfig,
+) -> Result<CommandOutput, GameError> {
+    match command {
+        ret_lang::Command::Play(_) => {
+            state.mode = state::Mode::Travel;
+            let mut output = CommandOutput::from(String::from("You return to your adventure."));
+            if let Some(room) = state.current_room() {
+                output.push(state.describe(room));
+            }
+            Ok(output)
+        }
+        ret_lang::Command::Save(command) => {
+            let slot = command.target.as_deref().unwrap_or(save::DEFAULT_SLOT);
+            save::save_game(state, slot, config.db_path.as_str()).map_err(GameError::Save)?;
+            Ok(CommandOutput::from(format!(
+                "Game saved to slot '{}'.",
+                slot
+            )))
+        }
+        ret_lang::Command::Load(command) => {
+            let slot = command.target.as_deref().unwrap_or(save::DEFAULT_SLOT);
+            let mut loaded =
+                save::load_game(slot, config.db_path.as_str()).map_err(GameError::Save)?;
+            // The roller isn't saved, so keep the one the game is already using.
+            std::mem::swap(&mut loaded.roller, &mut state.roller);
+            *state = loaded;
+            state.mode = state::Mode::Menu;
+            Ok(CommandOutput::from(format!(
+                "Game loaded from slot '{}'.",
+                slot
+            )))
+        }
+        ret_lang::Command::Help(_) => Ok(CommandOutput::from(String::from(MENU_HELP_MESSAGE))),
+        ret_lang::Command::Validate(command) => validate(command.target.as_str(), config),
+        ret_lang::Command::Dump(_) if config.debug => dump(state),
+        ret_lang::Command::Status(_) => Ok(status(state)),
+        ret_lang::Command::Exit(_) => Ok(exit(state)),
+        _ => Err(GameError::Refused(NOT_IN_MENU_MESSAGE)),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::migration::map;
+/// A function that asks the hero to confirm they want to exit, and confirms the exit when they
+/// ask twice in a row.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `CommandOutput` - The confirmation prompt, or the farewell once the exit is confirmed.
+fn exit(state: &mut state::GameState) -> CommandOutput {
+    if !state.pending_exit {
+        state.pending_exit = true;
+        return CommandOutput::from(String::from(CONFIRM_EXIT_MESSAGE));
+    }
+    state.pending_exit = false;
+    state.exit_confirmed = true;
+    CommandOutput::from(String::from(EXIT_MESSAGE))
+}
 
-    /// Test the travel_interpreter function.
-    #[test]
-    fn travel_interpreter_test() {
-        let mut game_state = state::GameState::new();
-        let test_map = map::test_area();
-        game_state.map = Some(test_map);
-        game_state.room = Some((1, 1));
-        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
-        let output =
-            travel_interpreter(&command, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
-        assert_eq!(output, "Hero went north. This is room 4.");
+/// A function that names an item for the player, with the count when there is more than one.
+///
+/// # Arguments
+/// * `item` - A reference to the item to name.
+///
+/// # Returns
+/// * `String` - The name of the item, such as "arrow x5".
+fn counted(item: &item::Item) -> String {
+    match item.count {
+        1 => item.name.clone(),
+        count => format!("{} x{}", item.name, count),
+    }
+}
+
+/// A function that checks a count given to an item command against the size of the stack.
+///
+/// # Arguments
+/// * `count` - How many of the item the hero asked for, if they said.
+/// * `available` - How many of the item there are.
+///
+/// # Returns
+/// * `Result<(), GameError>` - Ok, or an error when there aren't that many.
+fn check_count(count: Option<u32>, available: u32) -> Result<(), GameError> {
+    match count {
+        Some(count) if count > available => Err(GameError::Refused(NOT_THAT_MANY_MESSAGE)),
+        _ => Ok(()),
     }
+}
 
-    // Test the travel_interpreter function with an invalid command.
-    #[test]
-    fn travel_interpreter_invalid_command_test() {
+/// A function that moves an item from the current room into the hero's inventory.
+/// Items that would put the hero over their carry capacity are left behind, unless they
+/// weigh nothing.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the item to take.
+/// * `count` - How many of the item to take. None, or more than there are, takes the whole
+///   stack.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of taking the item, or an error message.
+fn take_item(
+    name: &str,
+    count: Option<u32>,
+    state: &mut state::GameState,
+) -> Result<CommandOutput, GameError> {
+    let capacity = state.player.carry_capacity();
+    let current_weight = state.current_weight();
+    let room = state
+        .current_room_mut()
+        .ok_or(GameError::NoSuchTarget(NO_ITEM_MESSAGE))?;
+    let index =
+        item::resolve_item(name, &room.items)?.ok_or(GameError::NoSuchTarget(NO_ITEM_MESSAGE))?;
+    let stack = &room.items[index];
+    let mut output = CommandOutput::new();
+    if count.is_some_and(|c| c > stack.count) {
+        output.push(format!(
+            "There are only {}, so you take them all.",
+            stack.count
+        ));
+    }
+    let weight = stack.weight * count.map_or(stack.count, |c| c.min(stack.count));
+    if weight > 0 && current_weight + weight > capacity {
+        return Err(GameError::Refused(CARRY_LIMIT_MESSAGE));
+    }
+    let item = item::remove(&mut room.items, index, count);
+    output.push(format!("You take the {}.", counted(&item)));
+    state.events.push(Event::ItemTaken {
+        item: item.name.clone(),
+    });
+    item::add(&mut state.inventory, item);
+    Ok(output)
+}
+
+/// A function that takes every item in the current room, one at a time like `take_item`, and
+/// reports the items that didn't fit.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - A summary of what was taken, or an error message.
+fn take_all(state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let names: Vec<String> = state
+        .current_room()
+        .map(|r| r.items.iter().map(|i| i.name.clone()).collect())
+        .unwrap_or_default();
+    if names.is_empty() {
+        return Err(GameError::Refused(NOTHING_TO_TAKE_MESSAGE));
+    }
+    let mut taken = vec![];
+    let mut left = vec![];
+    let mut failed = vec![];
+    for name in names {
+        match take_item(name.as_str(), None, state) {
+            Ok(_) => taken.push(name),
+            Err(GameError::Refused(CARRY_LIMIT_MESSAGE)) => left.push(name),
+            Err(e) => failed.push(format!("{}: {}", name, e)),
+        }
+    }
+    let mut output = CommandOutput::new();
+    if !taken.is_empty() {
+        output.push(format!("You take: {}.", taken.join(", ")));
+    }
+    if !left.is_empty() {
+        output.push(format!("You can't carry: {}.", left.join(", ")));
+    }
+    output.lines.append(&mut failed);
+    Ok(output)
+}
+
+/// A function that drops every item in the hero's inventory into the current room, one at a
+/// time like `drop_item`. Nothing is dropped without a room to drop it in, and any item that
+/// can't be dropped is reported next to the ones that were.
+///
+/// # Arguments
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - A summary of what was dropped, or an error message.
+fn drop_all(state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let names: Vec<String> = state.inventory.iter().map(|i| i.name.clone()).collect();
+    if names.is_empty() {
+        return Err(GameError::Refused(NOTHING_TO_DROP_MESSAGE));
+    }
+    if state.current_room().is_none() {
+        return Err(GameError::Refused(NOWHERE_TO_DROP_MESSAGE));
+    }
+    let mut dropped = vec![];
+    let mut failed = vec![];
+    for name in names {
+        match drop_item(name.as_str(), None, state) {
+            Ok(_) => dropped.push(name),
+            Err(e) => failed.push(format!("{}: {}", name, e)),
+        }
+    }
+    let mut output = CommandOutput::new();
+    if !dropped.is_empty() {
+        output.push(format!("You drop: {}.", dropped.join(", ")));
+    }
+    output.lines.append(&mut failed);
+    Ok(output)
+}
+
+/// A function that moves an item from the hero's inventory to an ally in their party. Enemies
+/// in the room won't take anything.
+///
+/// # Arguments
+/// * `command` - A reference to the give command.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of giving the item, or an error message.
+fn give(
+    command: &ret_lang::GiveCommand,
+    state: &mut state::GameState,
+) -> Result<CommandOutput, GameError> {
+    let index = state
+        .inventory
+        .iter()
+        .position(|i| i.name.eq_ignore_ascii_case(command.item.as_str()))
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    if state.ally_mut(command.target.as_str()).is_none() {
+        let is_enemy = state.current_room().is_some_and(|r| {
+            r.enemies
+                .iter()
+                .any(|e| e.name.eq_ignore_ascii_case(command.target.as_str()))
+        });
+        return Err(match is_enemy {
+            true => GameError::Refused(NOT_AN_ALLY_MESSAGE),
+            false => GameError::NoSuchTarget(NO_ONE_TO_GIVE_MESSAGE),
+        });
+    }
+    let item = item::remove(&mut state.inventory, index, None);
+    let ally = state
+        .ally_mut(command.target.as_str())
+        .ok_or(GameError::NoSuchTarget(NO_ONE_TO_GIVE_MESSAGE))?;
+    let output = format!("You give the {} to the {}.", counted(&item), ally.name);
+    item::add(&mut ally.inventory, item);
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that uses an item from the hero's inventory, applying its effect. Consumable
+/// items are used up.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the item to use.
+/// * `count` - How many times to use the item. None uses it once.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of using the item, or an error message.
+fn use_item(
+    name: &str,
+    count: Option<u32>,
+    state: &mut state::GameState,
+) -> Result<CommandOutput, GameError> {
+    let index = state
+        .inventory
+        .iter()
+        .position(|i| i.name.eq_ignore_ascii_case(name))
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    let item = &state.inventory[index];
+    check_count(count, item.count)?;
+    let output = match item.effect.ok_or(GameError::Refused(CANT_USE_MESSAGE))? {
+        Effect::Heal(amount) => {
+            // Don't waste the item when it would do nothing.
+            if state.player.hp >= state.player.max_hp {
+                return Err(GameError::Refused(FULL_HEALTH_MESSAGE));
+            }
+            let mut used = item::Item {
+                count: 0,
+                ..item.clone()
+            };
+            let mut healed = 0;
+            while used.count < count.unwrap_or(1) && state.player.hp < state.player.max_hp {
+                healed += state.player.heal(amount);
+                used.count += 1;
+            }
+            if used.consumable {
+                item::remove(&mut state.inventory, index, Some(used.count));
+            }
+            format!("You use the {} and heal {} HP.", counted(&used), healed)
+        }
+    };
+    Ok(CommandOutput::from(output))
+}
+
+/// A function that lights a light the hero is carrying or wielding.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the light.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of lighting the light, or an error message.
+fn light(name: &str, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let item = state
+        .inventory
+        .iter_mut()
+        .chain(state.player.weapon.iter_mut())
+        .find(|i| i.name.eq_ignore_ascii_case(name))
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    if !item.light {
+        return Err(GameError::Refused(NOT_A_LIGHT_MESSAGE));
+    }
+    if item.burned_out() {
+        return Err(GameError::Refused(BURNED_OUT_MESSAGE));
+    }
+    if item.lit {
+        return Err(GameError::Refused(ALREADY_LIT_MESSAGE));
+    }
+    item.lit = true;
+    Ok(CommandOutput::from(format!("You light the {}.", item.name)))
+}
+
+/// A function that puts out a light the hero is carrying or wielding, saving what is left of it.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the light.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of putting out the light, or an error message.
+fn extinguish(name: &str, state: &mut state::GameState) -> Result<CommandOutput, GameError> {
+    let item = state
+        .inventory
+        .iter_mut()
+        .chain(state.player.weapon.iter_mut())
+        .find(|i| i.name.eq_ignore_ascii_case(name))
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    if !item.is_lit() {
+        return Err(GameError::Refused(NOT_LIT_MESSAGE));
+    }
+    item.lit = false;
+    Ok(CommandOutput::from(format!(
+        "You put out the {}.",
+        item.name
+    )))
+}
+
+/// A function that moves an item from the hero's inventory into the current room.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the item to drop.
+/// * `count` - How many of the item to drop. None, or more than the hero has, drops the whole
+///   stack.
+/// * `state` - A mutable reference to a GameState.
+///
+/// # Returns
+/// * `Result<CommandOutput, GameError>` - The output of dropping the item, or an error message.
+fn drop_item(
+    name: &str,
+    count: Option<u32>,
+    state: &mut state::GameState,
+) -> Result<CommandOutput, GameError> {
+    let index = item::resolve_item(name, &state.inventory)?
+        .ok_or(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))?;
+    // There is nowhere to put the item without a room, so the hero keeps it.
+    if state.current_room().is_none() {
+        return Err(GameError::Refused(NOWHERE_TO_DROP_MESSAGE));
+    }
+    let mut output = CommandOutput::new();
+    let held = state.inventory[index].count;
+    if count.is_some_and(|c| c > held) {
+        output.push(format!("You only have {}, so you drop them all.", held));
+    }
+    let item = item::remove(&mut state.inventory, index, count);
+    output.push(format!("You drop the {}.", counted(&item)));
+    if let Some(room) = state.current_room_mut() {
+        item::add(&mut room.items, item);
+    }
+    Ok(output)
+}
+
+/// A function that takes a command runs game logic based on it.
+///
+/// # Arguments
+/// * `command` - A reference to a command from the ret_lang module.
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the messages.
+///
+/// # Returns
+/// * `Result<String, GameError>` - A string that is the output of the command, or an error.
+///
+/// # Examples
+/// ```
+/// use retribution::game::config;
+/// use retribution::game::interpreter;
+/// use retribution::game::map;
+/// use retribution::game::state;
+/// use retribution::game;
+/// use retribution::ret_lang;
+///
+/// let mut game_state = state::GameState::new();
+/// game_state.mode = state::Mode::Travel;
+/// let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+/// let game_config = config::GameConfig::new();
+/// let output = match interpreter::interpreter(&command, &mut game_state, &game_config) {
+///   Ok(o) => o,
+///   Err(e) => e.to_string(),
+/// };
+/// assert_eq!(output, "Not able to do that action right now.");
+/// ```
+pub fn interpreter(
+    command: &ret_lang::Command,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<String, GameError> {
+    interpret(command, state, config).map(|o| o.to_string())
+}
+
+/// A function that takes a command runs game logic based on it, keeping each line of output. A
+/// target of "it" stands for the item or creature named by the last command that worked.
+///
+/// # Arguments
+/// * `command` - A reference to a command from the ret_lang module.
+/// * `state` - A mutable reference to a GameState.
+/// * `config` - A reference to the game config holding the messages.
+///
+/// # Returns
+/// * `Result<Outcome, GameError>` - What the command did, or an error message.
+///
+/// # Examples
+/// ```
+/// use retribution::game::config;
+/// use retribution::game::interpreter;
+/// use retribution::game::state;
+/// use retribution::ret_lang;
+///
+/// let mut game_state = state::GameState::new();
+/// let command = ret_lang::parse_input("exit").unwrap_or_else(|e| panic!("{}", e));
+/// let game_config = config::GameConfig::new();
+/// let output = interpreter::interpret(&command, &mut game_state, &game_config).unwrap();
+/// assert_eq!(output.to_string(), "Are you sure? Type 'exit' again to quit.");
+/// let output = interpreter::interpret(&command, &mut game_state, &game_config).unwrap();
+/// assert!(matches!(output, interpreter::Outcome::GameOver(_)));
+/// assert_eq!(output.output().lines, vec!["Farewell hero."]);
+/// ```
+pub fn interpret(
+    command: &ret_lang::Command,
+    state: &mut state::GameState,
+    config: &config::GameConfig,
+) -> Result<Outcome, GameError> {
+    let command = &resolve_it(command, state.last_target.as_deref())?;
+    // "all" names every item at once, so "it" keeps pointing at the last single thing named.
+    let named = command
+        .target()
+        .filter(|t| !t.eq_ignore_ascii_case(ALL))
+        .map(|t| full_name(t, state));
+    // Any command other than exit cancels an exit waiting to be confirmed.
+    if !matches!(command, ret_lang::Command::Exit(_)) {
+        state.pending_exit = false;
+    }
+    // Time stands still while the game is paused in the menu.
+    let timed = state.mode != state::Mode::Menu;
+    let mut outcome = match state.mode {
+        state::Mode::Travel => travel_interpreter(command, state, config),
+        state::Mode::Combat => combat_interpreter(command, state),
+        state::Mode::Menu => menu_interpreter(command, state, config).map(Outcome::from),
+    }?;
+    // Only a command that was carried out takes a turn, so a typo doesn't burn the lights. A
+    // light that burns out does so at the end of the turn.
+    if timed {
+        state.turn += 1;
+        let mut burned = state.burn_lights();
+        outcome.output_mut().lines.append(&mut burned);
+    }
+    if named.is_some() {
+        state.last_target = named;
+    }
+    if state.exit_confirmed || !state.player.is_alive() {
+        outcome = Outcome::GameOver(outcome.into_output());
+    }
+    Ok(outcome)
+}
+
+/// A function that copies a command, aiming each "it" in it at what the hero last named instead.
+/// The item handed over by a give command can be "it" as well as who receives it.
+///
+/// # Arguments
+/// * `command` - A reference to the command.
+/// * `last_target` - The item or creature the hero last named, if they have named one.
+///
+/// # Returns
+/// * `Result<ret_lang::Command, GameError>` - The command aimed at the last target, or an error
+///   when "it" doesn't refer to anything yet.
+fn resolve_it(
+    command: &ret_lang::Command,
+    last_target: Option<&str>,
+) -> Result<ret_lang::Command, GameError> {
+    let mut command = command.clone();
+    let names: Vec<&mut String> = match &mut command {
+        ret_lang::Command::Give(give) => vec![&mut give.item, &mut give.target],
+        other => other.target_mut().into_iter().collect(),
+    };
+    for name in names.into_iter().filter(|n| n.eq_ignore_ascii_case(IT)) {
+        let last_target = last_target.ok_or(GameError::NoSuchTarget(UNKNOWN_IT_MESSAGE))?;
+        *name = String::from(last_target);
+    }
+    Ok(command)
+}
+
+/// A function that finds the full name of the item the hero named, looking on the floor, in
+/// their pack, and in their hand, so "it" later stands for the item itself rather than what was
+/// typed, such as the "rusty sword" for `rust`. Names that aren't of a single item are kept as
+/// they are.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name the hero gave.
+/// * `state` - A reference to a GameState.
+///
+/// # Returns
+/// * `String` - The full name of the item, or the name the hero gave.
+fn full_name(name: &str, state: &state::GameState) -> String {
+    let floor = state.current_room().map(|r| r.items.as_slice());
+    [
+        floor.unwrap_or_default(),
+        state.inventory.as_slice(),
+        state.player.weapon.as_slice(),
+    ]
+    .into_iter()
+    .find_map(|items| match item::resolve_item(name, items) {
+        Ok(Some(index)) => Some(items[index].name.clone()),
+        _ => None,
+    })
+    .unwrap_or_else(|| String::from(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ally::Ally;
+    use crate::game::dice::FixedRoller;
+    use crate::game::item::Item;
+    use crate::game::map::Coord;
+    use crate::migration::map;
+
+    /// Create a game state in room 1 of the test area with the given items on the floor.
+    fn test_state(items: Vec<Item>) -> state::GameState {
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(map::test_area());
+        game_state.room = Some(Coord::new(1, 1));
+        game_state.current_room_mut().unwrap().items = items;
+        game_state
+    }
+
+    /// Create an item with a weight.
+    fn item(name: &str, weight: u32) -> Item {
+        Item::new(String::from(name), format!("A {}.", name), weight)
+    }
+
+    /// Test that resuming from the menu returns the hero to their travels.
+    #[test]
+    fn menu_resume_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.mode = state::Mode::Menu;
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("resume").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(game_state.mode, state::Mode::Travel);
+        assert_eq!(
+            output.output().lines,
+            vec!["You return to your adventure.", "This is room 1."]
+        );
+
+        let command = ret_lang::parse_input("pause").unwrap_or_else(|e| panic!("{}", e));
+        interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(game_state.mode, state::Mode::Menu);
+    }
+
+    /// Test that commands for the adventure can't be used from the menu.
+    #[test]
+    fn menu_disallowed_command_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.mode = state::Mode::Menu;
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::Refused(NOT_IN_MENU_MESSAGE)));
+        assert_eq!(game_state.room, Some(Coord::new(1, 1)));
+    }
+
+    /// Test saving and loading a game from the menu.
+    #[test]
+    fn menu_save_load_test() {
+        let path = "menu_save_load_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        game_state.mode = state::Mode::Menu;
+        let save = ret_lang::parse_input("save").unwrap_or_else(|e| panic!("{}", e));
+        let saved = interpret(&save, &mut game_state, &game_config);
+        game_state.room = Some(Coord::new(0, 1));
+        let load = ret_lang::parse_input("load").unwrap_or_else(|e| panic!("{}", e));
+        let loaded = interpret(&load, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            saved.unwrap().output().lines,
+            vec!["Game saved to slot 'default'."]
+        );
+        assert_eq!(
+            loaded.unwrap().output().lines,
+            vec!["Game loaded from slot 'default'."]
+        );
+        assert_eq!(game_state.room, Some(Coord::new(1, 1)));
+        assert_eq!(game_state.mode, state::Mode::Menu);
+    }
+
+    /// Test that commands advance the turn counter, and that it is saved.
+    #[test]
+    fn turn_counter_test() {
+        let path = "turn_counter_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        for input in ["go north", "go south", "status", "menu", "save", "status"] {
+            let command = ret_lang::parse_input(input).unwrap_or_else(|e| panic!("{}", e));
+            let _ = interpret(&command, &mut game_state, &game_config);
+        }
+        let loaded = save::load_game(save::DEFAULT_SLOT, path);
+        std::fs::remove_file(path).unwrap();
+        // The menu command takes a turn, but nothing in the menu does.
+        assert_eq!(game_state.turn, 4);
+        assert_eq!(loaded.unwrap().turn, 4);
+    }
+
+    /// Test that a forced encounter roll on moving spawns a goblin and starts a fight.
+    #[test]
+    fn go_random_encounter_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.roller = Box::new(FixedRoller::new(vec![1, 1]));
+        let mut game_config = config::GameConfig::new();
+        game_config.encounter_chance = 25;
+        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().output().lines,
+            vec!["Hero went north. This is room 4.", "A goblin appears!"]
+        );
+        assert_eq!(game_state.mode, state::Mode::Combat);
+        assert_eq!(game_state.current_room().unwrap().enemies[0].name, "goblin");
+    }
+
+    /// Test that the game is over when the hero falls.
+    #[test]
+    fn game_over_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                6,
+            ));
+        game_state.mode = state::Mode::Combat;
+        game_state.player.hp = 1;
+        game_state.roller = Box::new(FixedRoller::new(vec![1, 1, 6]));
+        let command = ret_lang::parse_input("flee").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert!(matches!(output, Outcome::GameOver(_)));
+        assert!(output
+            .output()
+            .lines
+            .contains(&String::from("You have fallen.")));
+    }
+
+    /// Test that an enemy takes its turn after the hero's attack.
+    #[test]
+    fn combat_interpreter_enemy_turn_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        game_state.mode = state::Mode::Combat;
+        let hp = game_state.player.hp;
+        game_state.roller = Box::new(FixedRoller::new(vec![6, 5, 4, 3]));
+        let command = ret_lang::parse_input("attack goblin").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1..],
+            [
+                "You hit the goblin and deal 4 damage.",
+                "The goblin attacks and deals 3 damage."
+            ]
+        );
+        assert_eq!(game_state.player.hp, hp - 3);
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 6);
+    }
+
+    /// Test that an enemy that strikes back on a weak hit doesn't also take its turn that round.
+    #[test]
+    fn combat_interpreter_strike_back_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        game_state.mode = state::Mode::Combat;
+        let hp = game_state.player.hp;
+        game_state.roller = Box::new(FixedRoller::new(vec![3, 4, 2, 5, 6, 6, 1, 3]));
+        let command = ret_lang::parse_input("attack goblin").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        let hits = output
+            .output()
+            .lines
+            .iter()
+            .filter(|l| l.starts_with("The goblin"))
+            .count();
+        assert_eq!(hits, 1);
+        assert_eq!(game_state.player.hp, hp - 5);
+
+        // The next round, the goblin takes its turn again.
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines.last().unwrap(),
+            "The goblin attacks and deals 3 damage."
+        );
+    }
+
+    /// Test that "it" stands for the creature the hero last named.
+    #[test]
+    fn interpret_it_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        game_state.mode = state::Mode::Combat;
+        game_state.roller = Box::new(FixedRoller::new(vec![6, 5, 4, 3]));
+        let game_config = config::GameConfig::new();
+        let examine = ret_lang::parse_input("examine goblin").unwrap_or_else(|e| panic!("{}", e));
+        interpret(&examine, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(game_state.last_target, Some(String::from("goblin")));
+        let attack = ret_lang::parse_input("attack it").unwrap_or_else(|e| panic!("{}", e));
+        let output =
+            interpret(&attack, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines[1],
+            "You hit the goblin and deal 4 damage."
+        );
+        assert_eq!(game_state.current_room().unwrap().enemies[0].hp, 6);
+        assert_eq!(game_state.last_target, Some(String::from("goblin")));
+    }
+
+    /// Test that a command that fails doesn't take a turn, so a light about to burn out is still
+    /// lit, and the hero is told when it does burn out.
+    #[test]
+    fn interpret_failed_command_keeps_light_test() {
+        let mut game_state = test_state(vec![]);
+        let mut torch = Item::torch(String::from("torch"), String::from("Pitch."), 1, 1);
+        torch.lit = true;
+        game_state.inventory.push(torch);
+        let game_config = config::GameConfig::new();
+        let take = ret_lang::parse_input("take sword").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&take, &mut game_state, &game_config);
+        assert_eq!(output.err(), Some(GameError::NoSuchTarget(NO_ITEM_MESSAGE)));
+        assert_eq!(game_state.turn, 0);
+        assert!(game_state.has_light());
+        let look = ret_lang::parse_input("look").unwrap_or_else(|e| panic!("{}", e));
+        let output =
+            interpret(&look, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["This is room 1.", "Your torch burns out."]
+        );
+        assert_eq!(game_state.turn, 1);
+        assert!(!game_state.has_light());
+    }
+
+    /// Test that "it" isn't taken to mean everything after taking all.
+    #[test]
+    fn interpret_it_after_take_all_test() {
+        let mut game_state = test_state(vec![item("sword", 3), item("shield", 2)]);
+        let game_config = config::GameConfig::new();
+        let parse = |input| ret_lang::parse_input(input).unwrap_or_else(|e| panic!("{}", e));
+        interpret(&parse("take all"), &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(game_state.last_target, None);
+        assert_eq!(
+            interpret(&parse("drop it"), &mut game_state, &game_config).err(),
+            Some(GameError::NoSuchTarget(UNKNOWN_IT_MESSAGE))
+        );
+        assert_eq!(game_state.inventory.len(), 2);
+
+        interpret(&parse("examine sword"), &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&parse("drop it"), &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You drop the sword.");
+        assert_eq!(game_state.inventory, vec![item("shield", 2)]);
+    }
+
+    /// Test that "it" stands for the full name of the item the hero named, including as the
+    /// item of a give command.
+    #[test]
+    fn interpret_it_give_test() {
+        let mut game_state = test_state(vec![item("rusty sword", 2), item("rusty shield", 2)]);
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            4,
+        ));
+        let game_config = config::GameConfig::new();
+        let parse = |input| ret_lang::parse_input(input).unwrap_or_else(|e| panic!("{}", e));
+        interpret(&parse("take sw"), &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(game_state.last_target, Some(String::from("rusty sword")));
+        let output = interpret(&parse("give it to squire"), &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["You give the rusty sword to the squire."]
+        );
+        assert_eq!(game_state.allies[0].inventory, vec![item("rusty sword", 2)]);
+    }
+
+    /// Test that "it" is refused before the hero has named anything.
+    #[test]
+    fn interpret_it_unknown_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("take it").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output.err(),
+            Some(GameError::NoSuchTarget(UNKNOWN_IT_MESSAGE))
+        );
+        assert_eq!(game_state.turn, 0);
+        assert_eq!(
+            GameError::NoSuchTarget(UNKNOWN_IT_MESSAGE).to_string(),
+            "I don't know what 'it' refers to."
+        );
+    }
+
+    /// Test that an enemy that yields to a parley ends the fight.
+    #[test]
+    fn combat_interpreter_parley_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        game_state.start_combat();
+        game_state.roller = Box::new(FixedRoller::new(vec![6, 5]));
+        let command = ret_lang::parse_input("parley goblin").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines[1..], ["The goblin yields and flees."]);
+        assert_eq!(game_state.mode, state::Mode::Travel);
+        assert!(game_state.combat.is_none());
+        assert!(game_state.current_room().unwrap().enemies.is_empty());
+    }
+
+    /// Test that a dark room can only be seen with a light.
+    #[test]
+    fn dark_room_test() {
+        let mut game_state = test_state(vec![]);
+        if let Some(crate::game::map::GridSquare::Room(r)) = game_state
+            .map
+            .as_mut()
+            .and_then(|m| m.get_grid_square_mut(Coord::new(0, 1)))
+        {
+            r.dark = true;
+        }
+        let game_config = config::GameConfig::new();
+        let go_north = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let look = ret_lang::parse_input("look").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&go_north, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().to_string(),
+            "Hero went north. It's pitch black."
+        );
+        let output = interpret(&look, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), state::DARK_MESSAGE);
+
+        let mut torch = Item::torch(String::from("torch"), String::from("Burning."), 1, 10);
+        torch.lit = true;
+        game_state.inventory.push(torch);
+        let output = interpret(&look, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), "This is room 4.");
+    }
+
+    /// Test that a torch burns down over turns until a dark room can't be seen.
+    #[test]
+    fn torch_burns_out_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.current_room_mut().unwrap().dark = true;
+        game_state.inventory.push(Item::torch(
+            String::from("torch"),
+            String::from("Pitch."),
+            1,
+            3,
+        ));
+        let game_config = config::GameConfig::new();
+        let parse = |input| ret_lang::parse_input(input).unwrap_or_else(|e| panic!("{}", e));
+        let look = parse("look");
+        let light = parse("light torch");
+        let douse = parse("douse torch");
+
+        let output = interpret(&light, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), "You light the torch.");
+        let output = interpret(&douse, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), "You put out the torch.");
+        // The torch burned for the turn it was lit, but not while it is out.
+        assert_eq!(game_state.inventory[0].turns_remaining, Some(2));
+        let output = interpret(&light, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), "You light the torch.");
+
+        let output = interpret(&look, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().output().lines,
+            vec!["This is room 1.", "Your torch burns out."]
+        );
+        let output = interpret(&look, &mut game_state, &game_config);
+        assert_eq!(output.unwrap().to_string(), state::DARK_MESSAGE);
+        let output = interpret(&light, &mut game_state, &game_config);
+        assert_eq!(output.err(), Some(GameError::Refused(BURNED_OUT_MESSAGE)));
+    }
+
+    /// Test the travel_interpreter function.
+    #[test]
+    fn travel_interpreter_test() {
+        let mut game_state = state::GameState::new();
+        let test_map = map::test_area();
+        game_state.map = Some(test_map);
+        game_state.room = Some(Coord::new(1, 1));
+        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output = travel_interpreter(&command, &mut game_state, &game_config)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "Hero went north. This is room 4.");
+        assert!(matches!(
+            output,
+            Outcome::Moved { from, to, .. } if from == Coord::new(1, 1) && to == Coord::new(0, 1)
+        ));
+    }
+
+    /// Test that the inventory is listed in name order with identical items stacked.
+    #[test]
+    fn inventory_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("inventory").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(output.output().lines, vec![EMPTY_INVENTORY_MESSAGE]);
+
+        game_state.inventory = vec![item("sword", 2)];
+        game_state.inventory.extend(vec![item("arrow", 0); 20]);
+        game_state.inventory.push(item("bow", 2));
+        let command = ret_lang::parse_input("i").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(
+            output.output().lines,
+            vec!["arrow x20", "bow", "sword", "Load: 4/10"]
+        );
+        assert_eq!(game_state.inventory.len(), 22);
+    }
+
+    /// Test validating a map from the menu, and one that doesn't exist.
+    #[test]
+    fn menu_validate_test() {
+        let path = "menu_validate_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        game_state.mode = state::Mode::Menu;
+        let mut outputs = vec![];
+        for sentence in ["validate Test Area", "validate Nowhere"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            outputs.push(interpret(&command, &mut game_state, &game_config));
+        }
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            outputs[0].as_ref().unwrap().output().lines,
+            vec!["Test Area has no issues."]
+        );
+        assert_eq!(outputs[1], Err(GameError::NoSuchTarget(NO_MAP_MESSAGE)));
+    }
+
+    /// Test that where reports the map, coordinates, and room after a move.
+    #[test]
+    fn travel_interpreter_where_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("go east").unwrap_or_else(|e| panic!("{}", e));
+        travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        let command = ret_lang::parse_input("where").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(output.output().lines, vec!["Test Area (1, 2): Room 3"]);
+
+        game_state.room = None;
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)));
+    }
+
+    /// Test going through a named exit and along the compass from the same room.
+    #[test]
+    fn travel_interpreter_named_exit_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .exits
+            .insert(String::from("ladder"), Coord::new(1, 2));
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("go ladder").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().to_string(),
+            "Hero went through the ladder. This is room 3."
+        );
+        assert_eq!(game_state.room, Some(Coord::new(1, 2)));
+
+        game_state.room = Some(Coord::new(1, 1));
+        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().to_string(),
+            "Hero went north. This is room 4."
+        );
+
+        let command = ret_lang::parse_input("go cellar").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::InvalidDirection));
+    }
+
+    /// Test moving diagonally on a map that allows it and one that doesn't.
+    #[test]
+    fn travel_interpreter_diagonal_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.room = Some(Coord::new(1, 0));
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("go ne").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::Refused(NO_DIAGONAL_MESSAGE)));
+        assert_eq!(game_state.room, Some(Coord::new(1, 0)));
+
+        game_state.map.as_mut().unwrap().allow_diagonal = true;
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output.unwrap().to_string(),
+            "Hero went northeast. This is room 4."
+        );
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+    }
+
+    /// Test that moving north or west from the corner of the map is rejected.
+    #[test]
+    fn travel_interpreter_edge_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.room = Some(Coord::new(0, 0));
+        let game_config = config::GameConfig::new();
+        for sentence in ["go north", "go west"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            let output = travel_interpreter(&command, &mut game_state, &game_config);
+            assert_eq!(output, Err(GameError::InvalidDirection));
+            assert_eq!(game_state.room, Some(Coord::new(0, 0)));
+        }
+    }
+
+    /// Test that dump shows the game state as JSON that reads back, but only in debug mode.
+    #[test]
+    fn travel_interpreter_dump_test() {
+        let mut game_state = test_state(vec![item("torch", 1)]);
+        game_state.player.hp = 3;
+        let mut game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("dump").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)));
+
+        game_config.debug = true;
+        let output = travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        let json = output.output().lines.join("\n");
+        let dumped: state::GameState = serde_json::from_str(json.as_str()).unwrap();
+        assert_eq!(dumped.current_map_name(), Some("Test Area"));
+        assert_eq!(dumped.room, Some(Coord::new(1, 1)));
+        assert_eq!(dumped.player.hp, 3);
+        assert_eq!(
+            serde_json::to_value(&dumped).unwrap(),
+            serde_json::to_value(&game_state).unwrap()
+        );
+    }
+
+    /// Test that going back returns the hero to the room they came from.
+    #[test]
+    fn travel_interpreter_back_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(
+            game_state.previous,
+            Some((String::from("Test Area"), Coord::new(1, 1)))
+        );
+
+        let command = ret_lang::parse_input("back").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(
+            output.output().lines,
+            vec!["Hero went south. This is room 1."]
+        );
+        assert_eq!(game_state.room, Some(Coord::new(1, 1)));
+        assert_eq!(
+            game_state.previous,
+            Some((String::from("Test Area"), Coord::new(0, 1)))
+        );
+    }
+
+    /// Test that going back fails without a previous room or a way to it.
+    #[test]
+    fn travel_interpreter_back_refused_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("back").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::Refused(CANT_GO_BACK_MESSAGE)));
+
+        // Room 2 isn't next to room 4, so there's no way back to it.
+        game_state.room = Some(Coord::new(0, 1));
+        game_state.previous = Some((String::from("Test Area"), Coord::new(1, 0)));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::Refused(CANT_GO_BACK_MESSAGE)));
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+    }
+
+    /// Test that reload picks up edits to the map, keeping the hero in their room while it's
+    /// still there.
+    #[test]
+    fn travel_interpreter_reload_test() {
+        let path = "travel_interpreter_reload_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_state = test_state(vec![]);
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let command = ret_lang::parse_input("reload").unwrap_or_else(|e| panic!("{}", e));
+        let refused = travel_interpreter(&command, &mut game_state, &game_config);
+
+        game_config.debug = true;
+        let mut edited = map::test_area();
+        if let Some(crate::game::map::GridSquare::Room(room)) =
+            edited.get_grid_square_mut(Coord::new(1, 1))
+        {
+            room.description = String::from("This is room 1, freshly painted.");
+        }
+        crate::game::map::save_map(&edited, Some(String::from(path)), true).unwrap();
+        let repainted = travel_interpreter(&command, &mut game_state, &game_config);
+        let room = game_state.room;
+
+        game_state.room = Some(Coord::new(1, 2));
+        edited.grid[1][2] = None;
+        crate::game::map::save_map(&edited, Some(String::from(path)), true).unwrap();
+        let clamped = travel_interpreter(&command, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            refused,
+            Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
+        );
+        assert_eq!(
+            repainted.unwrap().output().lines,
+            vec!["Reloaded Test Area (1, 1). This is room 1, freshly painted."]
+        );
+        assert_eq!(room, Some(Coord::new(1, 1)));
+        assert_eq!(
+            clamped.unwrap().output().lines,
+            vec!["Reloaded Test Area (0, 1). This is room 4."]
+        );
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+    }
+
+    /// Test that warp jumps to a room of another map, but only in debug mode and into a room.
+    #[test]
+    fn travel_interpreter_warp_test() {
+        let path = "travel_interpreter_warp_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_state = test_state(vec![]);
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let command =
+            ret_lang::parse_input("warp \"Test Area 2\" 1 0").unwrap_or_else(|e| panic!("{}", e));
+        let refused = travel_interpreter(&command, &mut game_state, &game_config);
+
+        game_config.debug = true;
+        let missing = ret_lang::parse_input("warp Nowhere").unwrap_or_else(|e| panic!("{}", e));
+        let no_map = travel_interpreter(&missing, &mut game_state, &game_config);
+        let portal =
+            ret_lang::parse_input("warp Test Area 2 0 0").unwrap_or_else(|e| panic!("{}", e));
+        let no_room = travel_interpreter(&portal, &mut game_state, &game_config);
+        let stayed = game_state.current_map_name().map(String::from);
+        let warped = travel_interpreter(&command, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            refused,
+            Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
+        );
+        assert_eq!(no_map, Err(GameError::NoSuchTarget(NO_MAP_MESSAGE)));
+        assert_eq!(no_room, Err(GameError::NoSuchTarget(NO_ROOM_MESSAGE)));
+        assert_eq!(stayed.as_deref(), Some("Test Area"));
+        assert_eq!(
+            warped.unwrap().output().lines,
+            vec!["Hero jumps to Test Area 2 (1, 0). This is in test area 2."]
+        );
+        assert_eq!(game_state.current_map_name(), Some("Test Area 2"));
+        assert_eq!(game_state.room, Some(Coord::new(1, 0)));
+    }
+
+    /// Test that there's no going back after a warp, since the hero didn't step there.
+    #[test]
+    fn travel_interpreter_back_after_warp_test() {
+        let path = "travel_interpreter_back_after_warp_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_state = test_state(vec![]);
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        game_config.debug = true;
+        let go = ret_lang::parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+        let warp =
+            ret_lang::parse_input("warp \"Test Area 2\" 1 0").unwrap_or_else(|e| panic!("{}", e));
+        let back = ret_lang::parse_input("back").unwrap_or_else(|e| panic!("{}", e));
+        travel_interpreter(&go, &mut game_state, &game_config).unwrap();
+        let warped = travel_interpreter(&warp, &mut game_state, &game_config);
+        let output = travel_interpreter(&back, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+
+        warped.unwrap();
+        assert_eq!(game_state.previous, None);
+        assert_eq!(output, Err(GameError::Refused(CANT_GO_BACK_MESSAGE)));
+        assert_eq!(game_state.current_map_name(), Some("Test Area 2"));
+        assert_eq!(game_state.room, Some(Coord::new(1, 0)));
+    }
+
+    /// Test that goto jumps to a room, but only in debug mode and inside the map.
+    #[test]
+    fn travel_interpreter_goto_test() {
+        let mut game_state = test_state(vec![]);
+        let mut game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("goto 0 1").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)));
+
+        game_config.debug = true;
+        let output = travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(
+            output.output().lines,
+            vec!["Hero jumps to Test Area (0, 1). This is room 4."]
+        );
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+
+        for (sentence, error) in [
+            ("goto 3 0", OUTSIDE_MAP_MESSAGE),
+            ("goto 0 0", NO_ROOM_MESSAGE),
+        ] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            let output = travel_interpreter(&command, &mut game_state, &game_config);
+            assert_eq!(output, Err(GameError::NoSuchTarget(error)));
+        }
+        assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+    }
+
+    /// Test walking through the portal into Test Area 2 and back to where the hero started.
+    #[test]
+    fn travel_interpreter_portal_round_trip_test() {
+        let path = "portal_round_trip_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        let mut outputs = vec![];
+        for sentence in ["go south", "go north"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            outputs.push(
+                travel_interpreter(&command, &mut game_state, &game_config)
+                    .map_err(|e| e.to_string()),
+            );
+        }
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            outputs[0].as_ref().unwrap().output().lines,
+            vec!["Hero went south. This is in test area 2."]
+        );
+        assert_eq!(
+            outputs[1].as_ref().unwrap().output().lines,
+            vec!["Hero went north. This is room 1."]
+        );
+        assert_eq!(game_state.map.as_ref().unwrap().name, "Test Area");
+        assert_eq!(game_state.room, Some(Coord::new(1, 1)));
+    }
+
+    /// Test that a locked portal turns the hero away until they hold the key.
+    #[test]
+    fn travel_interpreter_locked_portal_test() {
+        let path = "locked_portal_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![item("key", 0)]);
+        if let Some(crate::game::map::GridSquare::Portal(portal)) = game_state
+            .map
+            .as_mut()
+            .unwrap()
+            .get_grid_square_mut(Coord::new(2, 1))
+        {
+            portal.required_item = Some(String::from("key"));
+        }
+        let go_south = ret_lang::parse_input("go south").unwrap_or_else(|e| panic!("{}", e));
+        let locked = travel_interpreter(&go_south, &mut game_state, &game_config);
+        let take = ret_lang::parse_input("take key").unwrap_or_else(|e| panic!("{}", e));
+        travel_interpreter(&take, &mut game_state, &game_config).unwrap();
+        let unlocked = travel_interpreter(&go_south, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            locked.map_err(|e| e.to_string()),
+            Err(String::from("The door is locked; you need the key."))
+        );
+        assert_eq!(
+            unlocked.unwrap().output().lines,
+            vec!["Hero went south. This is in test area 2."]
+        );
+        assert_eq!(game_state.map.as_ref().unwrap().name, "Test Area 2");
+    }
+
+    /// Test that a room with a portal inside is still described as a room, and the portal can
+    /// be gone through by name.
+    #[test]
+    fn travel_interpreter_room_portal_test() {
+        let path = "room_portal_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        let go_west = ret_lang::parse_input("go west").unwrap_or_else(|e| panic!("{}", e));
+        let entered = travel_interpreter(&go_west, &mut game_state, &game_config);
+        let go_trapdoor = ret_lang::parse_input("go trapdoor").unwrap_or_else(|e| panic!("{}", e));
+        let through = travel_interpreter(&go_trapdoor, &mut game_state, &game_config);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            entered.unwrap().output().lines,
+            vec!["Hero went west. This is room 2."]
+        );
+        assert_eq!(
+            through.unwrap().output().lines,
+            vec!["Hero went through the trapdoor. This is in test area 2."]
+        );
+        assert_eq!(game_state.map.as_ref().unwrap().name, "Test Area 2");
+        assert_eq!(game_state.room, Some(Coord::new(1, 0)));
+    }
+
+    /// Test that a portal that collapses can't be used to go back.
+    #[test]
+    fn travel_interpreter_consumed_portal_test() {
+        let path = "consumed_portal_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut game_config = config::GameConfig::new();
+        game_config.db_path = String::from(path);
+        let mut game_state = test_state(vec![]);
+        if let Some(crate::game::map::GridSquare::Portal(portal)) = game_state
+            .map
+            .as_mut()
+            .unwrap()
+            .get_grid_square_mut(Coord::new(2, 1))
+        {
+            portal.consume_on_use = true;
+        }
+        let mut outputs = vec![];
+        for sentence in ["go south", "go north", "go south"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            outputs.push(travel_interpreter(&command, &mut game_state, &game_config));
+        }
+        std::fs::remove_file(path).unwrap();
+        assert!(outputs[0].is_ok());
+        // The portal back from Test Area 2 isn't consumed, but the one it leads to is gone.
+        assert!(outputs[1].is_ok());
+        assert_eq!(
+            outputs[2],
+            Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE))
+        );
+        let map = game_state.map.as_ref().unwrap();
+        assert_eq!(map.name, "Test Area");
+        assert_eq!(map.get_grid_square(Coord::new(2, 1)), None);
+        assert!(game_state
+            .collapsed
+            .contains(&(String::from("Test Area"), Coord::new(2, 1))));
+    }
+
+    /// Test that moving marks rooms visited and the map hides the rest.
+    #[test]
+    fn travel_interpreter_visited_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.mark_visited();
+        let game_config = config::GameConfig::new();
+        for sentence in ["go north", "go south", "go west"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        }
+        assert!(game_state.is_visited("Test Area", Coord::new(0, 1)));
+        assert!(game_state.is_visited("Test Area", Coord::new(1, 0)));
+        assert!(!game_state.is_visited("Test Area", Coord::new(1, 2)));
+
+        let command = ret_lang::parse_input("map").unwrap_or_else(|e| panic!("{}", e));
+        let output = travel_interpreter(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(output.output().lines, vec!["? # ?", "@ # ?", "? ? ?"]);
+    }
+
+    // Test the travel_interpreter function with an invalid command.
+    #[test]
+    fn travel_interpreter_invalid_command_test() {
+        let mut game_state = state::GameState::new();
+        let command = ret_lang::parse_input("endure").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        assert_eq!(output, Err(GameError::NotAllowed(config::NOT_ABLE_MESSAGE)));
+    }
+
+    /// Test the travel_interpreter function with an exit command.
+    #[test]
+    fn travel_interpreter_exit_test() {
+        let mut game_state = state::GameState::new();
+        let command = ret_lang::parse_input("exit").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output = interpret(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output,
+            Ok(Outcome::Message(CommandOutput::from(String::from(
+                CONFIRM_EXIT_MESSAGE
+            ))))
+        );
+        assert!(!game_state.exit_confirmed);
+        let output = interpret(&command, &mut game_state, &game_config);
+        assert_eq!(
+            output,
+            Ok(Outcome::GameOver(CommandOutput::from(String::from(
+                EXIT_MESSAGE
+            ))))
+        );
+        assert!(game_state.exit_confirmed);
+    }
+
+    /// Test that another command between two exits cancels the pending exit.
+    #[test]
+    fn exit_cancelled_test() {
+        let mut game_state = state::GameState::new();
+        let exit = ret_lang::parse_input("exit").unwrap_or_else(|e| panic!("{}", e));
+        let status = ret_lang::parse_input("status").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        interpret(&exit, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        interpret(&status, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        let output =
+            interpret(&exit, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines, vec![CONFIRM_EXIT_MESSAGE]);
+        assert!(!game_state.exit_confirmed);
+    }
+
+    /// Test taking everything when the carry capacity leaves some items behind.
+    #[test]
+    fn take_all_carry_limit_test() {
+        let mut game_state =
+            test_state(vec![item("sword", 3), item("anvil", 9), item("shield", 2)]);
+        game_state.player.strength = 5;
+        let command = ret_lang::parse_input("take all").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["You take: sword, shield.", "You can't carry: anvil."]
+        );
+        assert_eq!(
+            game_state.current_room().unwrap().items,
+            vec![item("anvil", 9)]
+        );
+        assert_eq!(
+            take_all(&mut test_state(vec![])).err(),
+            Some(GameError::Refused(NOTHING_TO_TAKE_MESSAGE))
+        );
+    }
+
+    /// Test giving an item to an ally, and to someone who isn't there.
+    #[test]
+    fn give_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.inventory = vec![item("potion", 1)];
+        game_state.recruit(Ally::new(
+            String::from("old man"),
+            String::from("An old man."),
+            4,
+        ));
+        let game_config = config::GameConfig::new();
+        let command =
+            ret_lang::parse_input("give potion to knight").unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            interpret(&command, &mut game_state, &game_config).err(),
+            Some(GameError::NoSuchTarget(NO_ONE_TO_GIVE_MESSAGE))
+        );
+        assert_eq!(game_state.inventory, vec![item("potion", 1)]);
+
+        let command =
+            ret_lang::parse_input("give potion to old man").unwrap_or_else(|e| panic!("{}", e));
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["You give the potion to the old man."]
+        );
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.allies[0].inventory, vec![item("potion", 1)]);
+    }
+
+    /// Test giving an item with a name of several words, and that enemies won't take anything.
+    #[test]
+    fn give_multi_word_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.inventory = vec![item("healing potion", 1)];
+        game_state.recruit(Ally::new(
+            String::from("squire"),
+            String::from("A squire."),
+            4,
+        ));
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("give healing potion to goblin")
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            interpret(&command, &mut game_state, &game_config).err(),
+            Some(GameError::Refused(NOT_AN_ALLY_MESSAGE))
+        );
+        assert_eq!(game_state.inventory, vec![item("healing potion", 1)]);
+
+        let command = ret_lang::parse_input("give healing potion to squire")
+            .unwrap_or_else(|e| panic!("{}", e));
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["You give the healing potion to the squire."]
+        );
+        assert_eq!(
+            game_state.allies[0].inventory,
+            vec![item("healing potion", 1)]
+        );
+    }
+
+    /// Test using a healing potion when wounded and at full health.
+    #[test]
+    fn use_potion_test() {
+        let mut game_state = test_state(vec![]);
+        let potion = Item::potion(String::from("potion"), String::from("Red."), 1, 5);
+        game_state.inventory = vec![potion.clone(), item("rope", 1)];
+        assert_eq!(
+            use_item("potion", None, &mut game_state).err(),
+            Some(GameError::Refused(FULL_HEALTH_MESSAGE))
+        );
+        assert_eq!(game_state.inventory.len(), 2);
+
+        game_state.player.hp = game_state.player.max_hp - 3;
+        let output = use_item("Potion", None, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.lines, vec!["You use the potion and heal 3 HP."]);
+        assert_eq!(game_state.player.hp, game_state.player.max_hp);
+        assert_eq!(game_state.inventory, vec![item("rope", 1)]);
+
+        assert_eq!(
+            use_item("rope", None, &mut game_state).err(),
+            Some(GameError::Refused(CANT_USE_MESSAGE))
+        );
+        assert_eq!(
+            use_item("potion", None, &mut game_state).err(),
+            Some(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))
+        );
+    }
+
+    /// Test that a new hero knows the default spell, and learned spells are listed.
+    #[test]
+    fn spells_test() {
+        let mut game_state = test_state(vec![]);
+        let command = ret_lang::parse_input("spells").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec![
+                "Known spells:",
+                "light (level 0, 0 mana): Conjures a light in your hand."
+            ]
+        );
+        game_state.player.learn_spell("missile").unwrap();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines.last().unwrap(),
+            "missile (level 1, 2 mana): Deals 2d4 damage to an enemy."
+        );
+        assert_eq!(output.output().lines.len(), 3);
+    }
+
+    /// Test learning a new spell from a spellbook, and learning it again.
+    #[test]
+    fn learn_spell_test() {
+        let mut game_state = test_state(vec![]);
+        let game_config = config::GameConfig::new();
+        let command = ret_lang::parse_input("learn fireball").unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            interpret(&command, &mut game_state, &game_config).err(),
+            Some(GameError::Refused(NO_SPELLBOOK_MESSAGE))
+        );
+
+        game_state.inventory.push(item("spellbook", 1));
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines, vec!["You learn fireball."]);
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.output().lines, vec!["You already know fireball."]);
+        assert_eq!(game_state.player.spells, vec!["light", "fireball"]);
+
+        let command = ret_lang::parse_input("learn wish").unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            interpret(&command, &mut game_state, &game_config).err(),
+            Some(GameError::NoSuchTarget(player::UNKNOWN_SPELL_MESSAGE))
+        );
+    }
+
+    /// Test resting while travelling, and being refused during a fight.
+    #[test]
+    fn rest_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.player.hp = game_state.player.max_hp - 2;
+        game_state.player.mana = 0;
+        let command = ret_lang::parse_input("rest").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines,
+            vec!["You rest and recover 2 HP and 5 mana."]
+        );
+        assert_eq!(game_state.player.hp, game_state.player.max_hp);
+        assert_eq!(game_state.player.mana, 5);
+
+        game_state.mode = state::Mode::Combat;
+        assert_eq!(
+            interpret(&command, &mut game_state, &game_config).err(),
+            Some(GameError::Refused(CANT_REST_MESSAGE))
+        );
+        assert_eq!(game_state.player.mana, 5);
+    }
+
+    /// Test dropping everything the hero carries.
+    #[test]
+    fn drop_all_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.inventory = vec![item("sword", 3), item("shield", 2)];
+        let output = drop_all(&mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.lines, vec!["You drop: sword, shield."]);
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.current_room().unwrap().items.len(), 2);
+        assert_eq!(
+            drop_all(&mut game_state).err(),
+            Some(GameError::Refused(NOTHING_TO_DROP_MESSAGE))
+        );
+
+        // Without a room to drop them in, the hero keeps everything.
+        game_state.inventory = vec![item("sword", 3), item("shield", 2)];
+        game_state.room = None;
+        assert_eq!(
+            drop_all(&mut game_state).err(),
+            Some(GameError::Refused(NOWHERE_TO_DROP_MESSAGE))
+        );
+        assert_eq!(
+            drop_item("sword", None, &mut game_state).err(),
+            Some(GameError::Refused(NOWHERE_TO_DROP_MESSAGE))
+        );
+        assert_eq!(game_state.inventory.len(), 2);
+    }
+
+    /// Test taking an item that exactly fills the carry capacity.
+    #[test]
+    fn take_at_carry_limit_test() {
+        let mut game_state = test_state(vec![item("shield", 2)]);
+        game_state.player.strength = 5;
+        game_state.inventory.push(item("sword", 3));
+        let output = take_item("shield", None, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You take the shield.");
+        assert_eq!(game_state.current_weight(), 5);
+    }
+
+    /// Test taking an item that goes over the carry capacity.
+    #[test]
+    fn take_over_carry_limit_test() {
+        let mut game_state = test_state(vec![item("shield", 3)]);
+        game_state.player.strength = 5;
+        game_state.inventory.push(item("sword", 3));
+        let output = take_item("shield", None, &mut game_state);
+        assert_eq!(output, Err(GameError::Refused(CARRY_LIMIT_MESSAGE)));
+        assert_eq!(game_state.current_weight(), 3);
+        assert_eq!(game_state.current_room_mut().unwrap().items.len(), 1);
+    }
+
+    /// Test that items without weight always fit.
+    #[test]
+    fn take_weightless_item_test() {
+        let mut game_state = test_state(vec![item("feather", 0)]);
+        game_state.player.strength = 5;
+        game_state.inventory.push(item("anvil", 5));
+        assert!(take_item("feather", None, &mut game_state).is_ok());
+    }
+
+    /// Test that dropping an item frees up carry capacity.
+    #[test]
+    fn drop_frees_capacity_test() {
+        let mut game_state = test_state(vec![item("shield", 3)]);
+        game_state.player.strength = 5;
+        game_state.inventory.push(item("sword", 3));
+        assert_eq!(
+            take_item("shield", None, &mut game_state),
+            Err(GameError::Refused(CARRY_LIMIT_MESSAGE))
+        );
+        let output = drop_item("sword", None, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You drop the sword.");
+        assert!(take_item("shield", None, &mut game_state).is_ok());
+        assert_eq!(
+            game_state.current_room_mut().unwrap().items[0].name,
+            "sword"
+        );
+    }
+
+    /// Test taking identical items as one stack, then dropping part of it.
+    #[test]
+    fn take_and_drop_stack_test() {
+        let mut arrows = item("arrow", 0);
+        arrows.count = 20;
+        let mut game_state = test_state(vec![arrows, item("arrow", 0)]);
+        let game_config = config::GameConfig::new();
+        for sentence in ["take arrow", "take arrow"] {
+            let command = ret_lang::parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            interpret(&command, &mut game_state, &game_config).unwrap();
+        }
+        assert_eq!(game_state.inventory.len(), 1);
+        assert_eq!(game_state.inventory[0].count, 21);
+
+        let command = ret_lang::parse_input("drop 5 arrow").unwrap_or_else(|e| panic!("{}", e));
+        let output = interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(output.to_string(), "You drop the arrow x5.");
+        assert_eq!(game_state.inventory[0].count, 16);
+        let room = game_state.current_room().unwrap();
+        assert_eq!(room.items.len(), 1);
+        assert_eq!(room.items[0].count, 5);
+
+        let command = ret_lang::parse_input("take 2 arrow").unwrap_or_else(|e| panic!("{}", e));
+        interpret(&command, &mut game_state, &game_config).unwrap();
+        assert_eq!(game_state.inventory[0].count, 18);
+        assert_eq!(game_state.current_room().unwrap().items[0].count, 3);
+    }
+
+    /// Test that asking for more than there are takes or drops the whole stack with a note.
+    #[test]
+    fn take_and_drop_too_many_test() {
+        let mut arrows = item("arrow", 0);
+        arrows.count = 3;
+        let mut game_state = test_state(vec![arrows]);
+        let output = take_item("arrow", Some(10), &mut game_state).unwrap();
+        assert_eq!(
+            output.lines,
+            vec![
+                "There are only 3, so you take them all.",
+                "You take the arrow x3."
+            ]
+        );
+        assert!(game_state.current_room().unwrap().items.is_empty());
+
+        let output = drop_item("arrow", Some(4), &mut game_state).unwrap();
+        assert_eq!(
+            output.lines,
+            vec![
+                "You only have 3, so you drop them all.",
+                "You drop the arrow x3."
+            ]
+        );
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.current_room().unwrap().items[0].count, 3);
+    }
+
+    /// Test that the weight of a stack counts every item in it.
+    #[test]
+    fn take_stack_over_carry_limit_test() {
+        let mut rocks = item("rock", 1);
+        rocks.count = 10;
+        let mut game_state = test_state(vec![rocks]);
+        game_state.player.strength = 5;
+        assert_eq!(
+            take_item("rock", None, &mut game_state),
+            Err(GameError::Refused(CARRY_LIMIT_MESSAGE))
+        );
+        assert!(take_item("rock", Some(5), &mut game_state).is_ok());
+        assert_eq!(game_state.current_weight(), 5);
+    }
+
+    /// Test taking and dropping items that aren't there.
+    #[test]
+    fn take_and_drop_missing_item_test() {
+        let mut game_state = test_state(vec![]);
+        assert_eq!(
+            take_item("sword", None, &mut game_state),
+            Err(GameError::NoSuchTarget(NO_ITEM_MESSAGE))
+        );
+        assert_eq!(
+            drop_item("sword", None, &mut game_state),
+            Err(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))
+        );
+    }
+
+    /// Test that take, drop, and examine find an item by part of its name.
+    #[test]
+    fn take_drop_examine_partial_name_test() {
+        let mut game_state = test_state(vec![item("rusty sword", 2), item("rusty shield", 3)]);
+        assert_eq!(
+            take_item("rust", None, &mut game_state),
+            Err(GameError::Ambiguous(vec![
+                String::from("rusty sword"),
+                String::from("rusty shield")
+            ]))
+        );
+        let output = take_item("sword", None, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You take the rusty sword.");
+        let output = examine("shie", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A rusty shield.");
+        let output = drop_item("rust", None, &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You drop the rusty sword.");
+        assert_eq!(
+            examine("lantern", &mut game_state),
+            Err(GameError::NoSuchTarget(NOTHING_SPECIAL_MESSAGE))
+        );
+    }
+
+    /// Test that an enemy named exactly is examined before an item whose name starts the same.
+    #[test]
+    fn examine_enemy_before_partial_item_test() {
+        let mut game_state = test_state(vec![item("goblin ear", 1), item("pirate hat", 1)]);
+        let room = game_state.current_room_mut().unwrap();
+        room.enemies.push(Enemy::new(
+            String::from("goblin"),
+            String::from("A goblin with a crooked grin."),
+            6,
+        ));
+        room.enemies
+            .push(Enemy::new(String::from("rat"), String::from("A rat."), 2));
+        let output = examine("goblin", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A goblin with a crooked grin.");
+        let output = examine("rat", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A rat.");
+        let output = examine("gob", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A goblin ear.");
+    }
+
+    /// Test examining an item in the inventory.
+    #[test]
+    fn examine_inventory_item_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.inventory.push(item("lantern", 1));
+        let output = examine("Lantern", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A lantern.");
+    }
+
+    /// Test examining an enemy in the room.
+    #[test]
+    fn examine_room_enemy_test() {
+        let mut game_state = test_state(vec![]);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin with a crooked grin."),
+                6,
+            ));
+        let output = examine("goblin", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "A goblin with a crooked grin.");
+        assert_eq!(
+            examine("dragon", &mut game_state),
+            Err(GameError::NoSuchTarget(NOTHING_SPECIAL_MESSAGE))
+        );
+    }
+
+    /// Test that an equipped sword's damage die is used when attacking.
+    #[test]
+    fn equip_sword_damage_test() {
+        let mut game_state = test_state(vec![]);
+        let sword = Item::weapon(String::from("sword"), String::from("A sword."), 2, 8);
+        game_state.inventory.push(sword);
+        game_state
+            .current_room_mut()
+            .unwrap()
+            .enemies
+            .push(Enemy::new(
+                String::from("goblin"),
+                String::from("A goblin."),
+                10,
+            ));
+        let output = equip("sword", &mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You wield the sword.");
+        assert!(game_state.inventory.is_empty());
+        assert_eq!(game_state.player.damage_die(), 8);
+
+        // The damage roll is a d8, so a roll of 8 only comes from the sword.
+        game_state.roller = Box::new(FixedRoller::new(vec![5, 5, 8]));
+        let command = ret_lang::parse_input("attack goblin").unwrap_or_else(|e| panic!("{}", e));
+        let game_config = config::GameConfig::new();
+        let output =
+            interpret(&command, &mut game_state, &game_config).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(
+            output.output().lines.last().unwrap(),
+            "You hit the goblin and deal 8 damage."
+        );
+    }
+
+    /// Test equipping things that can't be wielded, and unequipping.
+    #[test]
+    fn equip_errors_and_unequip_test() {
+        let mut game_state = test_state(vec![]);
+        assert_eq!(
+            equip("sword", &mut game_state),
+            Err(GameError::NoSuchTarget(NOT_CARRIED_MESSAGE))
+        );
+        game_state.inventory.push(item("rope", 1));
+        assert_eq!(
+            equip("rope", &mut game_state),
+            Err(GameError::Refused(NOT_A_WEAPON_MESSAGE))
+        );
+        assert_eq!(
+            unequip(&mut game_state),
+            Err(GameError::Refused(NOT_WIELDING_MESSAGE))
+        );
+        game_state.player.weapon = Some(Item::weapon(
+            String::from("axe"),
+            String::from("An axe."),
+            3,
+            10,
+        ));
+        let output = unequip(&mut game_state).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(output.to_string(), "You put away the axe.");
+        assert_eq!(game_state.inventory.len(), 2);
+        assert!(game_state.player.weapon.is_none());
+    }
+
+    /// Test that the status sheet shows hit points and every stat.
+    #[test]
+    fn status_test() {
+        let mut game_state = test_state(vec![]);
+        game_state.player.hp = 12;
+        let output = status(&game_state).to_string();
+        assert!(output.contains("HP: 12/20"));
+        assert!(output.contains("Level: 1 (XP: 0/8)"));
+        assert!(output.contains("Mana: 10/10"));
+        for label in [
+            "Strength:",
+            "Dexterity:",
+            "Constitution:",
+            "Intelligence:",
+            "Wisdom:",
+            "Charisma:",
+        ] {
+            assert!(output.contains(label), "{} missing from status.", label);
+        }
+        assert!(output.contains("Weapon: none (d4)"));
+        assert!(output.contains("Mode: Travel"));
+    }
+
+    /// Test that command output keeps each line separate.
+    #[test]
+    fn command_output_lines_test() {
+        let mut output = CommandOutput::from(String::from("You swing.\nThe goblin falls."));
+        output.push(String::from("The room is quiet."));
+        assert_eq!(output.lines.len(), 3);
+        assert_eq!(
+            output.to_string(),
+            "You swing.\nThe goblin falls.\nThe room is quiet."
+        );
+    }
+
+    // Test the travel_interpreter function with a custom not able message.
+    #[test]
+    fn travel_interpreter_custom_message_test() {
         let mut game_state = state::GameState::new();
         let command = ret_lang::parse_input("endure").unwrap_or_else(|e| panic!("{}", e));
-        let output = travel_interpreter(&command, &mut game_state);
-        assert_eq!(output, Err("Not able to do that action right now."));
+        let mut game_config = config::GameConfig::new();
+        game_config.not_able_message = String::from("You can't do that.");
+        let output = travel_interpreter(&command, &mut game_state, &game_config);
+        let error = output.err().unwrap();
+        assert_eq!(error, GameError::NotAllowed(config::NOT_ABLE_MESSAGE));
+        assert_eq!(game_config.error_message(&error), "You can't do that.");
     }
 }
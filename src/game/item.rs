@@ -0,0 +1,574 @@
+//! # Item
+//! Module that represents the things a hero can find and carry.
+use crate::game::error::GameError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The ammo a new ranged weapon comes with.
+pub const DEFAULT_AMMO: u32 = 3;
+
+/// An enum that represents how a weapon is used in a fight.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WeaponType {
+    /// A weapon used up close, such as a sword. Fists are melee too.
+    #[default]
+    Melee,
+    /// A weapon used from afar, such as a bow.
+    Ranged,
+}
+
+/// An enum that represents what happens when an item is used.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Effect {
+    /// Heals the hero by the given hit points, up to their max.
+    Heal(i32),
+}
+
+/// A struct that represents an item in the game world.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Item {
+    /// The name of the item.
+    pub name: String,
+    /// The description of the item.
+    pub description: String,
+    /// How heavy the item is to carry.
+    #[serde(default)]
+    pub weight: u32,
+    /// The number of sides on the damage die when the item is wielded. None if it isn't a weapon.
+    #[serde(default)]
+    pub damage: Option<u32>,
+    /// How the item is used when it is wielded as a weapon.
+    #[serde(default)]
+    pub weapon_type: WeaponType,
+    /// The ammo left to shoot when the item is a ranged weapon.
+    #[serde(default)]
+    pub ammo: u32,
+    /// What happens when the item is used. None if it can't be used.
+    #[serde(default)]
+    pub effect: Option<Effect>,
+    /// Whether the item is used up when it is used.
+    #[serde(default)]
+    pub consumable: bool,
+    /// Whether the item can give off light, so the hero can see in dark rooms.
+    #[serde(default)]
+    pub light: bool,
+    /// Whether the light is lit.
+    #[serde(default)]
+    pub lit: bool,
+    /// The turns the light has left to burn. None if it never burns out.
+    #[serde(default)]
+    pub turns_remaining: Option<u32>,
+    /// How many of the item are in the stack. Items that can't stack always have a count of 1.
+    #[serde(default = "default_count")]
+    pub count: u32,
+}
+
+/// The count of an item saved before items could stack.
+fn default_count() -> u32 {
+    1
+}
+
+impl Item {
+    /// Constructor for the Item struct.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the item.
+    /// * `description` - A string that is the description of the item.
+    /// * `weight` - A u32 that is how heavy the item is.
+    ///
+    /// # Returns
+    /// * `Item` - A new Item.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let sword = item::Item::new(String::from("sword"), String::from("A short, sharp sword."), 2);
+    /// assert_eq!(sword.name, "sword");
+    /// assert_eq!(sword.weight, 2);
+    /// ```
+    pub fn new(name: String, description: String, weight: u32) -> Item {
+        Item {
+            name,
+            description,
+            weight,
+            damage: None,
+            weapon_type: WeaponType::Melee,
+            ammo: 0,
+            effect: None,
+            consumable: false,
+            light: false,
+            lit: false,
+            turns_remaining: None,
+            count: 1,
+        }
+    }
+
+    /// Constructor for an Item that can be wielded as a weapon.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the weapon.
+    /// * `description` - A string that is the description of the weapon.
+    /// * `weight` - A u32 that is how heavy the weapon is.
+    /// * `damage` - A u32 that is the number of sides on the weapon's damage die.
+    ///
+    /// # Returns
+    /// * `Item` - A new weapon Item.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let sword = item::Item::weapon(String::from("sword"), String::from("Sharp."), 2, 8);
+    /// assert_eq!(sword.damage, Some(8));
+    /// ```
+    pub fn weapon(name: String, description: String, weight: u32, damage: u32) -> Item {
+        Item {
+            damage: Some(damage),
+            ..Item::new(name, description, weight)
+        }
+    }
+
+    /// Constructor for an Item that can be wielded as a ranged weapon. It comes with
+    /// `DEFAULT_AMMO` ammo.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the weapon.
+    /// * `description` - A string that is the description of the weapon.
+    /// * `weight` - A u32 that is how heavy the weapon is.
+    /// * `damage` - A u32 that is the number of sides on the weapon's damage die.
+    ///
+    /// # Returns
+    /// * `Item` - A new ranged weapon Item.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let bow = item::Item::ranged_weapon(String::from("bow"), String::from("Yew."), 1, 6);
+    /// assert_eq!(bow.weapon_type, item::WeaponType::Ranged);
+    /// assert_eq!(bow.ammo, item::DEFAULT_AMMO);
+    /// ```
+    pub fn ranged_weapon(name: String, description: String, weight: u32, damage: u32) -> Item {
+        Item {
+            weapon_type: WeaponType::Ranged,
+            ammo: DEFAULT_AMMO,
+            ..Item::weapon(name, description, weight, damage)
+        }
+    }
+
+    /// Constructor for a potion that heals the hero and is used up when drunk.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the potion.
+    /// * `description` - A string that is the description of the potion.
+    /// * `weight` - A u32 that is how heavy the potion is.
+    /// * `heal` - An i32 that is the hit points the potion heals.
+    ///
+    /// # Returns
+    /// * `Item` - A new consumable Item that heals.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let potion = item::Item::potion(String::from("potion"), String::from("Red."), 1, 5);
+    /// assert_eq!(potion.effect, Some(item::Effect::Heal(5)));
+    /// assert!(potion.consumable);
+    /// ```
+    pub fn potion(name: String, description: String, weight: u32, heal: i32) -> Item {
+        Item {
+            effect: Some(Effect::Heal(heal)),
+            consumable: true,
+            ..Item::new(name, description, weight)
+        }
+    }
+
+    /// Constructor for an unlit torch that lights up dark rooms until it burns out.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the torch.
+    /// * `description` - A string that is the description of the torch.
+    /// * `weight` - A u32 that is how heavy the torch is.
+    /// * `turns` - A u32 that is how many turns the torch burns for.
+    ///
+    /// # Returns
+    /// * `Item` - A new Item that can give off light.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let mut torch = item::Item::torch(String::from("torch"), String::from("Pitch."), 1, 10);
+    /// assert!(!torch.is_lit());
+    /// torch.lit = true;
+    /// assert!(torch.is_lit());
+    /// ```
+    pub fn torch(name: String, description: String, weight: u32, turns: u32) -> Item {
+        Item {
+            light: true,
+            turns_remaining: Some(turns),
+            ..Item::new(name, description, weight)
+        }
+    }
+
+    /// Checks if the item can share a stack with others like it. Weapons and lights wear out
+    /// one at a time, so each one is kept apart.
+    ///
+    /// # Returns
+    /// * `bool` - True if the item can stack.
+    pub fn stackable(&self) -> bool {
+        self.damage.is_none() && !self.light
+    }
+
+    /// Checks if two items are the same kind of thing, no matter how many of each there are.
+    ///
+    /// # Arguments
+    /// * `other` - A reference to the item to compare with.
+    ///
+    /// # Returns
+    /// * `bool` - True if every field but the count matches.
+    pub fn same_kind(&self, other: &Item) -> bool {
+        Item {
+            count: other.count,
+            ..self.clone()
+        } == *other
+    }
+
+    /// The weight of the whole stack.
+    ///
+    /// # Returns
+    /// * `u32` - The weight of one item times the count.
+    pub fn total_weight(&self) -> u32 {
+        self.weight * self.count
+    }
+
+    /// Checks if the item is giving off light right now.
+    ///
+    /// # Returns
+    /// * `bool` - True if the item is a lit light that hasn't burned out.
+    pub fn is_lit(&self) -> bool {
+        self.light && self.lit && !self.burned_out()
+    }
+
+    /// Checks if the item is a light that has no turns left to burn.
+    ///
+    /// # Returns
+    /// * `bool` - True if the light has burned out.
+    pub fn burned_out(&self) -> bool {
+        self.turns_remaining == Some(0)
+    }
+
+    /// Burns a lit light for a turn, putting it out when it runs out of turns.
+    ///
+    /// # Returns
+    /// * `bool` - True if the light burned out this turn.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    ///
+    /// let mut torch = item::Item::torch(String::from("torch"), String::from("Pitch."), 1, 2);
+    /// torch.lit = true;
+    /// assert!(!torch.burn());
+    /// assert!(torch.burn());
+    /// assert!(torch.burned_out());
+    /// assert!(!torch.is_lit());
+    /// ```
+    pub fn burn(&mut self) -> bool {
+        if !self.is_lit() {
+            return false;
+        }
+        match self.turns_remaining.as_mut() {
+            Some(turns) => {
+                *turns -= 1;
+                if *turns == 0 {
+                    self.lit = false;
+                    return true;
+                }
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+/// A function that puts an item into a list of items, adding it to a stack of the same kind
+/// when it can stack.
+///
+/// # Arguments
+/// * `items` - A mutable reference to the items to add to.
+/// * `item` - The item to add.
+///
+/// # Examples
+/// ```
+/// use retribution::game::item;
+///
+/// let arrow = item::Item::new(String::from("arrow"), String::from("Fletched."), 1);
+/// let mut items = vec![];
+/// item::add(&mut items, arrow.clone());
+/// item::add(&mut items, arrow);
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(items[0].count, 2);
+/// ```
+pub fn add(items: &mut Vec<Item>, item: Item) {
+    match items
+        .iter_mut()
+        .find(|i| item.stackable() && i.same_kind(&item))
+    {
+        Some(stack) => stack.count += item.count,
+        None => items.push(item),
+    }
+}
+
+/// A function that takes some of a stack out of a list of items. The stack is removed once
+/// none are left.
+///
+/// # Arguments
+/// * `items` - A mutable reference to the items to take from.
+/// * `index` - The position of the stack.
+/// * `count` - How many to take. None, or more than are there, takes the whole stack.
+///
+/// # Returns
+/// * `Item` - The items taken, as a stack of their own.
+///
+/// # Examples
+/// ```
+/// use retribution::game::item;
+///
+/// let mut arrows = item::Item::new(String::from("arrow"), String::from("Fletched."), 1);
+/// arrows.count = 20;
+/// let mut items = vec![arrows];
+/// assert_eq!(item::remove(&mut items, 0, Some(5)).count, 5);
+/// assert_eq!(items[0].count, 15);
+/// assert_eq!(item::remove(&mut items, 0, None).count, 15);
+/// assert!(items.is_empty());
+/// ```
+pub fn remove(items: &mut Vec<Item>, index: usize, count: Option<u32>) -> Item {
+    match count {
+        Some(count) if count < items[index].count => {
+            items[index].count -= count;
+            Item {
+                count,
+                ..items[index].clone()
+            }
+        }
+        _ => items.remove(index),
+    }
+}
+
+/// A function that groups items of the same kind into stacks, so twenty arrows can be listed as
+/// one line. Items only group when every field but the count matches, so a half-burned torch
+/// stays apart from a fresh one. The stacks are sorted by name, then by weight.
+///
+/// # Arguments
+/// * `items` - A slice of the items to stack.
+///
+/// # Returns
+/// * `Vec<(&Item, usize)>` - Each kind of item and how many of it there are.
+///
+/// # Examples
+/// ```
+/// use retribution::game::item;
+///
+/// let arrow = item::Item::new(String::from("arrow"), String::from("Fletched."), 1);
+/// let bow = item::Item::new(String::from("bow"), String::from("Yew."), 2);
+/// let items = vec![bow.clone(), arrow.clone(), arrow.clone()];
+/// assert_eq!(item::stack(&items), vec![(&arrow, 2), (&bow, 1)]);
+/// ```
+pub fn stack(items: &[Item]) -> Vec<(&Item, usize)> {
+    let mut stacks: Vec<(&Item, usize)> = vec![];
+    for item in items {
+        match stacks.iter_mut().find(|(i, _)| i.same_kind(item)) {
+            Some((_, count)) => *count += item.count as usize,
+            None => stacks.push((item, item.count as usize)),
+        }
+    }
+    stacks.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name).then(a.weight.cmp(&b.weight)));
+    stacks
+}
+
+/// A function that finds the item a hero means by a name, which can be the start or any part of
+/// the item's name when that is enough to tell it apart, so `rust` finds the "rusty sword". An
+/// exact name always wins, then a name that starts with it, then a name that holds it anywhere.
+/// Case is ignored.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name the hero gave.
+/// * `candidates` - A slice of the items the hero could mean.
+///
+/// # Returns
+/// * `Result<Option<usize>, GameError>` - The position of the item, None if no item matches, or
+///   an error listing the items when the name matches more than one kind.
+///
+/// # Examples
+/// ```
+/// use retribution::game::item;
+///
+/// let sword = item::Item::new(String::from("rusty sword"), String::from("Old."), 2);
+/// let rope = item::Item::new(String::from("rope"), String::from("Frayed."), 1);
+/// let items = vec![sword, rope];
+/// assert_eq!(item::resolve_item("rust", &items), Ok(Some(0)));
+/// assert_eq!(item::resolve_item("ROPE", &items), Ok(Some(1)));
+/// assert_eq!(item::resolve_item("lantern", &items), Ok(None));
+/// ```
+pub fn resolve_item(name: &str, candidates: &[Item]) -> Result<Option<usize>, GameError> {
+    let name = name.to_lowercase();
+    let tests: [&dyn Fn(&str) -> bool; 3] =
+        [&|n| n == name, &|n| n.starts_with(name.as_str()), &|n| {
+            n.contains(name.as_str())
+        }];
+    for test in tests {
+        let found: Vec<usize> = (0..candidates.len())
+            .filter(|&i| test(candidates[i].name.to_lowercase().as_str()))
+            .collect();
+        let mut names: Vec<String> = vec![];
+        for &i in &found {
+            if !names.contains(&candidates[i].name) {
+                names.push(candidates[i].name.clone());
+            }
+        }
+        match names.len() {
+            0 => continue,
+            1 => return Ok(found.first().copied()),
+            _ => return Err(GameError::Ambiguous(names)),
+        }
+    }
+    Ok(None)
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (weight {})", self.name, self.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that a unique start of a name finds the item, and an exact name beats a longer one.
+    #[test]
+    fn resolve_item_prefix_test() {
+        let items = vec![
+            Item::new(String::from("torch"), String::from("Pitch."), 1),
+            Item::new(String::from("rusty sword"), String::from("Old."), 2),
+            Item::new(String::from("torch holder"), String::from("Iron."), 1),
+        ];
+        assert_eq!(resolve_item("rust", &items), Ok(Some(1)));
+        assert_eq!(resolve_item("sword", &items), Ok(Some(1)));
+        assert_eq!(resolve_item("Torch", &items), Ok(Some(0)));
+        assert_eq!(resolve_item("hold", &items), Ok(Some(2)));
+    }
+
+    /// Test that a name matching more than one kind of item lists them.
+    #[test]
+    fn resolve_item_ambiguous_test() {
+        let sword = Item::new(String::from("rusty sword"), String::from("Old."), 2);
+        let items = vec![
+            sword.clone(),
+            Item::new(String::from("rusty shield"), String::from("Dented."), 3),
+            sword,
+        ];
+        let error = resolve_item("rusty", &items).unwrap_err();
+        assert_eq!(
+            error,
+            GameError::Ambiguous(vec![
+                String::from("rusty sword"),
+                String::from("rusty shield")
+            ])
+        );
+        assert_eq!(
+            error.to_string(),
+            "Which do you mean: rusty sword, rusty shield?"
+        );
+        // Two of the same item aren't a choice.
+        assert_eq!(resolve_item("sword", &items), Ok(Some(0)));
+    }
+
+    /// Test that a name matching nothing finds nothing.
+    #[test]
+    fn resolve_item_no_match_test() {
+        let items = vec![Item::new(String::from("rope"), String::from("Frayed."), 1)];
+        assert_eq!(resolve_item("lantern", &items), Ok(None));
+        assert_eq!(resolve_item("rope", &[]), Ok(None));
+    }
+
+    /// Test the item constructor.
+    #[test]
+    fn item_new_test() {
+        let item = Item::new(String::from("rope"), String::from("Fifty feet of rope."), 1);
+        assert_eq!(item.name, "rope");
+        assert_eq!(item.description, "Fifty feet of rope.");
+        assert_eq!(item.weight, 1);
+    }
+
+    /// Test that only identical items stack, and that stacks are sorted by name then weight.
+    #[test]
+    fn stack_test() {
+        let arrow = Item::new(String::from("arrow"), String::from("Fletched."), 1);
+        let rock = Item::new(String::from("rock"), String::from("Heavy."), 3);
+        let pebble = Item::new(String::from("rock"), String::from("Light."), 1);
+        let mut lit = Item::torch(String::from("torch"), String::from("Pitch."), 1, 5);
+        lit.lit = true;
+        let unlit = Item::torch(String::from("torch"), String::from("Pitch."), 1, 5);
+        let items = vec![
+            lit.clone(),
+            rock.clone(),
+            arrow.clone(),
+            unlit.clone(),
+            pebble.clone(),
+            arrow.clone(),
+            arrow.clone(),
+        ];
+        let stacks = stack(&items);
+        assert_eq!(
+            stacks,
+            vec![
+                (&arrow, 3),
+                (&pebble, 1),
+                (&rock, 1),
+                (&lit, 1),
+                (&unlit, 1)
+            ]
+        );
+        assert!(stack(&[]).is_empty());
+    }
+
+    /// Test that stackable items combine and split by count, and unique items stay apart.
+    #[test]
+    fn add_remove_test() {
+        let arrow = Item::new(String::from("arrow"), String::from("Fletched."), 1);
+        let sword = Item::weapon(String::from("sword"), String::from("Sharp."), 2, 8);
+        let mut items = vec![];
+        for _ in 0..3 {
+            add(&mut items, arrow.clone());
+            add(&mut items, sword.clone());
+        }
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].count, 3);
+        assert_eq!(items[0].total_weight(), 3);
+        assert!(items[1..].iter().all(|i| i.count == 1));
+
+        let taken = remove(&mut items, 0, Some(2));
+        assert_eq!(taken.count, 2);
+        assert_eq!(items[0].count, 1);
+        assert_eq!(remove(&mut items, 0, Some(9)).count, 1);
+        assert_eq!(items.len(), 3);
+    }
+
+    /// Test that items saved before stacking load with a count of 1.
+    #[test]
+    fn default_count_test() {
+        let json = r#"{"name":"rope","description":"Fifty feet of rope."}"#;
+        let item: Item = serde_json::from_str(json).unwrap();
+        assert_eq!(item.count, 1);
+    }
+
+    /// Test the item display formatting.
+    #[test]
+    fn item_display_test() {
+        let item = Item::new(String::from("rope"), String::from("Fifty feet of rope."), 1);
+        assert_eq!(item.to_string(), "rope (weight 1)");
+    }
+}
@@ -1,8 +1,182 @@
 //! # Map
 //! Module that represents a location in the game world.
+use crate::db;
+use crate::game::enemy::Enemy;
+use crate::game::item::Item;
+use crate::ret_lang::Direction;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// An enum that represents the errors that can happen when working with maps.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapError {
+    /// A database operation failed.
+    Database(&'static str),
+    /// More than one room in the map has this name.
+    DuplicateRoom(String),
+    /// A map must have at least one row and one column.
+    InvalidDimensions(usize, usize),
+    /// A portal in the named map doesn't lead to a room.
+    BrokenPortal(String),
+    /// A map by this name is already in the database.
+    AlreadyExists(String),
+    /// The database stayed locked or couldn't be opened, even after retrying.
+    DbUnavailable,
+    /// The database has no maps table, as the migrations haven't been run on it.
+    NotMigrated,
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Database(e) => write!(f, "{}", e),
+            MapError::DuplicateRoom(name) => write!(f, "Duplicate room name: {}.", name),
+            MapError::InvalidDimensions(rows, cols) => {
+                write!(f, "Invalid map dimensions: {}x{}.", rows, cols)
+            }
+            MapError::BrokenPortal(name) => write!(f, "Broken portal in map: {}.", name),
+            MapError::AlreadyExists(name) => write!(f, "A map named {} already exists.", name),
+            MapError::DbUnavailable => write!(f, "The database is unavailable."),
+            MapError::NotMigrated => write!(
+                f,
+                "The database has no maps. Run game::init to set it up before loading a map."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// An enum that represents a problem a map author should fix before shipping a map.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapIssue {
+    /// The map doesn't have a single room.
+    NoRooms,
+    /// More than one room in the map has this name.
+    DuplicateRoom(String),
+    /// The portal at these coordinates doesn't lead to a room.
+    DanglingPortal(Coord),
+    /// The named room at these coordinates can't be walked to from the start.
+    Unreachable(String, Coord),
+}
+
+impl fmt::Display for MapIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapIssue::NoRooms => write!(f, "The map has no rooms."),
+            MapIssue::DuplicateRoom(name) => write!(f, "More than one room is named {}.", name),
+            MapIssue::DanglingPortal(c) => {
+                write!(f, "The portal at ({}, {}) leads nowhere.", c.row, c.col)
+            }
+            MapIssue::Unreachable(name, c) => {
+                write!(f, "{} at ({}, {}) can't be reached.", name, c.row, c.col)
+            }
+        }
+    }
+}
+
+/// A struct that represents the coordinates of a square on a map grid.
+/// Coordinates are stored as a (row, col) pair so saved maps and game states keep their shape.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(from = "(usize, usize)", into = "(usize, usize)")]
+pub struct Coord {
+    /// The row of the square, counting down from the top of the map.
+    pub row: usize,
+    /// The column of the square, counting right from the left of the map.
+    pub col: usize,
+}
+
+impl Coord {
+    /// Constructor for the Coord struct.
+    ///
+    /// # Arguments
+    /// * `row` - A usize that is the row of the square.
+    /// * `col` - A usize that is the column of the square.
+    ///
+    /// # Returns
+    /// * `Coord` - A new Coord.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map;
+    ///
+    /// let coord = map::Coord::new(1, 2);
+    /// assert_eq!(coord.row, 1);
+    /// assert_eq!(coord.col, 2);
+    /// ```
+    pub fn new(row: usize, col: usize) -> Coord {
+        Coord { row, col }
+    }
+
+    /// The square above this one, or None when this square is in the top row.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    ///
+    /// assert_eq!(Coord::new(1, 1).north(), Some(Coord::new(0, 1)));
+    /// assert_eq!(Coord::new(0, 1).north(), None);
+    /// ```
+    pub fn north(&self) -> Option<Coord> {
+        Some(Coord::new(self.row.checked_sub(1)?, self.col))
+    }
+
+    /// The square below this one, or None if it can't be represented.
+    pub fn south(&self) -> Option<Coord> {
+        Some(Coord::new(self.row.checked_add(1)?, self.col))
+    }
+
+    /// The square right of this one, or None if it can't be represented.
+    pub fn east(&self) -> Option<Coord> {
+        Some(Coord::new(self.row, self.col.checked_add(1)?))
+    }
+
+    /// The square left of this one, or None when this square is in the first column.
+    pub fn west(&self) -> Option<Coord> {
+        Some(Coord::new(self.row, self.col.checked_sub(1)?))
+    }
+
+    /// The square next to this one in a direction, or None if it is off the edge of the grid.
+    ///
+    /// # Arguments
+    /// * `direction` - The direction of the square.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::ret_lang::Direction;
+    ///
+    /// assert_eq!(Coord::new(1, 1).toward(Direction::NorthEast), Some(Coord::new(0, 2)));
+    /// assert_eq!(Coord::new(1, 0).toward(Direction::SouthWest), None);
+    /// ```
+    pub fn toward(&self, direction: Direction) -> Option<Coord> {
+        match direction {
+            Direction::North => self.north(),
+            Direction::South => self.south(),
+            Direction::East => self.east(),
+            Direction::West => self.west(),
+            Direction::NorthEast => self.north()?.east(),
+            Direction::NorthWest => self.north()?.west(),
+            Direction::SouthEast => self.south()?.east(),
+            Direction::SouthWest => self.south()?.west(),
+        }
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((row, col): (usize, usize)) -> Self {
+        Coord::new(row, col)
+    }
+}
+
+impl From<Coord> for (usize, usize) {
+    fn from(coord: Coord) -> Self {
+        (coord.row, coord.col)
+    }
+}
 
 /// A struct that represents a map in the game world.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -11,6 +185,9 @@ pub struct Map {
     pub name: String,
     /// A grid of rooms and portals in the game world.
     pub grid: Vec<Vec<Option<GridSquare>>>,
+    /// Whether the hero can move diagonally between rooms, as well as along the compass points.
+    #[serde(default)]
+    pub allow_diagonal: bool,
 }
 
 impl Map {
@@ -18,20 +195,24 @@ impl Map {
     ///
     /// # Arguments
     /// * `name` - A string that is the name of the map.
-    /// * `x` - An i32 that is the number of rooms in the x direction.
-    /// * `y` - An i32 that is the number of rooms in the y direction.
+    /// * `rows` - A usize that is the number of rows of rooms.
+    /// * `cols` - A usize that is the number of columns of rooms.
     ///
     /// # Returns
-    /// * `Map` - A new Map.
+    /// * `Result<Map, MapError>` - A new Map, or an error if either dimension is zero.
     ///
     /// # Examples
     /// ```
     /// use retribution::game::map;
     ///
-    /// let map = map::Map::new(String::from("Test Area"), 3, 3);
+    /// let map = map::Map::new(String::from("Test Area"), 3, 3).unwrap();
     /// assert_eq!(map.name, "Test Area");
+    /// assert!(map::Map::new(String::from("Nowhere"), 0, 3).is_err());
     /// ```
-    pub fn new(name: String, rows: i32, cols: i32) -> Map {
+    pub fn new(name: String, rows: usize, cols: usize) -> Result<Map, MapError> {
+        if rows == 0 || cols == 0 {
+            return Err(MapError::InvalidDimensions(rows, cols));
+        }
         let mut grid = vec![];
         // Create a grid of rooms.
         for _ in 0..rows {
@@ -41,14 +222,17 @@ impl Map {
             }
             grid.push(row);
         }
-        Map { name, grid }
+        Ok(Map {
+            name,
+            grid,
+            allow_diagonal: false,
+        })
     }
 
     /// A safe way to get a room from the map.
     ///
     /// # Arguments
-    /// * `row` - An i32 that is the row coordinate of the room.
-    /// * `col` - An i32 that is the col coordinate of the room.
+    /// * `coord` - A Coord that is the coordinates of the room.
     ///
     /// # Returns
     /// * `Option<Room>` - An option that is the room at the given coordinates, or None.
@@ -63,39 +247,130 @@ impl Map {
     ///         String::from("This is a test room.")
     ///     )
     /// );
-    /// let mut map = map::Map::new(String::from("Test Area"), 3, 3);
-    /// map.set_grid_square(1, 1, room);
-    /// let result = map.get_grid_square(1, 1);
+    /// let mut map = map::Map::new(String::from("Test Area"), 3, 3).unwrap();
+    /// map.set_grid_square(map::Coord::new(1, 1), room);
+    /// let result = map.get_grid_square(map::Coord::new(1, 1));
     /// assert!(result.is_some());
-    /// let result = map.get_grid_square(0, 0);
-    /// assert!(result.is_none());
-    /// let result = map.get_grid_square(-1, -1);
+    /// let result = map.get_grid_square(map::Coord::new(0, 0));
     /// assert!(result.is_none());
-    /// let result = map.get_grid_square(3, 3);
+    /// let result = map.get_grid_square(map::Coord::new(3, 3));
     /// assert!(result.is_none());
     /// ```
-    pub fn get_grid_square(&self, row: i32, col: i32) -> Option<&GridSquare> {
-        if col < 0 || row < 0 {
-            return None;
-        }
-        // We can safely assume these are positive numbers based on the check above.
-        let col = col as usize;
-        let row = row as usize;
-        if self.grid.len() <= row || self.grid[0].len() <= col {
-            return None;
-        }
-        let grid_square = &self.grid[row][col];
-        match grid_square {
-            Some(r) => Some(&r),
-            None => None,
+    pub fn get_grid_square(&self, coord: Coord) -> Option<&GridSquare> {
+        self.grid.get(coord.row)?.get(coord.col)?.as_ref()
+    }
+
+    /// Checks if coordinates fall inside the grid of the map.
+    ///
+    /// # Arguments
+    /// * `coord` - A Coord that is the coordinates to check.
+    ///
+    /// # Returns
+    /// * `bool` - True if the coordinates are inside the grid.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map;
+    ///
+    /// let map = map::Map::new(String::from("Test Area"), 3, 2).unwrap();
+    /// assert!(map.in_bounds(map::Coord::new(2, 1)));
+    /// assert!(!map.in_bounds(map::Coord::new(2, 2)));
+    /// ```
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        self.grid
+            .get(coord.row)
+            .is_some_and(|r| coord.col < r.len())
+    }
+
+    /// Finds the first room of the map, reading each row from left to right.
+    ///
+    /// # Returns
+    /// * `Option<Coord>` - The coordinates of the first room, or None if the map has no rooms.
+    pub fn first_room(&self) -> Option<Coord> {
+        self.rooms().next().map(|(coord, _)| coord)
+    }
+
+    /// Walks every filled square of the map, reading each row from left to right and skipping
+    /// the empty ones.
+    ///
+    /// # Returns
+    /// * `impl Iterator<Item = (Coord, &GridSquare)>` - The coordinates and contents of each square.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::migration;
+    ///
+    /// let map = migration::map::test_area();
+    /// let (coord, _) = map.iter_grid().next().unwrap();
+    /// assert_eq!(coord, Coord::new(0, 1));
+    /// ```
+    pub fn iter_grid(&self) -> impl Iterator<Item = (Coord, &GridSquare)> {
+        self.grid.iter().enumerate().flat_map(|(row, squares)| {
+            squares
+                .iter()
+                .enumerate()
+                .filter_map(move |(col, s)| s.as_ref().map(|s| (Coord::new(row, col), s)))
+        })
+    }
+
+    /// Walks every room of the map, reading each row from left to right.
+    ///
+    /// # Returns
+    /// * `impl Iterator<Item = (Coord, &Room)>` - The coordinates and room of each room square.
+    pub fn rooms(&self) -> impl Iterator<Item = (Coord, &Room)> {
+        self.iter_grid().filter_map(|(coord, s)| match s {
+            GridSquare::Room(r) => Some((coord, r)),
+            _ => None,
+        })
+    }
+
+    /// Walks every portal of the map, reading each row from left to right. Portals inside a
+    /// room are given the coordinates of their room.
+    ///
+    /// # Returns
+    /// * `impl Iterator<Item = (Coord, &Portal)>` - The coordinates and portal of each portal
+    ///   square or room portal.
+    pub fn portals(&self) -> impl Iterator<Item = (Coord, &Portal)> {
+        self.iter_grid().flat_map(|(coord, s)| {
+            let portals: Vec<&Portal> = match s {
+                GridSquare::Room(r) => r.portals.iter().collect(),
+                GridSquare::Portal(p) => vec![p],
+            };
+            portals.into_iter().map(move |p| (coord, p))
+        })
+    }
+
+    /// Empties a square of the map, such as when a portal collapses.
+    ///
+    /// # Arguments
+    /// * `coord` - A Coord that is the coordinates of the square to empty.
+    pub fn clear_grid_square(&mut self, coord: Coord) {
+        if let Some(square) = self
+            .grid
+            .get_mut(coord.row)
+            .and_then(|r| r.get_mut(coord.col))
+        {
+            *square = None;
         }
     }
 
+    /// A safe way to get a mutable room from the map.
+    ///
+    /// # Arguments
+    /// * `coord` - A Coord that is the coordinates of the room.
+    ///
+    /// # Returns
+    /// * `Option<&mut GridSquare>` - The grid square at the given coordinates, or None.
+    pub fn get_grid_square_mut(&mut self, coord: Coord) -> Option<&mut GridSquare> {
+        self.grid.get_mut(coord.row)?.get_mut(coord.col)?.as_mut()
+    }
+
     /// A safe way to set a room in the map.
     ///
     /// # Arguments
-    /// * `row` - An usize that is the row coordinate of the room.
-    /// * `col` - An usize that is the col coordinate of the room.
+    /// * `coord` - A Coord that is the coordinates of the room.
+    /// * `grid_square` - The room or portal to place at the coordinates.
     ///
     /// # Returns
     /// * `Result<(), &str>` - A result that is Ok, or an error message.
@@ -105,21 +380,351 @@ impl Map {
     /// use retribution::game::map;
     ///
     /// let room = map::GridSquare::Room(map::Room::new(String::from("Test Room"), String::from("This is a test room.")));
-    /// let mut map = map::Map::new(String::from("Test Area"), 3, 3);
-    /// map.set_grid_square(1, 1, room);
-    /// let result = map.get_grid_square(1, 1);
+    /// let mut map = map::Map::new(String::from("Test Area"), 3, 3).unwrap();
+    /// map.set_grid_square(map::Coord::new(1, 1), room.clone()).unwrap();
+    /// let result = map.get_grid_square(map::Coord::new(1, 1));
     /// assert!(result.is_some());
+    /// assert!(map.set_grid_square(map::Coord::new(3, 0), room).is_err());
+    /// ```
+    pub fn set_grid_square(&mut self, coord: Coord, grid_square: GridSquare) -> Result<(), &str> {
+        let square = self
+            .grid
+            .get_mut(coord.row)
+            .and_then(|r| r.get_mut(coord.col))
+            .ok_or("Index out of bounds.")?;
+        *square = Some(grid_square);
+        Ok(())
+    }
+
+    /// Links this map to another with a matched pair of portals, so stepping back through the
+    /// other map's portal returns the hero to where they left.
+    ///
+    /// # Arguments
+    /// * `portal` - A Coord that is where the portal to the other map sits in this map.
+    /// * `arrival` - A Coord that is the room in this map the hero returns to.
+    /// * `other` - A mutable reference to the map to link to.
+    /// * `other_portal` - A Coord that is where the portal back sits in the other map.
+    /// * `other_arrival` - A Coord that is the room in the other map the hero arrives in.
+    ///
+    /// # Returns
+    /// * `Result<(), MapError>` - A result that is Ok, or an error if a portal can't be placed or
+    ///   an arrival isn't a room.
+    ///
+    /// # Examples
     /// ```
-    pub fn set_grid_square(
+    /// use retribution::game::map;
+    ///
+    /// let room = |name: &str| map::GridSquare::Room(map::Room::new(String::from(name), String::new()));
+    /// let mut here = map::Map::new(String::from("Here"), 1, 2).unwrap();
+    /// here.set_grid_square(map::Coord::new(0, 0), room("Hall")).unwrap();
+    /// let mut there = map::Map::new(String::from("There"), 2, 1).unwrap();
+    /// there.set_grid_square(map::Coord::new(1, 0), room("Cellar")).unwrap();
+    /// here.link_portals(
+    ///     map::Coord::new(0, 1),
+    ///     map::Coord::new(0, 0),
+    ///     &mut there,
+    ///     map::Coord::new(0, 0),
+    ///     map::Coord::new(1, 0),
+    /// )
+    /// .unwrap();
+    /// assert!(here.validate_links(&there).is_ok());
+    /// ```
+    pub fn link_portals(
         &mut self,
-        row: usize,
-        col: usize,
-        grid_square: GridSquare,
-    ) -> Result<(), &str> {
-        if self.grid.len() < row || self.grid[row].len() < col {
-            return Err("Index out of bounds.");
-        }
-        self.grid[row][col] = Some(grid_square);
+        portal: Coord,
+        arrival: Coord,
+        other: &mut Map,
+        other_portal: Coord,
+        other_arrival: Coord,
+    ) -> Result<(), MapError> {
+        if !self.in_bounds(portal)
+            || !matches!(
+                other.get_grid_square(other_arrival),
+                Some(GridSquare::Room(_))
+            )
+        {
+            return Err(MapError::BrokenPortal(self.name.clone()));
+        }
+        if !other.in_bounds(other_portal)
+            || !matches!(self.get_grid_square(arrival), Some(GridSquare::Room(_)))
+        {
+            return Err(MapError::BrokenPortal(other.name.clone()));
+        }
+        let there = Portal::new(other.name.clone(), other.name.clone(), other_arrival);
+        let back = Portal::new(self.name.clone(), self.name.clone(), arrival);
+        // Both portals are checked above, so they are placed together or not at all.
+        self.grid[portal.row][portal.col] = Some(GridSquare::Portal(there));
+        other.grid[other_portal.row][other_portal.col] = Some(GridSquare::Portal(back));
+        Ok(())
+    }
+
+    /// Checks that the portals between this map and another are consistent, with every portal
+    /// leading to a room in the other map and the other map having a portal back.
+    ///
+    /// # Arguments
+    /// * `other` - A reference to the map the portals lead to.
+    ///
+    /// # Returns
+    /// * `Result<(), MapError>` - A result that is Ok, or the map with the broken portal.
+    pub fn validate_links(&self, other: &Map) -> Result<(), MapError> {
+        let portals_to = |from: &Map, to: &Map| -> Vec<Coord> {
+            from.portals()
+                .filter(|(_, p)| p.target == to.name)
+                .map(|(_, p)| p.location)
+                .collect()
+        };
+        for (from, to) in [(self, other), (other, self)] {
+            let arrivals = portals_to(from, to);
+            let leads_to_room = arrivals
+                .iter()
+                .all(|c| matches!(to.get_grid_square(*c), Some(GridSquare::Room(_))));
+            if !leads_to_room || (arrivals.is_empty() && !portals_to(to, from).is_empty()) {
+                return Err(MapError::BrokenPortal(from.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws the map as ASCII art, one line per row. The hero is drawn as `@`, rooms they have
+    /// visited as `#`, and every other square as `?` so undiscovered areas stay hidden.
+    ///
+    /// # Arguments
+    /// * `visited` - A function that checks if the hero has been in the room at a coordinate.
+    /// * `hero` - The coordinates of the hero, if they are on this map.
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The rows of the drawing.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::migration;
+    ///
+    /// let map = migration::map::test_area();
+    /// let hero = Coord::new(1, 1);
+    /// let lines = map.render(|c| c == hero || c == Coord::new(0, 1), Some(hero));
+    /// assert_eq!(lines, vec!["? # ?", "? @ ?", "? ? ?"]);
+    /// ```
+    pub fn render(&self, visited: impl Fn(Coord) -> bool, hero: Option<Coord>) -> Vec<String> {
+        self.grid
+            .iter()
+            .enumerate()
+            .map(|(row, squares)| {
+                (0..squares.len())
+                    .map(|col| {
+                        let coord = Coord::new(row, col);
+                        match self.get_grid_square(coord) {
+                            _ if hero == Some(coord) => "@",
+                            Some(GridSquare::Room(_)) if visited(coord) => "#",
+                            _ => "?",
+                        }
+                    })
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    /// Finds the room the hero ends up in when stepping onto a square. Portals to another map
+    /// lead off this map, so they don't count.
+    ///
+    /// # Arguments
+    /// * `coord` - A Coord that is the square stepped onto.
+    ///
+    /// # Returns
+    /// * `Option<Coord>` - The coordinates of the room, or None if there isn't one on this map.
+    fn arrive_at(&self, coord: Coord) -> Option<Coord> {
+        let coord = match self.get_grid_square(coord)? {
+            GridSquare::Room(_) => coord,
+            GridSquare::Portal(p) if p.target == self.name => p.location,
+            GridSquare::Portal(_) => return None,
+        };
+        matches!(self.get_grid_square(coord), Some(GridSquare::Room(_))).then_some(coord)
+    }
+
+    /// Finds the rooms of this map the hero can walk to in one move along the compass, or
+    /// diagonally when the map allows it.
+    ///
+    /// # Arguments
+    /// * `from` - A Coord that is where the hero is standing.
+    ///
+    /// # Returns
+    /// * `Vec<(Direction, Coord)>` - The direction of each move and the room it leads to.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::migration;
+    /// use retribution::ret_lang::Direction;
+    ///
+    /// let map = migration::map::test_area();
+    /// assert_eq!(
+    ///     map.moves(Coord::new(0, 1)),
+    ///     vec![(Direction::South, Coord::new(1, 1))]
+    /// );
+    /// ```
+    pub fn moves(&self, from: Coord) -> Vec<(Direction, Coord)> {
+        Direction::ALL
+            .into_iter()
+            .filter(|d| self.allow_diagonal || !d.is_diagonal())
+            .filter_map(|d| Some((d, self.arrive_at(from.toward(d)?)?)))
+            .collect()
+    }
+
+    /// Finds the shortest series of moves from one room of the map to another, stepping through
+    /// portals only when they lead back into this map. Named exits aren't followed, as they
+    /// have no direction.
+    ///
+    /// # Arguments
+    /// * `from` - A Coord that is the room to start in.
+    /// * `to` - A Coord that is the room to end in.
+    ///
+    /// # Returns
+    /// * `Option<Vec<Direction>>` - The moves to make, or None if the room can't be reached.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::migration;
+    /// use retribution::ret_lang::Direction;
+    ///
+    /// let map = migration::map::test_area();
+    /// assert_eq!(
+    ///     map.path_between(Coord::new(1, 0), Coord::new(0, 1)),
+    ///     Some(vec![Direction::East, Direction::North])
+    /// );
+    /// ```
+    pub fn path_between(&self, from: Coord, to: Coord) -> Option<Vec<Direction>> {
+        let start = self.arrive_at(from)?;
+        let mut came_from: HashMap<Coord, (Coord, Direction)> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(coord) = queue.pop_front() {
+            if coord == to {
+                let mut path = vec![];
+                let mut step = coord;
+                while let Some((previous, direction)) = came_from.get(&step) {
+                    path.push(*direction);
+                    step = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (direction, next) in self.moves(coord) {
+                if next != start && !came_from.contains_key(&next) {
+                    came_from.insert(next, (coord, direction));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds every room the hero can walk to from a starting room, through moves, named exits,
+    /// and portals, including those inside rooms, that stay on this map.
+    ///
+    /// # Arguments
+    /// * `start` - A Coord that is the room to start from.
+    ///
+    /// # Returns
+    /// * `HashSet<Coord>` - The rooms that can be reached, including the start.
+    fn reachable(&self, start: Coord) -> HashSet<Coord> {
+        let mut reached = HashSet::new();
+        let mut queue: Vec<Coord> = self.arrive_at(start).into_iter().collect();
+        while let Some(coord) = queue.pop() {
+            if !reached.insert(coord) {
+                continue;
+            }
+            if let Some(GridSquare::Room(room)) = self.get_grid_square(coord) {
+                let exits = room.exits.values().filter_map(|c| self.arrive_at(*c));
+                queue.extend(exits);
+                let portals = room
+                    .portals
+                    .iter()
+                    .filter(|p| p.target == self.name)
+                    .filter_map(|p| self.arrive_at(p.location));
+                queue.extend(portals);
+            }
+            queue.extend(self.moves(coord).into_iter().map(|(_, c)| c));
+        }
+        reached
+    }
+
+    /// Checks the map for anything that would spoil it for players: no rooms, rooms sharing a
+    /// name, portals that lead nowhere, and rooms that can't be reached from the start.
+    ///
+    /// # Arguments
+    /// * `start` - A Coord that is the room the hero starts in.
+    /// * `others` - A slice of the other maps the portals may lead to.
+    ///
+    /// # Returns
+    /// * `Vec<MapIssue>` - Every problem found, which is empty for a good map.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::migration;
+    ///
+    /// let (test_area, test_area_2) = migration::map::test_areas();
+    /// assert!(test_area.issues(Coord::new(1, 1), &[test_area_2]).is_empty());
+    /// ```
+    pub fn issues(&self, start: Coord, others: &[Map]) -> Vec<MapIssue> {
+        if self.rooms().next().is_none() {
+            return vec![MapIssue::NoRooms];
+        }
+        let mut issues = vec![];
+        let mut names = HashSet::new();
+        for (_, room) in self.rooms() {
+            if !names.insert(room.name.as_str()) {
+                issues.push(MapIssue::DuplicateRoom(room.name.clone()));
+            }
+        }
+        for (coord, portal) in self.portals() {
+            let target = match portal.target == self.name {
+                true => Some(self),
+                false => others.iter().find(|m| m.name == portal.target),
+            };
+            let leads_to_room = target.is_some_and(|m| {
+                matches!(
+                    m.get_grid_square(portal.location),
+                    Some(GridSquare::Room(_))
+                )
+            });
+            if !leads_to_room {
+                issues.push(MapIssue::DanglingPortal(coord));
+            }
+        }
+        let reached = self.reachable(start);
+        for (coord, room) in self.rooms() {
+            if !reached.contains(&coord) {
+                issues.push(MapIssue::Unreachable(room.name.clone(), coord));
+            }
+        }
+        issues
+    }
+
+    /// Checks that the map is well formed, such as every room name being unique.
+    ///
+    /// # Returns
+    /// * `Result<(), MapError>` - A result that is Ok, or the first problem found.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map;
+    ///
+    /// let room = map::GridSquare::Room(map::Room::new(String::from("Hall"), String::from("A hall.")));
+    /// let mut map = map::Map::new(String::from("Test Area"), 1, 2).unwrap();
+    /// map.set_grid_square(map::Coord::new(0, 0), room.clone()).unwrap();
+    /// assert!(map.validate().is_ok());
+    /// map.set_grid_square(map::Coord::new(0, 1), room).unwrap();
+    /// assert_eq!(map.validate(), Err(map::MapError::DuplicateRoom(String::from("Hall"))));
+    /// ```
+    pub fn validate(&self) -> Result<(), MapError> {
+        let mut names = HashSet::new();
+        for (_, room) in self.rooms() {
+            if !names.insert(room.name.as_str()) {
+                return Err(MapError::DuplicateRoom(room.name.clone()));
+            }
+        }
         Ok(())
     }
 }
@@ -127,10 +732,26 @@ impl Map {
 /// A struct that represents a location in the game world.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Room {
-    /// The name of the room.
+    /// The name of the room. Value must be unique within the map.
     pub name: String,
     /// The description of the room.
     pub description: String,
+    /// The items lying in the room.
+    #[serde(default)]
+    pub items: Vec<Item>,
+    /// The enemies lurking in the room.
+    #[serde(default)]
+    pub enemies: Vec<Enemy>,
+    /// Whether the room is too dark to see without a light.
+    #[serde(default)]
+    pub dark: bool,
+    /// The named exits out of the room, such as "ladder", and the coordinates they lead to.
+    #[serde(default)]
+    pub exits: HashMap<String, Coord>,
+    /// The portals inside the room, such as stairs, taken by going through them by name. They
+    /// never collapse, as the room stays behind.
+    #[serde(default)]
+    pub portals: Vec<Portal>,
 }
 
 impl Room {
@@ -150,7 +771,39 @@ impl Room {
     /// let room = map::Room::new(String::from("Test Room"), String::from("This is a test room."));
     /// ```
     pub fn new(name: String, description: String) -> Room {
-        Room { name, description }
+        Room {
+            name,
+            description,
+            items: vec![],
+            enemies: vec![],
+            dark: false,
+            exits: HashMap::new(),
+            portals: vec![],
+        }
+    }
+
+    /// Finds a portal inside the room by name, ignoring case.
+    ///
+    /// # Arguments
+    /// * `name` - A string slice that is the name of the portal.
+    ///
+    /// # Returns
+    /// * `Option<&Portal>` - The portal, or None if the room doesn't have one by that name.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::{Coord, Portal, Room};
+    ///
+    /// let mut room = Room::new(String::from("Cellar"), String::from("A damp cellar."));
+    /// let stairs = Portal::new(String::from("stairs"), String::from("Tower"), Coord::new(0, 0));
+    /// room.portals.push(stairs);
+    /// assert!(room.portal("Stairs").is_some());
+    /// assert!(room.portal("ladder").is_none());
+    /// ```
+    pub fn portal(&self, name: &str) -> Option<&Portal> {
+        self.portals
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
     }
 }
 
@@ -162,8 +815,14 @@ pub struct Portal {
     pub name: String,
     /// Map name where the user is traveling to.
     pub target: String,
-    /// Coordinates where the user is traveling to in the map. row, col
-    pub location: (i32, i32),
+    /// Coordinates where the user is traveling to in the map.
+    pub location: Coord,
+    /// The name of the item the hero must hold to go through. None if the portal isn't locked.
+    #[serde(default)]
+    pub required_item: Option<String>,
+    /// Whether the portal collapses once the hero goes through it, so there's no going back.
+    #[serde(default)]
+    pub consume_on_use: bool,
 }
 
 impl Portal {
@@ -172,7 +831,7 @@ impl Portal {
     /// # Arguments
     /// * `name` - A string that is the name of the portal.
     /// * `target` - A string that is the name of the map the portal is targeting.
-    /// * `location` - A tuple of i32s that is the coordinates of the portal. (row, col)
+    /// * `location` - A Coord that is where the portal leads in the target map.
     ///
     /// # Returns
     /// * `Portal` - A new Portal.
@@ -181,50 +840,226 @@ impl Portal {
     /// ```
     /// use retribution::game::map;
     ///
-    /// let portal = map::Portal::new(String::from("Test Portal"), String::from("Test Area"), (1, 1));
+    /// let location = map::Coord::new(1, 1);
+    /// let portal = map::Portal::new(String::from("Test Portal"), String::from("Test Area"), location);
     /// assert_eq!(portal.name, "Test Portal");
     /// assert_eq!(portal.target, "Test Area");
-    /// assert_eq!(portal.location, (1, 1));
+    /// assert_eq!(portal.location, location);
     /// ```
-    pub fn new(name: String, target: String, location: (i32, i32)) -> Portal {
+    pub fn new(name: String, target: String, location: Coord) -> Portal {
         Portal {
             name,
             target,
             location,
+            required_item: None,
+            consume_on_use: false,
         }
     }
 }
 
+/// A function that opens the database the maps are stored in.
+///
+/// # Arguments
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+///
+/// # Returns
+/// * `Result<Connection, MapError>` - The open connection, or an error.
+fn open(path: Option<String>) -> Result<Connection, MapError> {
+    let path = path.unwrap_or_else(|| String::from(crate::DB_PATH));
+    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+    db::open(path.as_str()).map_err(|_| MapError::DbUnavailable)
+}
+
+/// A function that turns a failed query into a map error, telling a database that is busy or
+/// hasn't been migrated apart from other failures. A database without the maps table hasn't been
+/// migrated.
+///
+/// # Arguments
+/// * `conn` - A reference to the connection the query ran on.
+/// * `error` - The error the query failed with.
+///
+/// # Returns
+/// * `MapError` - The map error to report.
+fn query_error(conn: &Connection, error: rusqlite::Error) -> MapError {
+    if db::is_transient(&error) {
+        return MapError::DbUnavailable;
+    }
+    match db::has_table(conn, "maps") {
+        Ok(false) => MapError::NotMigrated,
+        _ => MapError::Database("Unable to query database."),
+    }
+}
+
 /// A function that loads maps from the database.
 ///
 /// # Arguments
 /// * `map_name` - A string that is the name of the map to load.
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+///
+/// # Returns
+/// * `Result<Map, MapError>` - A result that is Ok, or an error.
+pub fn load_map(map_name: &str, path: Option<String>) -> Result<Map, MapError> {
+    load_map_from(&open(path)?, map_name)
+}
+
+/// A function that loads a map over a connection that is already open, so loading several maps
+/// doesn't open the database each time.
+///
+/// # Arguments
+/// * `conn` - A reference to the open database connection.
+/// * `map_name` - A string slice that is the name of the map to load.
 ///
 /// # Returns
-/// * `Result<Map, &str>` - A result that is Ok, or an error message.
-pub fn load_map(map_name: &str, path: Option<String>) -> Result<Map, &str> {
-    let path = match path {
-        Some(p) => p,
-        None => String::from(crate::DB_PATH),
+/// * `Result<Map, MapError>` - A result that is Ok, or an error.
+pub fn load_map_from(conn: &Connection, map_name: &str) -> Result<Map, MapError> {
+    let row = db::with_retry(|| {
+        conn.query_row(
+            "SELECT name, grid, allow_diagonal FROM maps WHERE name = ?1",
+            [&map_name],
+            |row| Ok((row.get(0)?, row.get::<_, String>(1)?, row.get(2)?)),
+        )
+    });
+    let (name, grid_string, allow_diagonal) = match row {
+        Ok(r) => r,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(MapError::Database("No map found."))
+        }
+        Err(e) => return Err(query_error(conn, e)),
     };
-    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
-    let conn = Connection::open(path.as_str()).map_err(|_| "Unable to open database.")?;
-    let mut stmt = conn
-        .prepare("SELECT name, grid FROM maps WHERE name = ?1")
-        .map_err(|_| "Unable to prepare statement.")?;
-    let mut rows = stmt
-        .query(&[&map_name])
-        .map_err(|_| "Unable to query database.")?;
-    let row = match rows.next() {
-        Ok(Some(r)) => r,
-        Ok(None) => return Err("No map found."),
-        Err(_) => return Err("Unable to get row."),
+    let grid: Vec<Vec<Option<GridSquare>>> = serde_json::from_str(grid_string.as_str())
+        .map_err(|_| MapError::Database("Unable to deserialize grid."))?;
+    let mut map = Map {
+        name,
+        grid,
+        allow_diagonal,
+    };
+    map.validate()?;
+    load_items(conn, &mut map)?;
+    Ok(map)
+}
+
+/// A function that saves a map to the database, so maps built outside of migrations can be
+/// loaded. The map is checked before it is saved.
+///
+/// # Arguments
+/// * `map` - A reference to the map to save.
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+/// * `overwrite` - Whether a map already saved by the same name is replaced.
+///
+/// # Returns
+/// * `Result<(), MapError>` - A result that is Ok if the map was saved, or an error.
+pub fn save_map(map: &Map, path: Option<String>, overwrite: bool) -> Result<(), MapError> {
+    let mut conn = open(path)?;
+    save_map_to(&mut conn, map, overwrite)?;
+    conn.close()
+        .map_err(|_| MapError::Database("Unable to close database."))
+}
+
+/// A function that saves a map over a connection that is already open. The check for a map by
+/// the same name and the save run in one transaction, which takes the write lock up front, so
+/// the map is never left partly written or saved over another writer's.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to the open database connection.
+/// * `map` - A reference to the map to save.
+/// * `overwrite` - Whether a map already saved by the same name is replaced.
+///
+/// # Returns
+/// * `Result<(), MapError>` - A result that is Ok if the map was saved, or an error.
+pub fn save_map_to(conn: &mut Connection, map: &Map, overwrite: bool) -> Result<(), MapError> {
+    map.validate()?;
+    let grid = serde_json::to_string(&map.grid)
+        .map_err(|_| MapError::Database("Unable to serialize grid."))?;
+    let tx = db::transaction(conn).map_err(|e| query_error(conn, e))?;
+    let exists: bool = tx
+        .query_row(
+            "SELECT COUNT(*) FROM maps WHERE name = ?1",
+            [&map.name],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count > 0)
+        .map_err(|e| query_error(&tx, e))?;
+    if exists && !overwrite {
+        return Err(MapError::AlreadyExists(map.name.clone()));
+    }
+    tx.execute(
+        "INSERT INTO maps (name, grid, allow_diagonal) VALUES (?1, ?2, ?3)
+            ON CONFLICT(name) DO UPDATE SET grid = excluded.grid,
+                allow_diagonal = excluded.allow_diagonal",
+        rusqlite::params![map.name, grid, map.allow_diagonal],
+    )
+    .map_err(|_| MapError::Database("Unable to save map."))?;
+    tx.commit()
+        .map_err(|_| MapError::Database("Unable to commit transaction."))
+}
+
+/// A function that loads a map, and the maps its portals lead to, and checks it for issues
+/// starting from its first room.
+///
+/// # Arguments
+/// * `map_name` - A string slice that is the name of the map to check.
+/// * `path` - An optional path to the database, defaulting to the crate database path.
+///
+/// # Returns
+/// * `Result<Vec<MapIssue>, MapError>` - The issues found, or an error if the map can't be
+///   loaded.
+pub fn validate_map(map_name: &str, path: Option<String>) -> Result<Vec<MapIssue>, MapError> {
+    let conn = open(path)?;
+    let map = match load_map_from(&conn, map_name) {
+        // Loading stops at rooms sharing a name, so report that rather than failing.
+        Err(MapError::DuplicateRoom(name)) => return Ok(vec![MapIssue::DuplicateRoom(name)]),
+        map => map?,
     };
-    let name = row.get(0).map_err(|_| "Unable to get name.")?;
-    let grid_string: String = row.get(1).map_err(|_| "Unable to get grid.")?;
-    let grid: Vec<Vec<Option<GridSquare>>> =
-        serde_json::from_str(grid_string.as_str()).map_err(|_| "Unable to deserialize grid.")?;
-    Ok(Map { name, grid })
+    let targets: HashSet<&str> = map
+        .portals()
+        .map(|(_, p)| p.target.as_str())
+        .filter(|t| *t != map.name)
+        .collect();
+    let others: Vec<Map> = targets
+        .into_iter()
+        .filter_map(|t| load_map_from(&conn, t).ok())
+        .collect();
+    let start = map.first_room().unwrap_or(Coord::new(0, 0));
+    Ok(map.issues(start, &others))
+}
+
+/// A function that places the items stored in the database into the rooms of a map.
+///
+/// # Arguments
+/// * `conn` - A reference to the open database connection.
+/// * `map` - A mutable reference to the map to place the items in.
+///
+/// # Returns
+/// * `Result<(), MapError>` - A result that is Ok, or an error.
+fn load_items(conn: &Connection, map: &mut Map) -> Result<(), MapError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT row, col, name, description, weight, damage
+                FROM items WHERE map_name = ?1 ORDER BY id",
+        )
+        .map_err(|_| MapError::Database("Unable to prepare statement."))?;
+    let items = stmt
+        .query_map([&map.name], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, usize>(1)?,
+                Item {
+                    damage: row.get(5)?,
+                    ..Item::new(row.get(2)?, row.get(3)?, row.get(4)?)
+                },
+            ))
+        })
+        .map_err(|_| MapError::Database("Unable to query items."))?;
+    for item in items {
+        let (row, col, item) = item.map_err(|_| MapError::Database("Unable to get item."))?;
+        // Items placed outside of a room have nowhere to sit, so they are skipped.
+        if let Some(Some(GridSquare::Room(room))) =
+            map.grid.get_mut(row).and_then(|r| r.get_mut(col))
+        {
+            room.items.push(item);
+        }
+    }
+    Ok(())
 }
 
 /// A grid square is a struct that represents a square on the map grid.
@@ -241,10 +1076,252 @@ mod tests {
     #[test]
     fn load_map_test() {
         // Create an in memory database.
-        crate::migration::map::migrate_up(Some(String::from("test.db"))).unwrap();
+        crate::migration::migrate_up(Some(String::from("test.db"))).unwrap();
         let map = load_map("Test Area", Some(String::from("test.db"))).unwrap();
         std::fs::remove_file("test.db").unwrap();
         assert_eq!(map.name, "Test Area");
         assert_eq!(map.grid.len(), 3);
     }
+
+    /// Test that a portal without a way back is caught.
+    #[test]
+    fn validate_links_test() {
+        let (test_area, test_area_2) = crate::migration::map::test_areas();
+        assert!(test_area.validate_links(&test_area_2).is_ok());
+        let mut one_way = test_area_2.clone();
+        one_way.grid[0][0] = None;
+        assert_eq!(
+            test_area.validate_links(&one_way),
+            Err(MapError::BrokenPortal(String::from("Test Area 2")))
+        );
+    }
+
+    /// Test walking the squares, rooms, and portals of the test area.
+    #[test]
+    fn iter_grid_test() {
+        let map = crate::migration::map::test_area();
+        assert_eq!(map.iter_grid().count(), 5);
+        assert_eq!(map.rooms().count(), 4);
+        let portals: Vec<Coord> = map.portals().map(|(c, _)| c).collect();
+        assert_eq!(portals, vec![Coord::new(1, 0), Coord::new(2, 1)]);
+        assert_eq!(
+            map.rooms()
+                .find(|(_, r)| r.name == "Room 3")
+                .map(|(c, _)| c),
+            Some(Coord::new(1, 2))
+        );
+    }
+
+    /// Test that a room cut off from the rest of the map is reported.
+    #[test]
+    fn issues_unreachable_room_test() {
+        let mut map = Map::new(String::from("Islands"), 1, 3).unwrap();
+        map.set_grid_square(Coord::new(0, 0), crate::room!("Shore", "Sand."))
+            .unwrap();
+        map.set_grid_square(Coord::new(0, 2), crate::room!("Island", "Palms."))
+            .unwrap();
+        assert_eq!(
+            map.issues(Coord::new(0, 0), &[]),
+            vec![MapIssue::Unreachable(
+                String::from("Island"),
+                Coord::new(0, 2)
+            )]
+        );
+
+        // A named exit builds a way across.
+        if let Some(GridSquare::Room(shore)) = map.get_grid_square_mut(Coord::new(0, 0)) {
+            shore.exits.insert(String::from("boat"), Coord::new(0, 2));
+        }
+        assert!(map.issues(Coord::new(0, 0), &[]).is_empty());
+
+        // So does a portal inside a room, once the boat is gone.
+        if let Some(GridSquare::Room(shore)) = map.get_grid_square_mut(Coord::new(0, 0)) {
+            shore.exits.clear();
+            let ferry = Portal::new(
+                String::from("ferry"),
+                String::from("Islands"),
+                Coord::new(0, 2),
+            );
+            shore.portals.push(ferry);
+        }
+        assert!(map.issues(Coord::new(0, 0), &[]).is_empty());
+        assert_eq!(
+            Map::new(String::from("Void"), 1, 1)
+                .unwrap()
+                .issues(Coord::new(0, 0), &[]),
+            vec![MapIssue::NoRooms]
+        );
+    }
+
+    /// Test that the test areas are fully connected, and portals without their map dangle.
+    #[test]
+    fn issues_connected_map_test() {
+        let (test_area, test_area_2) = crate::migration::map::test_areas();
+        assert!(test_area
+            .issues(Coord::new(1, 1), std::slice::from_ref(&test_area_2))
+            .is_empty());
+        assert!(test_area_2
+            .issues(Coord::new(1, 0), std::slice::from_ref(&test_area))
+            .is_empty());
+        assert_eq!(
+            test_area.issues(Coord::new(1, 1), &[]),
+            vec![
+                MapIssue::DanglingPortal(Coord::new(1, 0)),
+                MapIssue::DanglingPortal(Coord::new(2, 1))
+            ]
+        );
+    }
+
+    /// Test finding a path between rooms that are connected, and ones that aren't.
+    #[test]
+    fn path_between_test() {
+        let map = crate::migration::map::test_area();
+        assert_eq!(
+            map.path_between(Coord::new(1, 0), Coord::new(1, 2)),
+            Some(vec![Direction::East, Direction::East])
+        );
+        assert_eq!(
+            map.path_between(Coord::new(1, 1), Coord::new(1, 1)),
+            Some(vec![])
+        );
+        // The empty corner, and the portal to Test Area 2, aren't rooms of this map.
+        assert_eq!(map.path_between(Coord::new(1, 1), Coord::new(0, 0)), None);
+        assert_eq!(map.path_between(Coord::new(1, 1), Coord::new(2, 1)), None);
+
+        let mut islands = Map::new(String::from("Islands"), 1, 3).unwrap();
+        islands
+            .set_grid_square(Coord::new(0, 0), crate::room!("Shore", "Sand."))
+            .unwrap();
+        islands
+            .set_grid_square(Coord::new(0, 2), crate::room!("Island", "Palms."))
+            .unwrap();
+        assert_eq!(
+            islands.path_between(Coord::new(0, 0), Coord::new(0, 2)),
+            None
+        );
+    }
+
+    /// Test validating a map stored in the database.
+    #[test]
+    fn validate_map_test() {
+        let path = String::from("validate_map_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let issues = validate_map("Test Area", Some(path.clone()));
+        let missing = validate_map("Nowhere", Some(path.clone()));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(issues, Ok(vec![]));
+        assert!(missing.is_err());
+    }
+
+    /// Test that coordinates are saved as a (row, col) pair.
+    #[test]
+    fn coord_serialize_test() {
+        let coord = Coord::new(2, 1);
+        assert_eq!(serde_json::to_string(&coord).unwrap(), "[2,1]");
+        assert_eq!(serde_json::from_str::<Coord>("[2,1]").unwrap(), coord);
+    }
+
+    /// Test that maps without any rows or columns can't be created.
+    #[test]
+    fn new_invalid_dimensions_test() {
+        let zero = Map::new(String::from("Nowhere"), 0, 0);
+        assert_eq!(zero.err(), Some(MapError::InvalidDimensions(0, 0)));
+        let no_cols = Map::new(String::from("Nowhere"), 3, 0);
+        assert_eq!(no_cols.err(), Some(MapError::InvalidDimensions(3, 0)));
+        assert!(Map::new(String::from("Somewhere"), 1, 1).is_ok());
+    }
+
+    /// Test that a built map can be saved and loaded back, but only overwritten when asked.
+    #[test]
+    fn save_map_test() {
+        let path = String::from("save_map_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let mut map = Map::new(String::from("Garden"), 1, 2).unwrap();
+        map.allow_diagonal = true;
+        let mut shed = Room::new(String::from("Shed"), String::from("A musty shed."));
+        shed.dark = true;
+        map.set_grid_square(Coord::new(0, 0), GridSquare::Room(shed))
+            .unwrap();
+        let lawn = Room::new(String::from("Lawn"), String::from("A green lawn."));
+        map.set_grid_square(Coord::new(0, 1), GridSquare::Room(lawn))
+            .unwrap();
+        let saved = save_map(&map, Some(path.clone()), false);
+        let loaded = load_map("Garden", Some(path.clone()));
+        let again = save_map(&map, Some(path.clone()), false);
+        let built = map.grid.clone();
+        map.grid[0][1] = None;
+        let overwritten = save_map(&map, Some(path.clone()), true);
+        let reloaded = load_map("Garden", Some(path.clone()));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(saved, Ok(()));
+        let loaded = loaded.unwrap();
+        assert!(loaded.allow_diagonal);
+        assert_eq!(loaded.grid, built);
+        assert_eq!(again, Err(MapError::AlreadyExists(String::from("Garden"))));
+        assert_eq!(overwritten, Ok(()));
+        assert_eq!(reloaded.unwrap().rooms().count(), 1);
+    }
+
+    /// Test that loading a map from a database that hasn't been migrated says so.
+    #[test]
+    fn load_map_not_migrated_test() {
+        let path = String::from("load_map_not_migrated_test.db");
+        let loaded = load_map("Test Area", Some(path.clone()));
+        let map = crate::migration::map::test_area();
+        let saved = save_map(&map, Some(path.clone()), false);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(loaded.err(), Some(MapError::NotMigrated));
+        assert_eq!(saved, Err(MapError::NotMigrated));
+    }
+
+    /// Test loading several maps, and saving one, over a single connection.
+    #[test]
+    fn load_map_from_test() {
+        let path = String::from("load_map_from_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let mut conn = Connection::open(path.as_str()).unwrap();
+        let names: Vec<String> = (0..10)
+            .flat_map(|_| ["Test Area", "Test Area 2"])
+            .map(|name| load_map_from(&conn, name).unwrap().name)
+            .collect();
+        let mut copy = load_map_from(&conn, "Test Area").unwrap();
+        copy.name = String::from("Test Area Copy");
+        let saved = save_map_to(&mut conn, &copy, false);
+        let loaded = load_map_from(&conn, "Test Area Copy");
+        let missing = load_map_from(&conn, "Nowhere");
+        conn.close().unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(names.len(), 20);
+        assert_eq!(names[19], "Test Area 2");
+        assert_eq!(saved, Ok(()));
+        assert_eq!(loaded.unwrap().grid, copy.grid);
+        assert_eq!(missing.err(), Some(MapError::Database("No map found.")));
+    }
+
+    /// Test that loading a map with two rooms sharing a name fails.
+    #[test]
+    fn load_map_duplicate_room_test() {
+        let path = String::from("load_map_duplicate_room_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let room = GridSquare::Room(Room::new(
+            String::from("Twin Room"),
+            String::from("This room has a twin."),
+        ));
+        let mut map = Map::new(String::from("Twin Area"), 1, 2).unwrap();
+        map.set_grid_square(Coord::new(0, 0), room.clone()).unwrap();
+        map.set_grid_square(Coord::new(0, 1), room).unwrap();
+        let db = Connection::open(path.as_str()).unwrap();
+        db.execute(
+            "INSERT INTO maps (name, grid) VALUES (?1, ?2)",
+            [map.name.clone(), serde_json::to_string(&map.grid).unwrap()],
+        )
+        .unwrap();
+        db.close().unwrap();
+        let result = load_map("Twin Area", Some(path.clone()));
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            result.map(|m| m.name),
+            Err(MapError::DuplicateRoom(String::from("Twin Room")))
+        );
+    }
 }
@@ -0,0 +1,311 @@
+//! # Player
+//! Module that represents the hero controlled by the player.
+use crate::game::item::{Item, WeaponType};
+use crate::game::spell;
+use serde::{Deserialize, Serialize};
+
+/// The score every stat starts at.
+pub const DEFAULT_STAT: i32 = 10;
+/// The hit points a new hero starts with.
+const DEFAULT_HP: i32 = 20;
+/// The mana a new hero starts with.
+const DEFAULT_MANA: i32 = 10;
+/// The sides on the damage die of a hero fighting with their fists.
+const FIST_DAMAGE: u32 = 4;
+/// Message shown when learning a spell that isn't in the registry.
+pub const UNKNOWN_SPELL_MESSAGE: &str = "There's no spell by that name.";
+/// Message shown when learning a spell the hero already knows.
+pub const ALREADY_KNOWN_MESSAGE: &str = "You already know that spell.";
+/// The XP needed to level up is the hero's level plus this amount.
+const LEVEL_UP_BASE_XP: u32 = 7;
+/// The max hit points the hero gains on leveling up.
+const LEVEL_UP_HP: i32 = 2;
+
+/// The spells a new hero knows, also used for heroes saved before spells existed.
+fn default_spells() -> Vec<String> {
+    vec![String::from(spell::DEFAULT_SPELL)]
+}
+
+/// The mana of a new hero, also used for heroes saved before mana existed.
+fn default_mana() -> i32 {
+    DEFAULT_MANA
+}
+
+/// The level a new hero starts at, also used for heroes saved before leveling existed.
+fn default_level() -> u32 {
+    1
+}
+
+/// A struct that represents the hero.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Player {
+    /// The name of the hero.
+    pub name: String,
+    /// The current hit points of the hero.
+    pub hp: i32,
+    /// The most hit points the hero can have.
+    pub max_hp: i32,
+    /// The current mana of the hero, spent to cast spells.
+    #[serde(default = "default_mana")]
+    pub mana: i32,
+    /// The most mana the hero can have.
+    #[serde(default = "default_mana")]
+    pub max_mana: i32,
+    /// How strong the hero is. Also decides how much the hero can carry.
+    pub strength: i32,
+    /// How quick and nimble the hero is.
+    pub dexterity: i32,
+    /// How tough the hero is.
+    pub constitution: i32,
+    /// How knowledgeable the hero is.
+    pub intelligence: i32,
+    /// How perceptive the hero is.
+    pub wisdom: i32,
+    /// How persuasive the hero is.
+    pub charisma: i32,
+    /// The weapon the hero is wielding.
+    #[serde(default)]
+    pub weapon: Option<Item>,
+    /// The experience the hero has marked towards their next level.
+    #[serde(default)]
+    pub xp: u32,
+    /// The level of the hero.
+    #[serde(default = "default_level")]
+    pub level: u32,
+    /// The names of the spells the hero knows.
+    #[serde(default = "default_spells")]
+    pub spells: Vec<String>,
+}
+
+impl Player {
+    /// Constructor for the Player struct.
+    ///
+    /// # Arguments
+    /// * `name` - A string that is the name of the hero.
+    ///
+    /// # Returns
+    /// * `Player` - A new Player with full hit points and average stats.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let hero = player::Player::new(String::from("Hero"));
+    /// assert_eq!(hero.hp, hero.max_hp);
+    /// ```
+    pub fn new(name: String) -> Player {
+        Player {
+            name,
+            hp: DEFAULT_HP,
+            max_hp: DEFAULT_HP,
+            mana: DEFAULT_MANA,
+            max_mana: DEFAULT_MANA,
+            strength: DEFAULT_STAT,
+            dexterity: DEFAULT_STAT,
+            constitution: DEFAULT_STAT,
+            intelligence: DEFAULT_STAT,
+            wisdom: DEFAULT_STAT,
+            charisma: DEFAULT_STAT,
+            weapon: None,
+            xp: 0,
+            level: default_level(),
+            spells: default_spells(),
+        }
+    }
+
+    /// The number of sides on the hero's damage die, from their weapon or their fists.
+    ///
+    /// # Returns
+    /// * `u32` - The sides on the damage die.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// assert_eq!(hero.damage_die(), 4);
+    /// hero.weapon = Some(item::Item::weapon(String::from("sword"), String::from("Sharp."), 2, 8));
+    /// assert_eq!(hero.damage_die(), 8);
+    /// ```
+    pub fn damage_die(&self) -> u32 {
+        self.weapon
+            .as_ref()
+            .and_then(|w| w.damage)
+            .unwrap_or(FIST_DAMAGE)
+    }
+
+    /// How the hero fights with what they are wielding. Fists are a melee weapon.
+    ///
+    /// # Returns
+    /// * `WeaponType` - The type of the hero's weapon.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// assert_eq!(hero.weapon_type(), item::WeaponType::Melee);
+    /// hero.weapon = Some(item::Item::ranged_weapon(String::from("bow"), String::from("Yew."), 1, 6));
+    /// assert_eq!(hero.weapon_type(), item::WeaponType::Ranged);
+    /// ```
+    pub fn weapon_type(&self) -> WeaponType {
+        self.weapon
+            .as_ref()
+            .map(|w| w.weapon_type)
+            .unwrap_or(WeaponType::Melee)
+    }
+
+    /// The most weight the hero can carry, which is equal to their strength.
+    ///
+    /// # Returns
+    /// * `u32` - The carry capacity of the hero.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// hero.strength = 12;
+    /// assert_eq!(hero.carry_capacity(), 12);
+    /// ```
+    pub fn carry_capacity(&self) -> u32 {
+        self.strength.max(0) as u32
+    }
+
+    /// Heals the hero, up to their max hit points.
+    ///
+    /// # Arguments
+    /// * `amount` - An i32 that is the hit points to heal.
+    ///
+    /// # Returns
+    /// * `i32` - The hit points the hero actually healed.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// hero.hp = hero.max_hp - 3;
+    /// assert_eq!(hero.heal(5), 3);
+    /// assert_eq!(hero.hp, hero.max_hp);
+    /// ```
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        let healed = amount.min(self.max_hp - self.hp).max(0);
+        self.hp += healed;
+        healed
+    }
+
+    /// Restores the hero's mana, up to their max mana.
+    ///
+    /// # Arguments
+    /// * `amount` - An i32 that is the mana to restore.
+    ///
+    /// # Returns
+    /// * `i32` - The mana the hero actually restored.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// hero.mana = 0;
+    /// assert_eq!(hero.restore_mana(4), 4);
+    /// assert_eq!(hero.restore_mana(100), hero.max_mana - 4);
+    /// ```
+    pub fn restore_mana(&mut self, amount: i32) -> i32 {
+        let restored = amount.min(self.max_mana - self.mana).max(0);
+        self.mana += restored;
+        restored
+    }
+
+    /// The spells the hero knows, from the spell registry.
+    ///
+    /// # Returns
+    /// * `Vec<&spell::Spell>` - The known spells, in the order they were learned.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let hero = player::Player::new(String::from("Hero"));
+    /// assert_eq!(hero.known_spells()[0].name, "light");
+    /// ```
+    pub fn known_spells(&self) -> Vec<&'static spell::Spell> {
+        self.spells.iter().filter_map(|s| spell::find(s)).collect()
+    }
+
+    /// Teaches the hero a spell from the registry.
+    ///
+    /// # Arguments
+    /// * `name` - A string slice that is the name of the spell.
+    ///
+    /// # Returns
+    /// * `Result<&spell::Spell, &str>` - The spell learned, or an error message if there is no
+    ///   such spell or the hero already knows it.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// assert!(hero.learn_spell("fireball").is_ok());
+    /// assert_eq!(hero.known_spells().len(), 2);
+    /// ```
+    pub fn learn_spell(&mut self, name: &str) -> Result<&'static spell::Spell, &'static str> {
+        let spell = spell::find(name).ok_or(UNKNOWN_SPELL_MESSAGE)?;
+        if self.spells.iter().any(|s| s == spell.name) {
+            return Err(ALREADY_KNOWN_MESSAGE);
+        }
+        self.spells.push(String::from(spell.name));
+        Ok(spell)
+    }
+
+    /// The XP the hero needs to reach their next level.
+    ///
+    /// # Returns
+    /// * `u32` - The XP needed to level up.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let hero = player::Player::new(String::from("Hero"));
+    /// assert_eq!(hero.xp_to_level(), 8);
+    /// ```
+    pub fn xp_to_level(&self) -> u32 {
+        self.level + LEVEL_UP_BASE_XP
+    }
+
+    /// Marks XP for the hero, leveling them up when they reach the threshold. Leveling up spends
+    /// the XP and raises the hero's max hit points.
+    ///
+    /// # Arguments
+    /// * `amount` - A u32 that is the XP to mark.
+    ///
+    /// # Returns
+    /// * `bool` - True if the hero leveled up.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::player;
+    ///
+    /// let mut hero = player::Player::new(String::from("Hero"));
+    /// assert!(!hero.gain_xp(7));
+    /// assert!(hero.gain_xp(1));
+    /// assert_eq!(hero.level, 2);
+    /// assert_eq!(hero.xp, 0);
+    /// ```
+    pub fn gain_xp(&mut self, amount: u32) -> bool {
+        self.xp += amount;
+        if self.xp < self.xp_to_level() {
+            return false;
+        }
+        self.xp -= self.xp_to_level();
+        self.level += 1;
+        self.max_hp += LEVEL_UP_HP;
+        self.hp += LEVEL_UP_HP;
+        true
+    }
+}
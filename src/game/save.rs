@@ -0,0 +1,199 @@
+//! # Save
+//! This module saves the game state to the database and loads it back.
+use crate::db;
+use crate::game::state::GameState;
+use rusqlite::Connection;
+
+/// The slot used when the player doesn't name one.
+pub const DEFAULT_SLOT: &str = "default";
+/// The slot the game is saved to automatically as the hero travels.
+pub const AUTOSAVE_SLOT: &str = "autosave";
+
+/// A function that opens the database a game is saved in.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `Result<Connection, &'static str>` - The open connection, or an error message.
+fn open(path: &str) -> Result<Connection, &'static str> {
+    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+    db::open(path.as_str()).map_err(|_| "Unable to open database.")
+}
+
+/// A function that saves the game state to a slot, replacing any game already saved there.
+///
+/// # Arguments
+/// * `state` - A reference to the game state to save.
+/// * `slot` - A string slice that is the name of the save slot.
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok if the game was saved, or an error message.
+pub fn save_game(state: &GameState, slot: &str, path: &str) -> Result<(), &'static str> {
+    let db = open(path)?;
+    save_game_to(&db, state, slot)?;
+    db.close().map_err(|_| "Unable to close database.")
+}
+
+/// A function that saves the game state to a slot over a connection that is already open.
+///
+/// # Arguments
+/// * `db` - A reference to the open database connection.
+/// * `state` - A reference to the game state to save.
+/// * `slot` - A string slice that is the name of the save slot.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok if the game was saved, or an error message.
+pub fn save_game_to(db: &Connection, state: &GameState, slot: &str) -> Result<(), &'static str> {
+    let json = serde_json::to_string(state).map_err(|_| "Unable to serialize game state.")?;
+    let sql =
+        "INSERT OR REPLACE INTO saves (slot, state, saved_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)";
+    db::with_retry(|| db.execute(sql, [slot, json.as_str()]))
+        .map_err(|_| "Unable to save game.")?;
+    Ok(())
+}
+
+/// A function that saves the game state to the autosave slot. The save is small and the database
+/// waits out other writers, so it is done in the game loop rather than on a thread.
+///
+/// # Arguments
+/// * `state` - A reference to the game state to save.
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok if the game was saved, or an error message.
+pub fn autosave(state: &GameState, path: &str) -> Result<(), &'static str> {
+    save_game(state, AUTOSAVE_SLOT, path)
+}
+
+/// A function that loads the game state saved in a slot. The current map is saved by name, so it
+/// is reloaded from the same database.
+///
+/// # Arguments
+/// * `slot` - A string slice that is the name of the save slot.
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `Result<GameState, &'static str>` - The saved game state, or an error message.
+pub fn load_game(slot: &str, path: &str) -> Result<GameState, &'static str> {
+    let db = open(path)?;
+    let json: String = db::with_retry(|| {
+        db.query_row("SELECT state FROM saves WHERE slot = ?1", [slot], |row| {
+            row.get(0)
+        })
+    })
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => "No game is saved in that slot.",
+        _ => "Unable to load game.",
+    })?;
+    db.close().map_err(|_| "Unable to close database.")?;
+    let mut state: GameState =
+        serde_json::from_str(json.as_str()).map_err(|_| "Unable to deserialize game state.")?;
+    state
+        .reload_map(Some(path.to_string()))
+        .map_err(|_| "Unable to load the saved map.")?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::enemy::Enemy;
+    use crate::game::map::Coord;
+
+    /// Test that a saved game can be loaded back.
+    #[test]
+    fn save_and_load_test() {
+        let path = "save_and_load_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut state = GameState::new();
+        state.room = Some(Coord::new(1, 2));
+        state.player.hp = 7;
+        let saved = save_game(&state, "slot1", path);
+        let loaded = load_game("slot1", path);
+        let missing = load_game("slot2", path);
+        std::fs::remove_file(path).unwrap();
+        saved.unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.room, Some(Coord::new(1, 2)));
+        assert_eq!(loaded.player.hp, 7);
+        assert_eq!(missing.err(), Some("No game is saved in that slot."));
+    }
+
+    /// Test that a game saved in the middle of a fight loads back into the fight.
+    #[test]
+    fn save_and_load_combat_test() {
+        let path = "save_and_load_combat_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut state = GameState::new();
+        state.map = Some(crate::migration::map::test_area());
+        state.room = Some(Coord::new(1, 1));
+        let mut goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+        goblin.hp = 2;
+        state.current_room_mut().unwrap().enemies.push(goblin);
+        state.start_combat();
+        let saved = save_game(&state, "slot1", path);
+        let loaded = load_game("slot1", path);
+        std::fs::remove_file(path).unwrap();
+        saved.unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.mode, crate::game::state::Mode::Combat);
+        assert_eq!(loaded.combat, state.combat);
+        assert_eq!(loaded.current_room().unwrap().enemies[0].hp, 2);
+    }
+
+    /// Test that items taken from a room stay taken when the game is loaded, and come back when an
+    /// earlier save is loaded, so no item is ever in two places.
+    #[test]
+    fn save_take_load_test() {
+        let path = "save_take_load_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let game_config = crate::game::config::GameConfig {
+            db_path: String::from(path),
+            ..crate::game::config::GameConfig::new()
+        };
+        let mut state = GameState::new();
+        state.map =
+            Some(crate::game::map::load_map("Test Area", Some(String::from(path))).unwrap());
+        state.room = Some(Coord::new(1, 1));
+        let take = crate::ret_lang::parse_input("take sword").unwrap_or_else(|e| panic!("{}", e));
+        let before = save_game(&state, "before", path);
+        let taken = crate::game::interpreter::interpret(&take, &mut state, &game_config);
+        let after = save_game(&state, "after", path);
+        let loaded_before = load_game("before", path);
+        let loaded_after = load_game("after", path);
+        std::fs::remove_file(path).unwrap();
+        before.unwrap();
+        taken.unwrap_or_else(|e| panic!("{}", e));
+        after.unwrap();
+        let loaded_before = loaded_before.unwrap();
+        assert!(loaded_before.inventory.is_empty());
+        assert_eq!(loaded_before.current_room().unwrap().items.len(), 1);
+        let loaded_after = loaded_after.unwrap();
+        assert_eq!(loaded_after.inventory.len(), 1);
+        assert!(loaded_after.current_room().unwrap().items.is_empty());
+    }
+
+    /// Test that the map is saved by name and reloaded with the same room.
+    #[test]
+    fn save_and_load_map_test() {
+        let path = "save_and_load_map_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        let mut state = GameState::new();
+        state.map = Some(crate::migration::map::test_area());
+        state.room = Some(Coord::new(0, 1));
+        let json = serde_json::to_value(&state).unwrap();
+        let saved = save_game(&state, "slot1", path);
+        let loaded = load_game("slot1", path);
+        std::fs::remove_file(path).unwrap();
+        saved.unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(json["map_name"], "Test Area");
+        assert_eq!(loaded.current_map_name(), Some("Test Area"));
+        assert_eq!(
+            loaded.current_room().map(|r| r.description.clone()),
+            state.current_room().map(|r| r.description.clone())
+        );
+    }
+}
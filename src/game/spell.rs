@@ -0,0 +1,112 @@
+//! # Spell
+//! Module that holds the spells a hero can learn and cast.
+use std::fmt;
+
+/// The spell every new hero knows.
+pub const DEFAULT_SPELL: &str = "light";
+/// The name of the item the hero needs to learn spells.
+pub const SPELLBOOK: &str = "spellbook";
+
+/// An enum that represents what a spell does when it is cast.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpellEffect {
+    /// Lights up the room.
+    Light,
+    /// Deals damage to the targeted enemy.
+    Damage { dice: u32, sides: u32 },
+    /// Deals damage to every enemy in the room.
+    DamageAll { dice: u32, sides: u32 },
+    /// Heals the hero.
+    Heal { sides: u32 },
+}
+
+/// A struct that represents a spell in the registry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Spell {
+    /// The name of the spell, which is what the hero types to cast it.
+    pub name: &'static str,
+    /// What the spell does, as shown to the player.
+    pub description: &'static str,
+    /// The level of the spell. Level 0 spells are cantrips.
+    pub level: u32,
+    /// The mana it costs to cast the spell.
+    pub cost: i32,
+    /// What the spell does when it is cast.
+    pub effect: SpellEffect,
+}
+
+impl fmt::Display for Spell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (level {}, {} mana): {}",
+            self.name, self.level, self.cost, self.description
+        )
+    }
+}
+
+/// Every spell in the game.
+const REGISTRY: [Spell; 4] = [
+    Spell {
+        name: "light",
+        description: "Conjures a light in your hand.",
+        level: 0,
+        cost: 0,
+        effect: SpellEffect::Light,
+    },
+    Spell {
+        name: "missile",
+        description: "Deals 2d4 damage to an enemy.",
+        level: 1,
+        cost: 2,
+        effect: SpellEffect::Damage { dice: 2, sides: 4 },
+    },
+    Spell {
+        name: "cure",
+        description: "Heals d8 hit points.",
+        level: 1,
+        cost: 3,
+        effect: SpellEffect::Heal { sides: 8 },
+    },
+    Spell {
+        name: "fireball",
+        description: "Deals 2d6 damage to every enemy in the room.",
+        level: 3,
+        cost: 6,
+        effect: SpellEffect::DamageAll { dice: 2, sides: 6 },
+    },
+];
+
+/// A function that finds a spell in the registry by name, ignoring case.
+///
+/// # Arguments
+/// * `name` - A string slice that is the name of the spell.
+///
+/// # Returns
+/// * `Option<&Spell>` - The spell, or None if there is no spell by that name.
+///
+/// # Examples
+/// ```
+/// use retribution::game::spell;
+///
+/// assert_eq!(spell::find("Fireball").map(|s| s.level), Some(3));
+/// assert!(spell::find("wish").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Spell> {
+    REGISTRY.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the default spell is in the registry, and how spells are shown.
+    #[test]
+    fn default_spell_test() {
+        let spell = find(DEFAULT_SPELL).unwrap();
+        assert_eq!(
+            spell.to_string(),
+            "light (level 0, 0 mana): Conjures a light in your hand."
+        );
+    }
+}
@@ -1,17 +1,78 @@
 //! # State
 //! This module contains the state of the game.
+use crate::game::ally;
+use crate::game::combat;
+use crate::game::dice;
+use crate::game::enemy;
+use crate::game::event::Event;
+use crate::game::item;
 use crate::game::map;
+use crate::game::player;
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Message shown in place of a room's description when it is too dark to see.
+pub const DARK_MESSAGE: &str = "It's pitch black.";
 
 /// A module that contains the state of the game.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct GameState {
     /// The current mode of the game.
     pub mode: Mode,
-    /// The current map the player is in.
+    /// The current map the player is in. Only the map's name and what is in its rooms are
+    /// serialized, as the database holds the map itself, so the map has to be reloaded with
+    /// `reload_map` after deserializing.
+    #[serde(skip)]
     pub map: Option<map::Map>,
-    /// The current room the player is in. row, col
-    pub room: Option<(i32, i32)>,
+    /// The name of the map to reload after deserializing.
+    #[serde(rename = "map_name", default)]
+    saved_map_name: Option<String>,
+    /// The items and enemies of the rooms of the map, as the hero left them, to put back once the
+    /// map is reloaded after deserializing.
+    #[serde(rename = "rooms", default)]
+    saved_rooms: Vec<RoomContents>,
+    /// The coordinates of the current room the player is in.
+    pub room: Option<map::Coord>,
+    /// The hero controlled by the player.
+    pub player: player::Player,
+    /// The items the hero is carrying.
+    pub inventory: Vec<item::Item>,
+    /// The allies travelling with the hero.
+    #[serde(default)]
+    pub allies: Vec<ally::Ally>,
+    /// The rooms the hero has been in, by map name and coordinates.
+    #[serde(default)]
+    pub visited: BTreeSet<(String, map::Coord)>,
+    /// The portals that collapsed after the hero went through them, by map name and coordinates.
+    #[serde(default)]
+    pub collapsed: BTreeSet<(String, map::Coord)>,
+    /// The number of commands interpreted outside the menu, used as the game's clock.
+    #[serde(default)]
+    pub turn: u64,
+    /// The bookkeeping of the fight the hero is in, or None outside of combat.
+    #[serde(default)]
+    pub combat: Option<combat::CombatState>,
+    /// The map name and coordinates of the room the hero last moved from, so they can go back.
+    /// It is cleared when the hero jumps, warps, or reloads the map, since there's no step back.
+    #[serde(skip)]
+    pub previous: Option<(String, map::Coord)>,
+    /// The item or creature the hero last named, which "it" refers to in the next command.
+    #[serde(skip)]
+    pub last_target: Option<String>,
+    /// The events of the latest command. These are sent to plugins next to the state.
+    #[serde(skip)]
+    pub events: Vec<Event>,
+    /// Whether the hero has asked to exit once and must confirm it.
+    #[serde(skip)]
+    pub pending_exit: bool,
+    /// Whether the hero has confirmed they want to exit, which ends the game loop.
+    #[serde(skip)]
+    pub exit_confirmed: bool,
+    /// The dice roller used to resolve moves.
+    #[serde(skip, default = "dice::default_roller")]
+    pub roller: Box<dyn dice::Roller>,
 }
 
 impl GameState {
@@ -30,15 +91,463 @@ impl GameState {
         GameState {
             mode: Mode::Travel,
             map: None,
+            saved_map_name: None,
+            saved_rooms: vec![],
             room: None,
+            player: player::Player::new(String::from("Hero")),
+            inventory: vec![],
+            allies: vec![],
+            visited: BTreeSet::new(),
+            collapsed: BTreeSet::new(),
+            turn: 0,
+            combat: None,
+            previous: None,
+            last_target: None,
+            events: vec![],
+            pending_exit: false,
+            exit_confirmed: false,
+            roller: dice::default_roller(),
+        }
+    }
+
+    /// The total weight of the items the hero is carrying, including their weapon.
+    ///
+    /// # Returns
+    /// * `u32` - The sum of the weights in the inventory and the wielded weapon.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    /// use retribution::game::state;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// game_state.inventory.push(item::Item::new(String::from("sword"), String::from("Sharp."), 2));
+    /// game_state.inventory.push(item::Item::new(String::from("shield"), String::from("Round."), 3));
+    /// assert_eq!(game_state.current_weight(), 5);
+    /// ```
+    pub fn current_weight(&self) -> u32 {
+        self.inventory
+            .iter()
+            .chain(self.player.weapon.iter())
+            .map(|i| i.total_weight())
+            .sum()
+    }
+
+    /// A function that gets the room the hero is standing in.
+    ///
+    /// # Returns
+    /// * `Option<&map::Room>` - The current room, or None when the hero isn't in a room.
+    pub fn current_room(&self) -> Option<&map::Room> {
+        let coord = self.room?;
+        match self.map.as_ref()?.get_grid_square(coord)? {
+            map::GridSquare::Room(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// A function that checks if the hero carries or wields an item.
+    ///
+    /// # Arguments
+    /// * `name` - A string slice that is the name of the item, ignoring case.
+    ///
+    /// # Returns
+    /// * `bool` - True if the hero holds the item.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    /// use retribution::game::state;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// assert!(!game_state.holds("brass key"));
+    /// game_state.inventory.push(item::Item::new(String::from("brass key"), String::from("Shiny."), 0));
+    /// assert!(game_state.holds("Brass Key"));
+    /// ```
+    pub fn holds(&self, name: &str) -> bool {
+        self.inventory
+            .iter()
+            .chain(self.player.weapon.iter())
+            .any(|i| i.name.eq_ignore_ascii_case(name))
+    }
+
+    /// A function that checks if the hero carries or wields something that gives off light.
+    ///
+    /// # Returns
+    /// * `bool` - True if the hero has a light.
+    pub fn has_light(&self) -> bool {
+        self.inventory
+            .iter()
+            .chain(self.player.weapon.iter())
+            .any(|i| i.is_lit())
+    }
+
+    /// A function that burns the hero's lit lights for a turn.
+    ///
+    /// # Returns
+    /// * `Vec<String>` - A message for each light that burned out.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::item;
+    /// use retribution::game::state;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// let mut torch = item::Item::torch(String::from("torch"), String::from("Pitch."), 1, 1);
+    /// torch.lit = true;
+    /// game_state.inventory.push(torch);
+    /// assert_eq!(game_state.burn_lights(), vec!["Your torch burns out."]);
+    /// assert!(!game_state.has_light());
+    /// ```
+    pub fn burn_lights(&mut self) -> Vec<String> {
+        self.inventory
+            .iter_mut()
+            .chain(self.player.weapon.iter_mut())
+            .filter_map(|i| i.burn().then(|| format!("Your {} burns out.", i.name)))
+            .collect()
+    }
+
+    /// A function that describes a room as the hero sees it. Dark rooms can't be seen without a
+    /// light.
+    ///
+    /// # Arguments
+    /// * `room` - A reference to the room to describe.
+    ///
+    /// # Returns
+    /// * `String` - The description of the room, or that it is too dark to see.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map;
+    /// use retribution::game::state;
+    ///
+    /// let game_state = state::GameState::new();
+    /// let mut room = map::Room::new(String::from("Cave"), String::from("A damp cave."));
+    /// assert_eq!(game_state.describe(&room), "A damp cave.");
+    /// room.dark = true;
+    /// assert_eq!(game_state.describe(&room), state::DARK_MESSAGE);
+    /// ```
+    pub fn describe(&self, room: &map::Room) -> String {
+        if room.dark && !self.has_light() {
+            return String::from(DARK_MESSAGE);
+        }
+        room.description.clone()
+    }
+
+    /// A function that tells the hero where they are: the name and description of the room, and
+    /// the ways out of it. A dark room only says that it is too dark to see.
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The lines describing the room, which are empty when the hero isn't in one.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::game::state;
+    /// use retribution::migration;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// game_state.map = Some(migration::map::test_area());
+    /// game_state.room = Some(Coord::new(0, 1));
+    /// assert_eq!(
+    ///     game_state.surroundings(),
+    ///     vec!["Room 4: This is room 4.", "Exits: south."]
+    /// );
+    /// ```
+    pub fn surroundings(&self) -> Vec<String> {
+        let (Some(current_map), Some(coord), Some(room)) =
+            (self.map.as_ref(), self.room, self.current_room())
+        else {
+            return vec![];
+        };
+        if room.dark && !self.has_light() {
+            return vec![String::from(DARK_MESSAGE)];
         }
+        let mut lines = vec![format!("{}: {}", room.name, room.description)];
+        let mut named: Vec<&String> = room.exits.keys().collect();
+        named.sort();
+        let exits: Vec<String> = current_map
+            .moves(coord)
+            .into_iter()
+            .map(|(d, _)| d.to_string())
+            .chain(named.into_iter().cloned())
+            .chain(room.portals.iter().map(|p| p.name.clone()))
+            .collect();
+        if !exits.is_empty() {
+            lines.push(format!("Exits: {}.", exits.join(", ")));
+        }
+        lines
+    }
+
+    /// A function that adds an ally to the hero's party.
+    ///
+    /// # Arguments
+    /// * `ally` - The ally joining the party.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::ally::Ally;
+    /// use retribution::game::state;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// game_state.recruit(Ally::new(String::from("squire"), String::from("A nervous squire."), 8));
+    /// assert!(game_state.ally_mut("Squire").is_some());
+    /// ```
+    pub fn recruit(&mut self, ally: ally::Ally) {
+        self.allies.push(ally);
+    }
+
+    /// A function that finds an ally in the hero's party by name, ignoring case.
+    ///
+    /// # Arguments
+    /// * `name` - A string slice that is the name of the ally.
+    ///
+    /// # Returns
+    /// * `Option<&mut ally::Ally>` - The ally, or None if no ally has that name.
+    pub fn ally_mut(&mut self, name: &str) -> Option<&mut ally::Ally> {
+        self.allies
+            .iter_mut()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+
+    /// A function that gets the name of the map the hero is in. After deserializing, this is the
+    /// name of the map waiting to be reloaded.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The name of the current map, or None when the hero isn't on a map.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::state;
+    /// use retribution::migration;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// assert_eq!(game_state.current_map_name(), None);
+    /// game_state.map = Some(migration::map::test_area());
+    /// assert_eq!(game_state.current_map_name(), Some("Test Area"));
+    /// ```
+    pub fn current_map_name(&self) -> Option<&str> {
+        match self.map.as_ref() {
+            Some(map) => Some(map.name.as_str()),
+            None => self.saved_map_name.as_deref(),
+        }
+    }
+
+    /// A function that loads the current map from the database by name, such as after the state
+    /// was deserialized. The items and enemies saved with the state are put back in their rooms,
+    /// as are the enemies of a fight the hero is in.
+    ///
+    /// # Arguments
+    /// * `path` - An optional path to the database, defaulting to the crate database path.
+    ///
+    /// # Returns
+    /// * `Result<(), map::MapError>` - A result that is Ok once the map is loaded, or an error.
+    pub fn reload_map(&mut self, path: Option<String>) -> Result<(), map::MapError> {
+        let name = match self.current_map_name() {
+            Some(name) => name.to_string(),
+            None => return Ok(()),
+        };
+        let mut map = self.load_map(name.as_str(), path)?;
+        // The map in the database is as it was made, so the rooms are put back the way the hero
+        // left them.
+        for contents in std::mem::take(&mut self.saved_rooms) {
+            if let Some(map::GridSquare::Room(room)) = map.get_grid_square_mut(contents.room) {
+                room.items = contents.items;
+                room.enemies = contents.enemies;
+            }
+        }
+        self.map = Some(map);
+        self.saved_map_name = None;
+        self.previous = None;
+        Ok(())
+    }
+
+    /// A function that loads a map from the database as the hero left it, without the portals
+    /// that have collapsed.
+    ///
+    /// # Arguments
+    /// * `name` - A string slice that is the name of the map to load.
+    /// * `path` - An optional path to the database, defaulting to the crate database path.
+    ///
+    /// # Returns
+    /// * `Result<map::Map, map::MapError>` - The map, or an error.
+    pub fn load_map(&self, name: &str, path: Option<String>) -> Result<map::Map, map::MapError> {
+        let mut map = map::load_map(name, path)?;
+        for (_, coord) in self.collapsed.iter().filter(|(n, _)| n == name) {
+            map.clear_grid_square(*coord);
+        }
+        Ok(map)
+    }
+
+    /// A function that starts a fight with the enemies in the hero's room, or brings the fight
+    /// up to date if one has started.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::enemy::Enemy;
+    /// use retribution::game::map::Coord;
+    /// use retribution::game::state::{GameState, Mode};
+    /// use retribution::migration;
+    ///
+    /// let mut game_state = GameState::new();
+    /// game_state.map = Some(migration::map::test_area());
+    /// game_state.room = Some(Coord::new(1, 1));
+    /// let goblin = Enemy::new(String::from("goblin"), String::from("A goblin."), 6);
+    /// game_state.current_room_mut().unwrap().enemies.push(goblin);
+    /// game_state.start_combat();
+    /// assert_eq!(game_state.mode, Mode::Combat);
+    /// assert_eq!(game_state.combat.unwrap().order, vec![0]);
+    /// ```
+    pub fn start_combat(&mut self) {
+        self.mode = Mode::Combat;
+        let enemies = self
+            .current_room()
+            .map(|r| r.enemies.clone())
+            .unwrap_or_default();
+        match self.combat.as_mut() {
+            Some(combat) => combat.update(&enemies),
+            None => self.combat = Some(combat::CombatState::new(&enemies)),
+        }
+    }
+
+    /// A function that ends the fight the hero is in, returning to travel.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::state::{GameState, Mode};
+    ///
+    /// let mut game_state = GameState::new();
+    /// game_state.start_combat();
+    /// game_state.end_combat();
+    /// assert_eq!(game_state.mode, Mode::Travel);
+    /// assert!(game_state.combat.is_none());
+    /// ```
+    pub fn end_combat(&mut self) {
+        self.mode = Mode::Travel;
+        self.combat = None;
+    }
+
+    /// A function that records the room the hero is standing in as visited.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::game::map::Coord;
+    /// use retribution::game::state;
+    /// use retribution::migration;
+    ///
+    /// let mut game_state = state::GameState::new();
+    /// game_state.map = Some(migration::map::test_area());
+    /// game_state.room = Some(Coord::new(1, 1));
+    /// game_state.mark_visited();
+    /// assert!(game_state.is_visited("Test Area", Coord::new(1, 1)));
+    /// assert!(!game_state.is_visited("Test Area", Coord::new(0, 1)));
+    /// ```
+    pub fn mark_visited(&mut self) {
+        if let (Some(map), Some(coord)) = (self.map.as_ref(), self.room) {
+            self.visited.insert((map.name.clone(), coord));
+        }
+    }
+
+    /// A function that records the hero arriving in their current room, marking it visited and
+    /// sending a PlayerMoved event.
+    pub fn arrive(&mut self) {
+        self.mark_visited();
+        if let (Some(map), Some(coord)) = (self.map.as_ref(), self.room) {
+            self.events.push(Event::PlayerMoved {
+                map: map.name.clone(),
+                to: coord,
+            });
+        }
+    }
+
+    /// A function that checks if the hero has been in a room.
+    ///
+    /// # Arguments
+    /// * `map_name` - A string slice that is the name of the map the room is in.
+    /// * `coord` - A Coord that is the coordinates of the room.
+    ///
+    /// # Returns
+    /// * `bool` - True if the hero has been in the room.
+    pub fn is_visited(&self, map_name: &str, coord: map::Coord) -> bool {
+        self.visited.contains(&(String::from(map_name), coord))
+    }
+
+    /// A function that gets the room the hero is standing in, for changing it.
+    ///
+    /// # Returns
+    /// * `Option<&mut map::Room>` - The current room, or None when the hero isn't in a room.
+    pub fn current_room_mut(&mut self) -> Option<&mut map::Room> {
+        let coord = self.room?;
+        match self.map.as_mut()?.get_grid_square_mut(coord)? {
+            map::GridSquare::Room(r) => Some(r),
+            _ => None,
+        }
+    }
+}
+
+/// A struct that holds what is in a room of the current map, so items taken from it don't come
+/// back when a saved game is loaded.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RoomContents {
+    /// The coordinates of the room.
+    pub room: map::Coord,
+    /// The items lying in the room.
+    pub items: Vec<item::Item>,
+    /// The enemies lurking in the room.
+    pub enemies: Vec<enemy::Enemy>,
+}
+
+/// Serializes the state with the current map written as only its name and what is in its rooms.
+/// The events, exit flags, and roller aren't serialized.
+impl Serialize for GameState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rooms: Vec<RoomContents> = match self.map.as_ref() {
+            Some(map) => map
+                .rooms()
+                .map(|(coord, room)| RoomContents {
+                    room: coord,
+                    items: room.items.clone(),
+                    enemies: room.enemies.clone(),
+                })
+                .collect(),
+            None => self.saved_rooms.clone(),
+        };
+        let mut state = serializer.serialize_struct("GameState", 11)?;
+        state.serialize_field("mode", &self.mode)?;
+        state.serialize_field("map_name", &self.current_map_name())?;
+        state.serialize_field("rooms", &rooms)?;
+        state.serialize_field("room", &self.room)?;
+        state.serialize_field("player", &self.player)?;
+        state.serialize_field("inventory", &self.inventory)?;
+        state.serialize_field("allies", &self.allies)?;
+        state.serialize_field("visited", &self.visited)?;
+        state.serialize_field("collapsed", &self.collapsed)?;
+        state.serialize_field("turn", &self.turn)?;
+        state.serialize_field("combat", &self.combat)?;
+        state.end()
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::new()
     }
 }
 
 /// An enum that represents the mode of the game.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Mode {
     Combat,
     Menu,
     Travel,
 }
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            Mode::Combat => "Combat",
+            Mode::Menu => "Menu",
+            Mode::Travel => "Travel",
+        };
+        write!(f, "{}", mode)
+    }
+}
@@ -1,4 +1,7 @@
+pub mod cli;
+pub mod db;
 pub mod game;
+pub mod logging;
 pub mod migration;
 pub mod plugin;
 pub mod ret_lang;
@@ -6,3 +9,5 @@ pub mod ret_lang;
 pub mod macros;
 
 const DB_PATH: &str = "~/retribution.db";
+/// The version of the game, taken from Cargo.toml when it is compiled.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,139 @@
+//! # Logging
+//! Traces what the game is doing, such as migration steps, state writes, and dice rolls, through
+//! the `log` facade. The messages are only kept when the crate is built with the `log` feature,
+//! and the level is read from `RETRIBUTION_LOG`, such as `RETRIBUTION_LOG=debug`. Without the
+//! feature the logging macros compile to nothing.
+
+/// The environment variable holding the lowest level of message to log.
+pub const LOG_ENV: &str = "RETRIBUTION_LOG";
+
+/// Logs a message at a level of the `log` facade, or does nothing without the `log` feature. The
+/// arguments are still checked, so a message can't go stale while the feature is off.
+macro_rules! log_at {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::log!(::log::Level::$level, $($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Logs a failure the game recovers from.
+macro_rules! error {
+    ($($arg:tt)+) => {
+        $crate::logging::log_at!(Error, $($arg)+)
+    };
+}
+
+/// Logs a step the game takes, such as running a migration.
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::logging::log_at!(Debug, $($arg)+)
+    };
+}
+
+/// Logs the finest details, such as every roll of the dice.
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::logging::log_at!(Trace, $($arg)+)
+    };
+}
+
+pub(crate) use {debug, error, log_at, trace};
+
+/// A logger that writes each message to stderr.
+#[cfg(feature = "log")]
+struct StderrLogger;
+
+#[cfg(feature = "log")]
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "[{}] {}: {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        #[cfg(test)]
+        if let Ok(mut captured) = tests::CAPTURED.lock() {
+            captured.push(line.clone());
+        }
+        eprintln!("{}", line);
+    }
+
+    fn flush(&self) {}
+}
+
+/// The logger the game installs.
+#[cfg(feature = "log")]
+static LOGGER: StderrLogger = StderrLogger;
+
+/// A function that starts logging at the level named by `RETRIBUTION_LOG`. Nothing is logged when
+/// the variable isn't set or doesn't name a level.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok once logging has started, or an error
+///   message if another logger was installed first.
+#[cfg(feature = "log")]
+pub fn init() -> Result<(), &'static str> {
+    let level = std::env::var(LOG_ENV)
+        .ok()
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(log::LevelFilter::Off);
+    init_with(level)
+}
+
+/// A function that starts logging at the given level, whatever `RETRIBUTION_LOG` is set to.
+///
+/// # Arguments
+/// * `level` - The lowest level of message to log.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok once logging has started, or an error
+///   message if another logger was installed first.
+#[cfg(feature = "log")]
+pub fn init_with(level: log::LevelFilter) -> Result<(), &'static str> {
+    log::set_logger(&LOGGER).map_err(|_| "A logger is already installed.")?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// A function that starts logging, which does nothing without the `log` feature.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is always Ok.
+#[cfg(not(feature = "log"))]
+pub fn init() -> Result<(), &'static str> {
+    Ok(())
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use std::sync::Mutex;
+
+    /// The lines logged while the tests run.
+    pub static CAPTURED: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    /// Test that logging at the debug level traces the migrations as they are applied.
+    #[test]
+    fn migration_debug_log_test() {
+        super::init_with(log::LevelFilter::Debug).unwrap();
+        let path = "migration_debug_log_test.db";
+        crate::migration::migrate_up(Some(String::from(path))).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let captured = CAPTURED.lock().unwrap();
+        assert!(captured.contains(&format!(
+            "[DEBUG] retribution::migration: Applied migration CreateMapMigration to {}.",
+            path
+        )));
+    }
+}
@@ -6,15 +6,15 @@ macro_rules! room {
     };
 }
 
-/// Macro for creating a grid square that is a portal, and takes in two string slices and a tuple
-/// of i32s
+/// Macro for creating a grid square that is a portal, and takes in two string slices and a
+/// (row, col) tuple of usizes
 #[macro_export]
 macro_rules! portal {
     ($name:expr, $target:expr, $location:expr) => {
         GridSquare::Portal(Portal::new(
             String::from($name),
             String::from($target),
-            $location,
+            Coord::from($location),
         ))
     };
 }
@@ -43,7 +43,7 @@ mod tests {
             GridSquare::Portal(Portal::new(
                 String::from("Test Portal"),
                 String::from("Test Area"),
-                (1, 1)
+                Coord::new(1, 1)
             )),
             portal
         );
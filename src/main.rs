@@ -1,43 +1,110 @@
+use retribution::cli;
 use retribution::game;
-use retribution::game::interpreter;
+use retribution::game::config;
+use retribution::game::dice;
 use retribution::game::map;
+use retribution::game::save;
 use retribution::game::state;
+use retribution::logging;
 use retribution::plugin;
-use retribution::ret_lang;
 use std::io;
+use std::io::IsTerminal;
+use std::sync::Arc;
 
 fn main() {
-    game::init().unwrap();
-    let test_map = map::load_map("Test Area", None).unwrap();
+    // Without the log feature there is nothing to start.
+    let _ = logging::init();
+    let args = match cli::Args::parse(std::env::args()) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if args.version {
+        println!("retribution {}", retribution::VERSION);
+        return;
+    }
+    let mut game_config = config::GameConfig::new();
+    game_config.db_path = args.db_path();
+    game_config.debug = args.debug;
+    game_config.color = args.color.resolve(io::stdout().is_terminal());
+    game_config.intro = true;
+    // Piped commands don't need to be prompted for.
+    game_config.quiet = !io::stdin().is_terminal();
+    game_config.difficulty = match args.difficulty() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    game_config.wal = match args.wal() {
+        Ok(wal) => wal,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    game::init_with(Some(game_config.db_path.clone()), game_config.wal).unwrap();
+    let test_map = map::load_map("Test Area", Some(game_config.db_path.clone())).unwrap();
+    let seed = match args.seed() {
+        Ok(seed) => seed.unwrap_or_else(dice::clock_seed),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
     let mut game_state = state::GameState::new();
+    game_state.roller = Box::new(dice::RandomRoller::seeded(seed));
     game_state.map = Some(test_map);
-    game_state.room = Some((1, 1));
+    game_state.room = Some(map::Coord::new(1, 1));
+    game_state.mark_visited();
+    game_state.mode = state::Mode::Menu;
     let mut reader = io::stdin();
-    let state_writer = plugin::StateWriter::new(None);
-
-    // Main game loop.
-    loop {
-        // We don't care if the state writer fails as the game will continue
-        // to function as normal.
-        let _ = state_writer.write_state(game_state.clone()).map_err(|_| ());
-        let input = match game::prompt(&mut reader) {
-            Ok(i) => i,
-            Err(e) => {
-                println!("{}", e);
-                continue;
+    let mut writer = io::stdout();
+    let state_writer = Arc::new(plugin::StateWriter::new(args.plugin_path()));
+    // Save the last state the plugins saw and clean up when the player presses Ctrl-C.
+    #[cfg(feature = "ctrlc")]
+    {
+        let state_writer = Arc::clone(&state_writer);
+        let config = game_config.clone();
+        let handler = ctrlc::set_handler(move || {
+            let latest = state_writer.latest();
+            let plugin_path = state_writer.output_file.as_str();
+            if let Err(e) = game::shutdown(latest.as_ref(), &config, plugin_path) {
+                eprintln!("{}", e);
             }
-        };
-        let command = match ret_lang::parse_input(&input[..]) {
-            Ok(c) => c,
-            _ => {
-                println!("{} is not a valid command.", input.trim());
-                continue;
-            }
-        };
-        let output = interpreter::interpreter(&command, &mut game_state);
-        match output {
-            Ok(o) => println!("{}", o),
-            Err(e) => println!("{}", e),
+            std::process::exit(130);
+        });
+        if handler.is_err() {
+            eprintln!("Unable to listen for Ctrl-C.");
         }
     }
+    // Like the prompt, the path is left out of piped output unless debugging.
+    if !game_config.quiet || game_config.debug {
+        println!(
+            "Game state for plugins is written to {}",
+            state_writer.output_file
+        );
+    }
+    println!("The dice are seeded with {}.", seed);
+    if game_config.autosave && save::load_game(save::AUTOSAVE_SLOT, &game_config.db_path).is_ok() {
+        println!(
+            "An autosave was found. Type 'load {}' to resume it.",
+            save::AUTOSAVE_SLOT
+        );
+    }
+    println!("Type 'play' to begin, or 'help' for the menu commands.");
+
+    if let Err(e) = game::run(
+        &mut reader,
+        &mut writer,
+        &mut game_state,
+        &game_config,
+        Some(state_writer.as_ref()),
+    ) {
+        eprintln!("{}", e);
+    }
+    let _ = game::tear_down(state_writer.output_file.as_str());
 }
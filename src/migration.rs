@@ -1,13 +1,156 @@
+use crate::db;
+use crate::logging;
+use rusqlite::Connection;
+
 const DB_PATH: &str = crate::DB_PATH;
 
+pub mod item;
 pub mod map;
+pub mod save;
 
 /// A struct that represents a map in the game world.
 pub trait Migration {
     /// Constructor for the struct.
     fn new(path: String) -> Self;
+    /// The unique name the migration is tracked under.
+    fn name(&self) -> &str;
+    /// The path to the database the migration runs against.
+    fn path(&self) -> &str;
     /// Run the migration.
     fn up(&self) -> Result<(), &'static str>;
     /// Rollback the migration.
     fn down(&self) -> Result<(), &'static str>;
+
+    /// Run the migration if it has not been applied yet, and record it as applied.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration is applied, or Err if not.
+    fn apply(&self) -> Result<(), &'static str> {
+        if is_applied(self.path(), self.name())? {
+            return Ok(());
+        }
+        self.up()?;
+        let db = open_tracking(self.path())?;
+        db::with_retry(|| {
+            db.execute(
+                "INSERT OR IGNORE INTO schema_migrations (name) VALUES (?1)",
+                [self.name()],
+            )
+        })
+        .map_err(|_| "Unable to record migration.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        logging::debug!("Applied migration {} to {}.", self.name(), self.path());
+        Ok(())
+    }
+
+    /// Roll back the migration if it has been applied, and remove its record.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration is rolled back, or Err if not.
+    fn rollback(&self) -> Result<(), &'static str> {
+        if !is_applied(self.path(), self.name())? {
+            return Ok(());
+        }
+        self.down()?;
+        let db = open_tracking(self.path())?;
+        db::with_retry(|| {
+            db.execute(
+                "DELETE FROM schema_migrations WHERE name = ?1",
+                [self.name()],
+            )
+        })
+        .map_err(|_| "Unable to remove migration record.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        logging::debug!(
+            "Rolled back migration {} from {}.",
+            self.name(),
+            self.path()
+        );
+        Ok(())
+    }
+}
+
+/// A function that opens the database and makes sure the schema_migrations table exists.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+///
+/// # Returns
+/// * `Result<Connection, &'static str>` - The open connection, or an error message.
+fn open_tracking(path: &str) -> Result<Connection, &'static str> {
+    let db = db::open(path).map_err(|_| "Unable to open database.")?;
+    db::with_retry(|| {
+        db.execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+            name TEXT PRIMARY KEY,
+            applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )",
+            [],
+        )
+    })
+    .map_err(|_| "Unable to create schema_migrations table.")?;
+    Ok(db)
+}
+
+/// A function that checks if a migration has been applied to a database.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+/// * `name` - A string slice that is the name of the migration.
+///
+/// # Returns
+/// * `Result<bool, &'static str>` - True if the migration has been applied, or an error message.
+pub fn is_applied(path: &str, name: &str) -> Result<bool, &'static str> {
+    let db = open_tracking(path)?;
+    let count: i64 = db::with_retry(|| {
+        db.query_row(
+            "SELECT COUNT(*) FROM schema_migrations WHERE name = ?1",
+            [name],
+            |row| row.get(0),
+        )
+    })
+    .map_err(|_| "Unable to query schema_migrations.")?;
+    db.close().map_err(|_| "Unable to close database.")?;
+    Ok(count > 0)
+}
+
+/// A function that handles migration errors.
+///
+/// # Arguments
+/// * `name` - A string that is the name of the migration.
+/// * `e` - A string that is the error message.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Err.
+fn handle_migration_error(name: String, e: &str) -> &'static str {
+    // Logging can be off, so a failed migration is always written to stderr too.
+    eprintln!("Migration Error ({}) {}", name, e);
+    logging::error!("Migration Error ({}) {}", name, e);
+    "Migration Error"
+}
+
+/// A function that runs every migration, creating all of the game's content.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok, or an error message.
+pub fn migrate_up(path: Option<String>) -> Result<(), &'static str> {
+    map::migrate_up(path.clone())?;
+    item::migrate_up(path.clone())?;
+    save::migrate_up(path)
+}
+
+/// A function that rolls back every migration, in the reverse order they were run.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok, or an error message.
+pub fn migrate_down(path: Option<String>) -> Result<(), &'static str> {
+    save::migrate_down(path.clone())?;
+    item::migrate_down(path.clone())?;
+    map::migrate_down(path)
 }
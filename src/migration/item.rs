@@ -0,0 +1,398 @@
+//! # Item Migration
+//!
+//! This module contains the migration for the item table in the database.
+
+use super::*;
+
+/// A struct that represents a migration to create the item table in the database.
+struct CreateItemMigration {
+    name: String,
+    path: String,
+}
+
+impl Migration for CreateItemMigration {
+    /// Constructor for the CreateItemMigration struct.
+    ///
+    /// # Arguments
+    /// * `path` - A string that is the path to the database.
+    ///
+    /// # Returns
+    /// * `CreateItemMigration` - A new CreateItemMigration.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        CreateItemMigration {
+            name: String::from("CreateItemMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Create the item table in the database. Each item sits in a room of a map.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the table was created, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "CREATE TABLE IF NOT EXISTS items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                map_name TEXT NOT NULL REFERENCES maps(name),
+                row INTEGER NOT NULL,
+                col INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT NOT NULL
+            )",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to create table.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Drop the item table in the database.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the table was dropped, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("DROP TABLE IF EXISTS items", []))
+            .map_err(|_| "Unable to drop table.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// A struct that represents a migration to add the weight column to the item table.
+struct AddItemWeightMigration {
+    name: String,
+    path: String,
+}
+
+impl Migration for AddItemWeightMigration {
+    /// Constructor for the AddItemWeightMigration struct.
+    ///
+    /// # Arguments
+    /// * `path` - A string that is the path to the database.
+    ///
+    /// # Returns
+    /// * `AddItemWeightMigration` - A new AddItemWeightMigration.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        AddItemWeightMigration {
+            name: String::from("AddItemWeightMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Add the weight column to the item table.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was added, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "ALTER TABLE items ADD COLUMN weight INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to add column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Drop the weight column from the item table.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was dropped, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("ALTER TABLE items DROP COLUMN weight", []))
+            .map_err(|_| "Unable to drop column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// A struct that represents a migration to add the damage column to the item table.
+struct AddItemDamageMigration {
+    name: String,
+    path: String,
+}
+
+impl Migration for AddItemDamageMigration {
+    /// Constructor for the AddItemDamageMigration struct.
+    ///
+    /// # Arguments
+    /// * `path` - A string that is the path to the database.
+    ///
+    /// # Returns
+    /// * `AddItemDamageMigration` - A new AddItemDamageMigration.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        AddItemDamageMigration {
+            name: String::from("AddItemDamageMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Add the damage column to the item table. Items that aren't weapons leave it null.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was added, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("ALTER TABLE items ADD COLUMN damage INTEGER", []))
+            .map_err(|_| "Unable to add column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Drop the damage column from the item table.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was dropped, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("ALTER TABLE items DROP COLUMN damage", []))
+            .map_err(|_| "Unable to drop column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// Struct for turning the seeded items in the test area map into weapons.
+pub struct TestAreaWeapons {
+    name: String,
+    path: String,
+}
+
+impl Migration for TestAreaWeapons {
+    /// Constructor for the TestAreaWeapons struct.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        TestAreaWeapons {
+            name: String::from("TestAreaWeapons"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Run the migration.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "UPDATE items SET damage = 8 WHERE map_name = 'Test Area' AND name = 'sword'",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to update item.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Rollback the migration.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "UPDATE items SET damage = NULL WHERE map_name = 'Test Area' AND name = 'sword'",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to update item.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// Struct for seeding the items in the test area map.
+pub struct TestAreaItems {
+    name: String,
+    path: String,
+}
+
+impl Migration for TestAreaItems {
+    /// Constructor for the TestAreaItems struct.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        TestAreaItems {
+            name: String::from("TestAreaItems"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Run the migration.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "INSERT INTO items (map_name, row, col, name, description, weight)
+                VALUES ('Test Area', 1, 1, 'sword', 'A short, sharp sword.', 2)",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to insert item.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Rollback the migration.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("DELETE FROM items WHERE map_name = ?1", ["Test Area"]))
+            .map_err(|_| "Unable to delete items.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// A function that runs the migration to create all item related content.
+/// Migrations that have already been applied are skipped.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok, or an error message.
+pub fn migrate_up(path: Option<String>) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    let migration = CreateItemMigration::new(path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddItemWeightMigration::new(migration.path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = TestAreaItems::new(migration.path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddItemDamageMigration::new(migration.path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = TestAreaWeapons::new(migration.path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    Ok(())
+}
+
+/// A function that rolls back the migration to create all item related content.
+/// Only migrations that have been applied are rolled back.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &str>` - A result that is Ok, or an error message.
+pub fn migrate_down(path: Option<String>) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    let migration = TestAreaWeapons::new(path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddItemDamageMigration::new(migration.path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = TestAreaItems::new(migration.path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddItemWeightMigration::new(migration.path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = CreateItemMigration::new(migration.path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the CreateItemMigration constructor.
+    #[test]
+    fn create_item_migration_new() {
+        let migration = CreateItemMigration::new(String::from(":memory:"));
+        assert_eq!(migration.name, "CreateItemMigration");
+        assert_eq!(migration.path, ":memory:");
+    }
+
+    /// Test that the seeded sword is in room 1 of the test area.
+    #[test]
+    fn test_area_items_test() {
+        let path = String::from("test_area_items_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let map = crate::game::map::load_map("Test Area", Some(path.clone()));
+        std::fs::remove_file(path).unwrap();
+        let map = map.unwrap();
+        let room = match map.get_grid_square(crate::game::map::Coord::new(1, 1)) {
+            Some(crate::game::map::GridSquare::Room(r)) => r,
+            _ => panic!("Room 1 expected."),
+        };
+        assert_eq!(room.items.len(), 1);
+        assert_eq!(room.items[0].name, "sword");
+        assert_eq!(room.items[0].weight, 2);
+        assert_eq!(room.items[0].damage, Some(8));
+    }
+}
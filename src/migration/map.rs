@@ -3,10 +3,8 @@
 //! This module contains the migration for the map table in the database.
 
 use super::*;
-use crate::game::map::{GridSquare, Map, Portal, Room};
-use crate::portal;
+use crate::game::map::{Coord, GridSquare, Map, Portal, Room};
 use crate::room;
-use rusqlite::Connection;
 use serde_json;
 
 /// A struct that represents a migration to create the map table in the database.
@@ -31,19 +29,29 @@ impl Migration for CreateMapMigration {
         }
     }
 
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
     /// Create the map table in the database.
     ///
     /// # Returns
     /// * `Result<(), &'static str>` - A result that is Ok if the table was created, or Err if not.
     fn up(&self) -> Result<(), &'static str> {
-        let db = Connection::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
-        db.execute(
-            "CREATE TABLE IF NOT EXISTS maps (
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "CREATE TABLE IF NOT EXISTS maps (
                 name TEXT PRIMARY KEY,
                 grid BLOB NOT NULL
             )",
-            [],
-        )
+                [],
+            )
+        })
         .map_err(|_| "Unable to create table.")?;
         db.close().map_err(|_| "Unable to close database.")?;
         Ok(())
@@ -54,35 +62,120 @@ impl Migration for CreateMapMigration {
     /// # Returns
     /// * `Result<(), &'static str>` - A result that is Ok if the table was dropped, or Err if not.
     fn down(&self) -> Result<(), &'static str> {
-        let db = Connection::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
-        db.execute("DROP TABLE IF EXISTS maps", [])
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("DROP TABLE IF EXISTS maps", []))
             .map_err(|_| "Unable to drop table.")?;
         db.close().map_err(|_| "Unable to close database.")?;
         Ok(())
     }
 }
 
-/// A function that creates a test area map.
+/// A struct that represents a migration adding the diagonal movement flag to the map table.
+struct AddMapDiagonalMigration {
+    name: String,
+    path: String,
+}
+
+impl Migration for AddMapDiagonalMigration {
+    /// Constructor for the AddMapDiagonalMigration struct.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        AddMapDiagonalMigration {
+            name: String::from("AddMapDiagonalMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Add the allow_diagonal column to the map table. Existing maps keep 4-way movement.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was added, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "ALTER TABLE maps ADD COLUMN allow_diagonal INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to add column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Drop the allow_diagonal column from the map table.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the column was dropped, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("ALTER TABLE maps DROP COLUMN allow_diagonal", []))
+            .map_err(|_| "Unable to drop column.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// A function that creates the test area maps, linked by a pair of portals. Room 2 also has a
+/// trapdoor down to test area 2.
 ///
 /// Room formation:
 /// ```text
-/// [  x  ] [ r 4 ] [  x  ]
-/// [ r 2 ] [ r 1 ] [ r 3 ]
+/// Test Area               Test Area 2
+/// [  x  ] [ r 4 ] [  x  ] [  p  ]
+/// [ r 2 ] [ r 1 ] [ r 3 ] [ r 1 ]
 /// [  x  ] [  p  ] [  x  ]
 /// ```
-pub fn test_area() -> Map {
+pub fn test_areas() -> (Map, Map) {
     let room1 = room!("Room 1", "This is room 1.");
-    let room2 = room!("Room 2", "This is room 2.");
+    let mut room2 = Room::new(String::from("Room 2"), String::from("This is room 2."));
+    room2.portals.push(Portal::new(
+        String::from("trapdoor"),
+        String::from("Test Area 2"),
+        Coord::new(1, 0),
+    ));
     let room3 = room!("Room 3", "This is room 3.");
     let room4 = room!("Room 4", "This is room 4.");
-    let portal = portal!("Test Area 2", "Test Area 2", (1, 0));
-    let mut map = Map::new(String::from("Test Area"), 3, 3);
-    map.set_grid_square(1, 1, room1).unwrap();
-    map.set_grid_square(1, 0, room2).unwrap();
-    map.set_grid_square(1, 2, room3).unwrap();
-    map.set_grid_square(0, 1, room4).unwrap();
-    map.set_grid_square(2, 1, portal).unwrap();
-    map
+    let mut map = Map::new(String::from("Test Area"), 3, 3).unwrap();
+    map.set_grid_square(Coord::new(1, 1), room1).unwrap();
+    map.set_grid_square(Coord::new(1, 0), GridSquare::Room(room2))
+        .unwrap();
+    map.set_grid_square(Coord::new(1, 2), room3).unwrap();
+    map.set_grid_square(Coord::new(0, 1), room4).unwrap();
+
+    let mut map_2 = Map::new(String::from("Test Area 2"), 2, 1).unwrap();
+    let room = room!("Room 1 - Test Area 2", "This is in test area 2.");
+    map_2.set_grid_square(Coord::new(1, 0), room).unwrap();
+
+    map.link_portals(
+        Coord::new(2, 1),
+        Coord::new(1, 1),
+        &mut map_2,
+        Coord::new(0, 0),
+        Coord::new(1, 0),
+    )
+    .unwrap();
+    (map, map_2)
+}
+
+/// A function that creates a test area map.
+///
+/// Room formation, with a trapdoor in room 2:
+/// ```text
+/// [  x  ] [ r 4 ] [  x  ]
+/// [ r 2 ] [ r 1 ] [ r 3 ]
+/// [  x  ] [  p  ] [  x  ]
+/// ```
+pub fn test_area() -> Map {
+    test_areas().0
 }
 
 /// A function that creates a test area 2 map.
@@ -93,12 +186,40 @@ pub fn test_area() -> Map {
 /// [ r 1 ]
 /// ```
 pub fn test_area_2() -> Map {
-    let mut map = Map::new(String::from("Test Area 2"), 2, 1);
-    let room = room!("Room 1 - Test Area 2", "This is in test area 2.");
-    let portal = portal!("Test Area", "Test Area", (1, 1));
-    map.set_grid_square(1, 0, room).unwrap();
-    map.set_grid_square(0, 0, portal).unwrap();
-    map
+    test_areas().1
+}
+
+/// A function that stores the test area maps. Ignoring keeps a map already stored by the same
+/// name, edits and all, while replacing overwrites its grid with the one built here. Replacing
+/// updates the stored row, so its other columns, such as `allow_diagonal`, are kept.
+///
+/// # Arguments
+/// * `path` - A string slice that is the path to the database.
+/// * `replace` - Whether to replace the maps already stored, rather than ignore them.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok if the maps were stored, or Err if not.
+fn store_test_areas(path: &str, replace: bool) -> Result<(), &'static str> {
+    let db = db::open(path).map_err(|_| "Unable to open database.")?;
+    let sql = if replace {
+        "INSERT INTO maps (name, grid) VALUES (?1, ?2)
+            ON CONFLICT(name) DO UPDATE SET grid = excluded.grid"
+    } else {
+        "INSERT OR IGNORE INTO maps (name, grid) VALUES (?1, ?2)"
+    };
+    let insert = |name: &str, map_json: String| -> Result<(), &'static str> {
+        db::with_retry(|| db.execute(sql, [name, &map_json]))
+            .map_err(|_| "Unable to insert map.")?;
+        Ok(())
+    };
+    let map_json =
+        serde_json::to_string(&test_area().grid).map_err(|_| "Unable to serialize map.")?;
+    let map_json_2 =
+        serde_json::to_string(&test_area_2().grid).map_err(|_| "Unable to serialize map.")?;
+    insert("Test Area", map_json)?;
+    insert("Test Area 2", map_json_2)?;
+    db.close().map_err(|_| "Unable to close database.")?;
+    Ok(())
 }
 
 /// Struct for creating a test area map.
@@ -117,28 +238,21 @@ impl Migration for TestArea {
         }
     }
 
-    /// Run the migration.
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Run the migration. Test areas already in the database are left as they are; use
+    /// `migrate_refresh` to replace them.
     ///
     /// # Returns
     /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
     fn up(&self) -> Result<(), &'static str> {
-        let db = Connection::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
-        let insert = |name: &str, map_json: String| -> Result<(), &'static str> {
-            db.execute(
-                "INSERT OR IGNORE INTO maps (name, grid) VALUES (?1, ?2)",
-                &[name, &map_json],
-            )
-            .map_err(|_| "Unable to insert map.")?;
-            Ok(())
-        };
-        let map_json =
-            serde_json::to_string(&test_area().grid).map_err(|_| "Unable to serialize map.")?;
-        let map_json_2 =
-            serde_json::to_string(&test_area_2().grid).map_err(|_| "Unable to serialize map.")?;
-        insert("Test Area", map_json)?;
-        insert("Test Area 2", map_json_2)?;
-        db.close().map_err(|_| "Unable to close database.")?;
-        Ok(())
+        store_test_areas(self.path.as_str(), false)
     }
 
     /// Rollback the migration.
@@ -146,31 +260,91 @@ impl Migration for TestArea {
     /// # Returns
     /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
     fn down(&self) -> Result<(), &'static str> {
-        let db = Connection::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
-        db.execute(
-            "DELETE FROM maps WHERE name = ?1 or name = ?2",
-            &["Test Area", "Test Area 2"],
-        )
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "DELETE FROM maps WHERE name = ?1 or name = ?2",
+                ["Test Area", "Test Area 2"],
+            )
+        })
         .map_err(|_| "Unable to delete map.")?;
         db.close().map_err(|_| "Unable to close database.")?;
         Ok(())
     }
 }
 
-/// A function that handles migration errors.
-///
-/// # Arguments
-/// * `name` - A string that is the name of the migration.
-/// * `e` - A string that is the error message.
-///
-/// # Returns
-/// * `Result<(), &'static str>` - A result that is Err.
-fn handle_migration_error(name: String, e: &str) -> &'static str {
-    eprintln!("Migration Error ({}) {}", name, e);
-    return "Migration Error";
+/// Struct for a migration adding the trapdoor to room 2 of an existing test area.
+struct AddTestAreaTrapdoorMigration {
+    name: String,
+    path: String,
+}
+
+impl AddTestAreaTrapdoorMigration {
+    /// Writes the grid of the test area over the one stored in the database.
+    ///
+    /// # Arguments
+    /// * `map` - A reference to the test area to store.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the map was stored, or Err if not.
+    fn store(&self, map: &Map) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        let map_json = serde_json::to_string(&map.grid).map_err(|_| "Unable to serialize map.")?;
+        db::with_retry(|| {
+            db.execute(
+                "UPDATE maps SET grid = ?1 WHERE name = ?2",
+                [map_json.as_str(), map.name.as_str()],
+            )
+        })
+        .map_err(|_| "Unable to update map.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+impl Migration for AddTestAreaTrapdoorMigration {
+    /// Constructor for the AddTestAreaTrapdoorMigration struct.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        AddTestAreaTrapdoorMigration {
+            name: String::from("AddTestAreaTrapdoorMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Store the test area with the trapdoor in room 2.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        self.store(&test_area())
+    }
+
+    /// Store the test area without the portals inside its rooms.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the migration was successful, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let mut map = test_area();
+        for square in map.grid.iter_mut().flatten() {
+            if let Some(GridSquare::Room(room)) = square {
+                room.portals.clear();
+            }
+        }
+        self.store(&map)
+    }
 }
 
 /// A function that runs the migration to create all map related content.
+/// Migrations that have already been applied are skipped.
 ///
 /// # Arguments
 /// * `path` - A string that is the path to the database.
@@ -181,16 +355,41 @@ pub fn migrate_up(path: Option<String>) -> Result<(), &'static str> {
     let path = path.unwrap_or_else(|| String::from(DB_PATH));
     let migration = CreateMapMigration::new(path);
     migration
-        .up()
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddMapDiagonalMigration::new(migration.path);
+    migration
+        .apply()
         .map_err(|e| handle_migration_error(migration.name, e))?;
     let migration = TestArea::new(migration.path);
     migration
-        .up()
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddTestAreaTrapdoorMigration::new(migration.path);
+    migration
+        .apply()
         .map_err(|e| handle_migration_error(migration.name, e))?;
     Ok(())
 }
 
+/// A function that runs the migrations to create all map related content, then replaces the test
+/// areas in the database with the ones built in code. Unlike `migrate_up`, which ignores maps
+/// already stored, this makes edits to the seeded maps take effect.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok, or an error message.
+pub fn migrate_refresh(path: Option<String>) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    migrate_up(Some(path.clone()))?;
+    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+    store_test_areas(path.as_str(), true)
+}
+
 /// A function that rolls back the migration to create all map related content.
+/// Only migrations that have been applied are rolled back.
 ///
 /// # Arguments
 /// * `path` - A string that is the path to the database.
@@ -199,17 +398,109 @@ pub fn migrate_up(path: Option<String>) -> Result<(), &'static str> {
 /// * `Result<(), &str>` - A result that is Ok, or an error message.
 pub fn migrate_down(path: Option<String>) -> Result<(), &'static str> {
     let path = path.unwrap_or_else(|| String::from(DB_PATH));
-    let migration = TestArea::new(path);
+    let migration = AddTestAreaTrapdoorMigration::new(path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = TestArea::new(migration.path);
     migration
-        .down()
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    let migration = AddMapDiagonalMigration::new(migration.path);
+    migration
+        .rollback()
         .map_err(|e| handle_migration_error(migration.name, e))?;
     let migration = CreateMapMigration::new(migration.path);
     migration
-        .down()
+        .rollback()
         .map_err(|e| handle_migration_error(migration.name, e))?;
     Ok(())
 }
 
+/// A function that renames a map in the database, along with the items placed in it, such as
+/// when an author clones a map to experiment with it. The portals of every map can be pointed at
+/// the new name too.
+///
+/// # Arguments
+/// * `old` - A string slice that is the name of the map to rename.
+/// * `new` - A string slice that is the new name, which no other map may have.
+/// * `path` - A string that is the path to the database.
+/// * `update_portals` - Whether the portals leading to the old name should lead to the new one.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok if the map was renamed, or an error message.
+pub fn rename_map(
+    old: &str,
+    new: &str,
+    path: Option<String>,
+    update_portals: bool,
+) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+    let db = db::open(path.as_str()).map_err(|_| "Unable to open database.")?;
+    let tx = db::transaction(&db).map_err(|_| "Unable to start transaction.")?;
+    let exists = |name: &str| -> Result<bool, &'static str> {
+        tx.query_row("SELECT COUNT(*) FROM maps WHERE name = ?1", [name], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map(|count| count > 0)
+        .map_err(|_| "Unable to query database.")
+    };
+    if !exists(old)? {
+        return Err("No map found.");
+    }
+    if exists(new)? {
+        return Err("A map by that name already exists.");
+    }
+    tx.execute("UPDATE maps SET name = ?1 WHERE name = ?2", [new, old])
+        .map_err(|_| "Unable to rename map.")?;
+    tx.execute(
+        "UPDATE items SET map_name = ?1 WHERE map_name = ?2",
+        [new, old],
+    )
+    .map_err(|_| "Unable to move items.")?;
+    if update_portals {
+        let grids: Vec<(String, String)> = {
+            let mut stmt = tx
+                .prepare("SELECT name, grid FROM maps")
+                .map_err(|_| "Unable to prepare statement.")?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|_| "Unable to query maps.")?;
+            rows.collect::<Result<_, _>>()
+                .map_err(|_| "Unable to get map.")?
+        };
+        for (name, grid_json) in grids {
+            let mut grid: Vec<Vec<Option<GridSquare>>> =
+                serde_json::from_str(grid_json.as_str())
+                    .map_err(|_| "Unable to deserialize grid.")?;
+            let mut changed = false;
+            for square in grid.iter_mut().flatten().flatten() {
+                let portals: Vec<&mut Portal> = match square {
+                    GridSquare::Portal(p) => vec![p],
+                    GridSquare::Room(r) => r.portals.iter_mut().collect(),
+                };
+                for portal in portals.into_iter().filter(|p| p.target == old) {
+                    portal.target = String::from(new);
+                    changed = true;
+                }
+            }
+            if changed {
+                let grid_json =
+                    serde_json::to_string(&grid).map_err(|_| "Unable to serialize map.")?;
+                tx.execute(
+                    "UPDATE maps SET grid = ?1 WHERE name = ?2",
+                    [grid_json.as_str(), name.as_str()],
+                )
+                .map_err(|_| "Unable to update map.")?;
+            }
+        }
+    }
+    tx.commit().map_err(|_| "Unable to commit transaction.")?;
+    db.close().map_err(|_| "Unable to close database.")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,6 +521,101 @@ mod tests {
         assert_eq!(migration.path, ":memory:");
     }
 
+    /// Test that running the migrations twice only applies them once.
+    #[test]
+    fn migrate_up_twice_test() {
+        let path = String::from("migrate_up_twice_test.db");
+        migrate_up(Some(path.clone())).unwrap();
+        migrate_up(Some(path.clone())).unwrap();
+        let count_applied = || -> i64 {
+            let db = Connection::open(path.as_str()).unwrap();
+            db.query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+        };
+        let applied = count_applied();
+        assert!(is_applied(path.as_str(), "CreateMapMigration").unwrap());
+        assert!(is_applied(path.as_str(), "TestArea").unwrap());
+        assert!(is_applied(path.as_str(), "AddMapDiagonalMigration").unwrap());
+        assert!(is_applied(path.as_str(), "AddTestAreaTrapdoorMigration").unwrap());
+        migrate_down(Some(path.clone())).unwrap();
+        let rolled_back = count_applied();
+        migrate_down(Some(path.clone())).unwrap();
+        std::fs::remove_file(path.as_str()).unwrap();
+        assert_eq!(applied, 4);
+        assert_eq!(rolled_back, 0);
+    }
+
+    /// Test that storing the test areas again ignores an edited map, and refreshing replaces its
+    /// grid while keeping its other columns.
+    #[test]
+    fn migrate_refresh_test() {
+        let path = String::from("migrate_refresh_test.db");
+        migrate_up(Some(path.clone())).unwrap();
+        let mut edited = test_area();
+        edited.grid[0][1] = None;
+        let edited_json = serde_json::to_string(&edited.grid).unwrap();
+        let stored_grid = || -> String {
+            let db = Connection::open(path.as_str()).unwrap();
+            db.query_row(
+                "SELECT grid FROM maps WHERE name = 'Test Area'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        let db = Connection::open(path.as_str()).unwrap();
+        db.execute(
+            "UPDATE maps SET grid = ?1, allow_diagonal = 1 WHERE name = 'Test Area'",
+            [edited_json.as_str()],
+        )
+        .unwrap();
+        db.close().unwrap();
+        store_test_areas(path.as_str(), false).unwrap();
+        let ignored = stored_grid();
+        migrate_refresh(Some(path.clone())).unwrap();
+        let refreshed = stored_grid();
+        let db = Connection::open(path.as_str()).unwrap();
+        let allow_diagonal: bool = db
+            .query_row(
+                "SELECT allow_diagonal FROM maps WHERE name = 'Test Area'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        db.close().unwrap();
+        std::fs::remove_file(path.as_str()).unwrap();
+        assert_eq!(ignored, edited_json);
+        assert_eq!(refreshed, serde_json::to_string(&test_area().grid).unwrap());
+        assert!(allow_diagonal);
+    }
+
+    /// Test renaming a map and its portals, then loading it under the new name.
+    #[test]
+    fn rename_map_test() {
+        let path = String::from("rename_map_test.db");
+        crate::migration::migrate_up(Some(path.clone())).unwrap();
+        let taken = rename_map("Test Area 2", "Test Area", Some(path.clone()), true);
+        let missing = rename_map("Nowhere", "Somewhere", Some(path.clone()), true);
+        let renamed = rename_map("Test Area 2", "Lower Area", Some(path.clone()), true);
+        let lower = crate::game::map::load_map("Lower Area", Some(path.clone()));
+        let old = crate::game::map::load_map("Test Area 2", Some(path.clone()));
+        let upper = crate::game::map::load_map("Test Area", Some(path.clone()));
+        std::fs::remove_file(path.as_str()).unwrap();
+        assert_eq!(taken, Err("A map by that name already exists."));
+        assert_eq!(missing, Err("No map found."));
+        assert_eq!(renamed, Ok(()));
+        assert_eq!(lower.unwrap().name, "Lower Area");
+        assert!(old.is_err());
+        let targets: Vec<String> = upper
+            .unwrap()
+            .portals()
+            .map(|(_, p)| p.target.clone())
+            .collect();
+        assert_eq!(targets, vec!["Lower Area", "Lower Area"]);
+    }
+
     /// Test handle_migration_error function.
     #[test]
     fn handle_migration_error_test() {
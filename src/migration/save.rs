@@ -0,0 +1,116 @@
+//! # Save Migration
+//!
+//! This module contains the migration for the save table in the database.
+
+use super::*;
+
+/// A struct that represents a migration to create the save table in the database.
+struct CreateSaveMigration {
+    name: String,
+    path: String,
+}
+
+impl Migration for CreateSaveMigration {
+    /// Constructor for the CreateSaveMigration struct.
+    ///
+    /// # Arguments
+    /// * `path` - A string that is the path to the database.
+    ///
+    /// # Returns
+    /// * `CreateSaveMigration` - A new CreateSaveMigration.
+    fn new(path: String) -> Self {
+        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
+        CreateSaveMigration {
+            name: String::from("CreateSaveMigration"),
+            path,
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// Create the save table in the database. Each save slot holds one serialized game state.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the table was created, or Err if not.
+    fn up(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| {
+            db.execute(
+                "CREATE TABLE IF NOT EXISTS saves (
+                slot TEXT PRIMARY KEY,
+                state TEXT NOT NULL,
+                saved_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+                [],
+            )
+        })
+        .map_err(|_| "Unable to create table.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+
+    /// Drop the save table in the database.
+    ///
+    /// # Returns
+    /// * `Result<(), &'static str>` - A result that is Ok if the table was dropped, or Err if not.
+    fn down(&self) -> Result<(), &'static str> {
+        let db = db::open(self.path.as_str()).map_err(|_| "Unable to open database.")?;
+        db::with_retry(|| db.execute("DROP TABLE IF EXISTS saves", []))
+            .map_err(|_| "Unable to drop table.")?;
+        db.close().map_err(|_| "Unable to close database.")?;
+        Ok(())
+    }
+}
+
+/// A function that runs the migration to create all save related content.
+/// Migrations that have already been applied are skipped.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &'static str>` - A result that is Ok, or an error message.
+pub fn migrate_up(path: Option<String>) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    let migration = CreateSaveMigration::new(path);
+    migration
+        .apply()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    Ok(())
+}
+
+/// A function that rolls back the migration to create all save related content.
+/// Only migrations that have been applied are rolled back.
+///
+/// # Arguments
+/// * `path` - A string that is the path to the database.
+///
+/// # Returns
+/// * `Result<(), &str>` - A result that is Ok, or an error message.
+pub fn migrate_down(path: Option<String>) -> Result<(), &'static str> {
+    let path = path.unwrap_or_else(|| String::from(DB_PATH));
+    let migration = CreateSaveMigration::new(path);
+    migration
+        .rollback()
+        .map_err(|e| handle_migration_error(migration.name, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the CreateSaveMigration constructor.
+    #[test]
+    fn create_save_migration_new() {
+        let migration = CreateSaveMigration::new(String::from(":memory:"));
+        assert_eq!(migration.name, "CreateSaveMigration");
+        assert_eq!(migration.path, ":memory:");
+    }
+}
@@ -1,22 +1,87 @@
 //! # Plugin
 //!
 //! Handles the plugin interface for the game.
-use std::thread;
-use serde::{Deserialize, Serialize};
+use crate::game::event::Event;
 use crate::game::state;
+use crate::logging;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+use std::thread;
 
-/// The version of the plugin.
-const VERSION: &str = "0.1.0";
+/// The version of the plugin, which is the version of the game.
+const VERSION: &str = crate::VERSION;
+/// The version of the serialized game state. Unlike `VERSION`, this is bumped every time the
+/// serialized shape of `GameState` changes, so plugins can tell when they need updating.
+pub const SCHEMA_VERSION: u32 = 26;
 /// The path to the plugin file.
 pub const PLUGIN_OUTPUT: &str = "~/ret-plugin.json";
 
-/// A struct that represents the output of the plugin.
+/// An enum that represents the errors that can happen when reading the plugin file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PluginError {
+    /// The plugin file couldn't be read.
+    Io(String),
+    /// The plugin file isn't valid plugin output.
+    Parse(String),
+    /// The plugin file was written with a different schema version than this game uses.
+    IncompatibleVersion { found: u32, expected: u32 },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PluginError::Io(e) => write!(f, "Unable to read plugin file: {}", e),
+            PluginError::Parse(e) => write!(f, "Unable to parse plugin file: {}", e),
+            PluginError::IncompatibleVersion { found, expected } => write!(
+                f,
+                "Plugin file has schema version {}, but version {} is expected.",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+/// A struct that holds only the versions of the plugin output, so they can be checked before the
+/// rest of the file is read.
+#[derive(Deserialize)]
+struct VersionHeader {
+    /// The version of the serialized game state.
+    schema_version: u32,
+}
+
+/// A struct that represents the output of the plugin. This is the shape of the plugin file, so
+/// plugins written in Rust can read it with `read_state`.
+///
+/// # Examples
+/// ```
+/// use retribution::game::state;
+/// use retribution::plugin::{self, PluginOutput};
+///
+/// let output = PluginOutput::new(state::GameState::new());
+/// assert_eq!(output.schema_version, plugin::SCHEMA_VERSION);
+///
+/// let path = String::from("plugin_output_doc_test.json");
+/// plugin::StateWriter::new(Some(path.clone()))
+///     .write_state(state::GameState::new())
+///     .unwrap();
+/// let output: PluginOutput = plugin::read_state(Some(path.clone())).unwrap();
+/// std::fs::remove_file(path).unwrap();
+/// assert_eq!(output.game_state.player.name, "Hero");
+/// assert!(output.events.is_empty());
+/// ```
 #[derive(Serialize, Deserialize)]
-struct PluginOutput {
+pub struct PluginOutput {
     /// The version of the plugin.
     pub version: String,
+    /// The version of the serialized game state.
+    pub schema_version: u32,
     /// The game state to write to the plugin file.
     pub game_state: state::GameState,
+    /// The events of the latest command.
+    pub events: Vec<Event>,
 }
 
 impl PluginOutput {
@@ -27,10 +92,13 @@ impl PluginOutput {
     ///
     /// # Returns
     /// * `PluginOutput` - A new PluginOutput.
-    pub fn new(game_state: state::GameState) -> PluginOutput {
+    pub fn new(mut game_state: state::GameState) -> PluginOutput {
+        let events = std::mem::take(&mut game_state.events);
         PluginOutput {
             version: VERSION.to_string(),
+            schema_version: SCHEMA_VERSION,
             game_state,
+            events,
         }
     }
 }
@@ -39,6 +107,8 @@ impl PluginOutput {
 pub struct StateWriter {
     /// The path to the plugin file.
     pub output_file: String,
+    /// The last state written, kept so the game can still be saved when it is interrupted.
+    latest: Mutex<Option<state::GameState>>,
 }
 
 impl StateWriter {
@@ -59,15 +129,30 @@ impl StateWriter {
     /// let state_writer = plugin::StateWriter::new(Some(path));
     /// ```
     pub fn new(path: Option<String>) -> StateWriter {
-        let path = match path {
-            Some(p) => p,
-            None => PLUGIN_OUTPUT.to_string(),
-        };
-        let path = path.replace("~", std::env::var("HOME").unwrap().as_str());
-        StateWriter { output_file: path }
+        StateWriter {
+            output_file: resolve_path(path),
+            latest: Mutex::new(None),
+        }
     }
 
-    /// Writes the state to the plugin file.
+    /// A function that gets the last state written to the plugin file.
+    ///
+    /// # Returns
+    /// * `Option<state::GameState>` - The last state written, or None if nothing has been written.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::plugin;
+    ///
+    /// let state_writer = plugin::StateWriter::new(Some(String::from("latest.json")));
+    /// assert!(state_writer.latest().is_none());
+    /// ```
+    pub fn latest(&self) -> Option<state::GameState> {
+        self.latest.lock().ok()?.clone()
+    }
+
+    /// Writes the state to the plugin file. The state is written to a temporary file first and
+    /// then renamed into place, so plugins never read a half written file.
     ///
     /// # Arguments
     /// * `state` - The state to write to the plugin file.
@@ -78,15 +163,68 @@ impl StateWriter {
         // spawn a thread to write the state to the plugin file.
         let output_file = self.output_file.clone();
         let state_clone = state.clone();
-        thread::spawn(move || {
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Some(state);
+        }
+        thread::spawn(move || -> Result<(), String> {
             let plugin_output = PluginOutput::new(state_clone);
-            let json = serde_json::to_string(&plugin_output).unwrap();
-            std::fs::write(output_file, json).unwrap();
-        }).join().map_err(|_| "Failed to write state to plugin file.".to_string())?;
-        Ok(())
+            let json = serde_json::to_string(&plugin_output).map_err(|e| e.to_string())?;
+            let temp_file = format!("{}.tmp", output_file);
+            std::fs::write(&temp_file, json).map_err(|e| e.to_string())?;
+            std::fs::rename(&temp_file, &output_file).map_err(|e| e.to_string())?;
+            logging::debug!("Wrote the game state to {}.", output_file);
+            Ok(())
+        })
+        .join()
+        .map_err(|_| "Failed to write state to plugin file.".to_string())?
     }
 }
 
+/// A function that picks the path to the plugin file, defaulting to `PLUGIN_OUTPUT`.
+///
+/// # Arguments
+/// * `path` - The path to the plugin file, if one was given.
+///
+/// # Returns
+/// * `String` - The path to the plugin file, with `~` expanded.
+fn resolve_path(path: Option<String>) -> String {
+    let path = path.unwrap_or_else(|| PLUGIN_OUTPUT.to_string());
+    path.replace("~", std::env::var("HOME").unwrap().as_str())
+}
+
+/// Reads the plugin file written by a `StateWriter`, so plugins written in Rust can use the same
+/// types as the game. The schema version is checked before the rest of the file is read.
+///
+/// # Arguments
+/// * `path` - The path to the plugin file, or None to use the default path.
+///
+/// # Returns
+/// * `Result<PluginOutput, PluginError>` - The output of the game, or an error.
+pub fn read_state(path: Option<String>) -> Result<PluginOutput, PluginError> {
+    let json =
+        std::fs::read_to_string(resolve_path(path)).map_err(|e| PluginError::Io(e.to_string()))?;
+    parse_state(json.as_str())
+}
+
+/// Parses plugin output, checking its schema version first.
+///
+/// # Arguments
+/// * `json` - A string slice that holds the contents of the plugin file.
+///
+/// # Returns
+/// * `Result<PluginOutput, PluginError>` - The output of the game, or an error.
+fn parse_state(json: &str) -> Result<PluginOutput, PluginError> {
+    let header: VersionHeader =
+        serde_json::from_str(json).map_err(|e| PluginError::Parse(e.to_string()))?;
+    if header.schema_version != SCHEMA_VERSION {
+        return Err(PluginError::IncompatibleVersion {
+            found: header.schema_version,
+            expected: SCHEMA_VERSION,
+        });
+    }
+    serde_json::from_str(json).map_err(|e| PluginError::Parse(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,11 +238,84 @@ mod tests {
         assert!(results.is_ok());
     }
 
+    /// Test that a plugin reading while the state is written always sees whole JSON.
+    #[test]
+    fn state_writer_atomic_write_test() {
+        let path = "state_writer_atomic_write_test.json";
+        let state_writer = StateWriter::new(Some(path.to_string()));
+        state_writer.write_state(state::GameState::new()).unwrap();
+        let done = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let reader_done = done.clone();
+        let reader = thread::spawn(move || {
+            let mut reads = 0;
+            while !reader_done.load(std::sync::atomic::Ordering::SeqCst) {
+                let json = std::fs::read_to_string(path).unwrap();
+                serde_json::from_str::<serde_json::Value>(json.as_str()).unwrap();
+                reads += 1;
+            }
+            reads
+        });
+        for _ in 0..200 {
+            state_writer.write_state(state::GameState::new()).unwrap();
+        }
+        done.store(true, std::sync::atomic::Ordering::SeqCst);
+        let reads = reader.join();
+        std::fs::remove_file(path).unwrap();
+        assert!(reads.is_ok());
+    }
+
+    /// Test that the state read back from the plugin file is the state that was written.
+    #[test]
+    fn read_state_round_trip_test() {
+        let path = String::from("read_state_round_trip_test.json");
+        let mut game_state = state::GameState::new();
+        game_state.map = Some(crate::migration::map::test_area());
+        game_state.room = Some(crate::game::map::Coord::new(1, 1));
+        game_state.arrive();
+        let expected = serde_json::to_value(PluginOutput::new(game_state.clone())).unwrap();
+        StateWriter::new(Some(path.clone()))
+            .write_state(game_state)
+            .unwrap();
+        let output = read_state(Some(path.clone()));
+        std::fs::remove_file(path.as_str()).unwrap();
+        assert_eq!(serde_json::to_value(output.unwrap()).unwrap(), expected);
+        assert!(matches!(read_state(Some(path)), Err(PluginError::Io(_))));
+    }
+
+    /// Test that output from another schema version is rejected before it is fully read.
+    #[test]
+    fn parse_state_incompatible_version_test() {
+        let json = r#"{"version": "9.9.9", "schema_version": 999, "game_state": "?"}"#;
+        let error = parse_state(json).err().unwrap();
+        assert_eq!(
+            error,
+            PluginError::IncompatibleVersion {
+                found: 999,
+                expected: SCHEMA_VERSION
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Plugin file has schema version 999, but version {} is expected.",
+                SCHEMA_VERSION
+            )
+        );
+        assert!(matches!(parse_state("{}"), Err(PluginError::Parse(_))));
+    }
+
     /// Test the plugin output constructor.
     #[test]
     fn plugin_output_test() {
         let game_state = state::GameState::new();
         let plugin_output = PluginOutput::new(game_state.clone());
-        assert_eq!(plugin_output.version, VERSION);
+        assert_eq!(plugin_output.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(plugin_output.schema_version, SCHEMA_VERSION);
+    }
+
+    /// Test the current schema version, which must change with the shape of the game state.
+    #[test]
+    fn schema_version_test() {
+        assert_eq!(SCHEMA_VERSION, 26);
     }
 }
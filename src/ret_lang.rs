@@ -4,32 +4,81 @@
 const AID: &str = "aid";
 const ASSIST: &str = "assist";
 const ATTACK: &str = "attack";
+const BACK: &str = "back";
 const CAST: &str = "cast";
 const CHARM: &str = "charm";
 const CONSULT: &str = "consult";
+const CONSUME: &str = "consume";
 const DEFEND: &str = "defend";
 const DEFY: &str = "defy";
 const DODGE: &str = "dodge";
+const DOUSE: &str = "douse";
 const DROP: &str = "drop";
+const DUMP: &str = "dump";
 const ENDURE: &str = "endure";
+const EQUIP: &str = "equip";
+const EXAMINE: &str = "examine";
 const EXIT: &str = "exit";
+const EXTINGUISH: &str = "extinguish";
 const FIGHT: &str = "fight";
+const FLEE: &str = "flee";
+const GIVE: &str = "give";
 const GO: &str = "go";
+const GOTO: &str = "goto";
 const HELP: &str = "help";
 const HIT: &str = "hit";
+const I: &str = "i";
 const INTERFERE: &str = "interfere";
 const IMPROVISE: &str = "improvise";
+const INVENTORY: &str = "inventory";
+const LEARN: &str = "learn";
+const LIGHT: &str = "light";
+const LOAD: &str = "load";
+const LOOK: &str = "look";
+const MAP: &str = "map";
+const MENU: &str = "menu";
 const PARLEY: &str = "parley";
+const PAUSE: &str = "pause";
+const PLAY: &str = "play";
 const PROTECT: &str = "protect";
+const Q: &str = "q";
+const QUIT: &str = "quit";
+const RELOAD: &str = "reload";
+const REST: &str = "rest";
+const RESUME: &str = "resume";
+const RUN: &str = "run";
+const SAVE: &str = "save";
 const SAY: &str = "say";
 const SEARCH: &str = "search";
 const SHOOT: &str = "shoot";
+const SPELLS: &str = "spells";
+const STATS: &str = "stats";
+const STATUS: &str = "status";
 const STUDY: &str = "study";
 const TAKE: &str = "take";
+const TO: &str = "to";
+const UNEQUIP: &str = "unequip";
+const USE: &str = "use";
+const VALIDATE: &str = "validate";
 const VOLLEY: &str = "volley";
+const WARP: &str = "warp";
+const WHERE: &str = "where";
+const X: &str = "x";
+
+pub mod alias;
+pub use alias::CommandKind;
 
 pub mod command;
 pub use command::*;
 
+pub mod direction;
+pub use direction::Direction;
+
 pub mod parser;
-pub use parser::parse_input;
+pub use parser::{
+    normalize, parse_input, parse_input_with, parse_inputs, parse_inputs_with, tokenize_spans,
+    ParseError, Token,
+};
+
+pub mod stat;
+pub use stat::Stat;
@@ -0,0 +1,186 @@
+//! # Alias
+//! The alias module maps every keyword the player can type to the kind of command it builds.
+
+use super::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// An enum that holds the kind of each command, without any of its arguments.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CommandKind {
+    Aid,
+    Back,
+    Cast,
+    Defend,
+    DefyDanger,
+    DiscernRealities,
+    Drop,
+    Dump,
+    Equip,
+    Examine,
+    Exit,
+    Extinguish,
+    Flee,
+    Give,
+    Go,
+    Goto,
+    HackAndSlash,
+    Help,
+    Interfere,
+    Inventory,
+    Learn,
+    Light,
+    Load,
+    Look,
+    Map,
+    Menu,
+    Parley,
+    Play,
+    Reload,
+    Rest,
+    Save,
+    Say,
+    Spells,
+    SpoutLore,
+    Status,
+    Take,
+    Unequip,
+    Use,
+    Validate,
+    Volley,
+    Warp,
+    Where,
+}
+
+/// A function that returns the table of every keyword and the command it builds.
+/// Adding an alias for a command is a single entry here.
+///
+/// # Returns
+/// * `&HashMap<&str, CommandKind>` - The alias table.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::alias::{alias_table, CommandKind};
+///
+/// assert_eq!(alias_table().get("hit"), Some(&CommandKind::HackAndSlash));
+/// ```
+pub fn alias_table() -> &'static HashMap<&'static str, CommandKind> {
+    static TABLE: OnceLock<HashMap<&'static str, CommandKind>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        HashMap::from([
+            (AID, CommandKind::Aid),
+            (ASSIST, CommandKind::Aid),
+            (ATTACK, CommandKind::HackAndSlash),
+            (BACK, CommandKind::Back),
+            (CAST, CommandKind::Cast),
+            (CHARM, CommandKind::DefyDanger),
+            (CONSULT, CommandKind::SpoutLore),
+            (CONSUME, CommandKind::Use),
+            (DEFEND, CommandKind::Defend),
+            (DEFY, CommandKind::DefyDanger),
+            (DODGE, CommandKind::DefyDanger),
+            (DOUSE, CommandKind::Extinguish),
+            (DROP, CommandKind::Drop),
+            (DUMP, CommandKind::Dump),
+            (ENDURE, CommandKind::DefyDanger),
+            (EQUIP, CommandKind::Equip),
+            (EXAMINE, CommandKind::Examine),
+            (EXIT, CommandKind::Exit),
+            (EXTINGUISH, CommandKind::Extinguish),
+            (FIGHT, CommandKind::HackAndSlash),
+            (FLEE, CommandKind::Flee),
+            (GIVE, CommandKind::Give),
+            (GO, CommandKind::Go),
+            (GOTO, CommandKind::Goto),
+            (HELP, CommandKind::Help),
+            (HIT, CommandKind::HackAndSlash),
+            (I, CommandKind::Inventory),
+            (IMPROVISE, CommandKind::DefyDanger),
+            (INTERFERE, CommandKind::Interfere),
+            (INVENTORY, CommandKind::Inventory),
+            (LEARN, CommandKind::Learn),
+            (LIGHT, CommandKind::Light),
+            (LOAD, CommandKind::Load),
+            (LOOK, CommandKind::Look),
+            (MAP, CommandKind::Map),
+            (MENU, CommandKind::Menu),
+            (PARLEY, CommandKind::Parley),
+            (PAUSE, CommandKind::Menu),
+            (PLAY, CommandKind::Play),
+            (PROTECT, CommandKind::Defend),
+            (Q, CommandKind::Exit),
+            (QUIT, CommandKind::Exit),
+            (RELOAD, CommandKind::Reload),
+            (REST, CommandKind::Rest),
+            (RESUME, CommandKind::Play),
+            (RUN, CommandKind::Flee),
+            (SAVE, CommandKind::Save),
+            (SAY, CommandKind::Say),
+            (SEARCH, CommandKind::DiscernRealities),
+            (SHOOT, CommandKind::Volley),
+            (SPELLS, CommandKind::Spells),
+            (STATS, CommandKind::Status),
+            (STATUS, CommandKind::Status),
+            (STUDY, CommandKind::DiscernRealities),
+            (TAKE, CommandKind::Take),
+            (UNEQUIP, CommandKind::Unequip),
+            (USE, CommandKind::Use),
+            (VALIDATE, CommandKind::Validate),
+            (VOLLEY, CommandKind::Volley),
+            (WARP, CommandKind::Warp),
+            (WHERE, CommandKind::Where),
+            (X, CommandKind::Examine),
+        ])
+    })
+}
+
+/// A function that finds every keyword for a kind of command, such as for the help system.
+///
+/// # Arguments
+/// * `kind` - The kind of command to find the keywords of.
+///
+/// # Returns
+/// * `Vec<&str>` - The keywords for the command, sorted alphabetically.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::alias::{aliases, CommandKind};
+///
+/// assert_eq!(aliases(CommandKind::Aid), vec!["aid", "assist"]);
+/// ```
+pub fn aliases(kind: CommandKind) -> Vec<&'static str> {
+    let mut keywords: Vec<&str> = alias_table()
+        .iter()
+        .filter(|(_, k)| **k == kind)
+        .map(|(keyword, _)| *keyword)
+        .collect();
+    keywords.sort();
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test a few of the aliases in the table.
+    #[test]
+    fn test_aliases() {
+        assert_eq!(
+            aliases(CommandKind::HackAndSlash),
+            vec!["attack", "fight", "hit"]
+        );
+        assert_eq!(
+            aliases(CommandKind::DefyDanger),
+            vec!["charm", "defy", "dodge", "endure", "improvise"]
+        );
+        assert_eq!(aliases(CommandKind::Examine), vec!["examine", "x"]);
+        assert_eq!(aliases(CommandKind::Exit), vec!["exit", "q", "quit"]);
+        assert_eq!(aliases(CommandKind::Go), vec!["go"]);
+        assert_eq!(aliases(CommandKind::Play), vec!["play", "resume"]);
+        assert_eq!(aliases(CommandKind::Use), vec!["consume", "use"]);
+        assert_eq!(
+            aliases(CommandKind::Extinguish),
+            vec!["douse", "extinguish"]
+        );
+    }
+}
@@ -10,7 +10,7 @@ macro_rules! create_command {
         $name:ident, $target:ty
     ) => {
         $(#[$doc])*
-        #[derive(Debug)]
+        #[derive(Clone, Debug)]
         pub struct $name {
             pub name: String,
             pub description: String,
@@ -19,6 +19,23 @@ macro_rules! create_command {
     }
 }
 
+/// A function that splits an optional count off the front of an item command, such as the 3 in
+/// `drop 3 arrow`. A lone number is treated as the name of the item.
+///
+/// # Arguments
+/// * `sentence` - A slice of the words of the command, starting with the keyword.
+///
+/// # Returns
+/// * `Result<(Option<u32>, &str), &str>` - The count, if one was given, and the name of the
+///   item, or an error when the count is zero.
+fn quantity<'a>(sentence: &[&'a str]) -> Result<(Option<u32>, &'a str), &'static str> {
+    match sentence[1].parse() {
+        Ok(0) if sentence.len() > 2 => Err("The count must be at least 1."),
+        Ok(count) if sentence.len() > 2 => Ok((Some(count), sentence[2])),
+        _ => Ok((None, sentence[1])),
+    }
+}
+
 create_command!(
     /// A struct that holds the name, description, and target of an AidCommand.
     ///
@@ -66,7 +83,7 @@ impl AidCommand {
 /// * `description` - A string that holds the description of the command.
 /// * `spell_name` - A string that holds the name of the spell to cast.
 /// * `target` - An optional string that holds the target of the command.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CastCommand {
     pub name: String,
     pub description: String,
@@ -90,9 +107,12 @@ impl CastCommand {
     /// assert_eq!(cast.description, "Cast a spell.");
     /// assert_eq!(cast.spell_name, "fireball");
     /// assert_eq!(cast.target, Some(String::from("goblin")));
+    ///
+    /// let cast = CastCommand::build(vec!["cast", "light"]).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(cast.target, None);
     /// ```
     pub fn build(sentence: Vec<&str>) -> Result<CastCommand, &str> {
-        if sentence.len() < 3 {
+        if sentence.len() < 2 {
             return Err("Not enough arguments for cast command.");
         }
         Ok(CastCommand {
@@ -152,13 +172,13 @@ impl DefendCommand {
 /// * `name` - A string that holds the name of the command.
 /// * `description` - A string that holds the description of the command.
 /// * `target` - An optional string that holds the target of the command.
-/// * `stat` - A string that holds the stat to use for the defy danger roll.
-#[derive(Debug)]
+/// * `stat` - A Stat that holds the stat to use for the defy danger roll.
+#[derive(Clone, Debug)]
 pub struct DefyDangerCommand {
     pub name: String,
     pub description: String,
     pub target: Option<String>,
-    pub stat: String,
+    pub stat: Stat,
 }
 
 impl DefyDangerCommand {
@@ -169,7 +189,7 @@ impl DefyDangerCommand {
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::DefyDangerCommand;
+    /// use retribution::ret_lang::{DefyDangerCommand, Stat};
     ///
     /// let sentence = vec!["defy", "wizard"];
     /// let defy = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -177,7 +197,7 @@ impl DefyDangerCommand {
     /// assert_eq!(defy.name, "defy");
     /// assert_eq!(defy.description, "Defy danger using a stat.");
     /// assert_eq!(defy.target, Some(String::from("wizard")));
-    /// assert_eq!(defy.stat, "wisdom");
+    /// assert_eq!(defy.stat, Stat::Wisdom);
     ///
     /// let sentence = vec!["dodge"];
     /// let dodge = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -185,7 +205,7 @@ impl DefyDangerCommand {
     /// assert_eq!(dodge.name, "dodge");
     /// assert_eq!(dodge.description, "Defy danger using a stat.");
     /// assert_eq!(dodge.target, None);
-    /// assert_eq!(dodge.stat, "dexterity");
+    /// assert_eq!(dodge.stat, Stat::Dexterity);
     ///
     /// let sentence = vec!["dodge", "goblin"];
     /// let dodge = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -193,7 +213,7 @@ impl DefyDangerCommand {
     /// assert_eq!(dodge.name, "dodge");
     /// assert_eq!(dodge.description, "Defy danger using a stat.");
     /// assert_eq!(dodge.target, Some(String::from("goblin")));
-    /// assert_eq!(dodge.stat, "dexterity");
+    /// assert_eq!(dodge.stat, Stat::Dexterity);
     ///
     /// let sentence = vec!["charm", "goblin"];
     /// let charm = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -201,7 +221,7 @@ impl DefyDangerCommand {
     /// assert_eq!(charm.name, "charm");
     /// assert_eq!(charm.description, "Defy danger using a stat.");
     /// assert_eq!(charm.target, Some(String::from("goblin")));
-    /// assert_eq!(charm.stat, "charisma");
+    /// assert_eq!(charm.stat, Stat::Charisma);
     ///
     /// let sentence = vec!["endure"];
     /// let endure = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -209,7 +229,7 @@ impl DefyDangerCommand {
     /// assert_eq!(endure.name, "endure");
     /// assert_eq!(endure.description, "Defy danger using a stat.");
     /// assert_eq!(endure.target, None);
-    /// assert_eq!(endure.stat, "constitution");
+    /// assert_eq!(endure.stat, Stat::Constitution);
     ///
     /// let sentence = vec!["improvise"];
     /// let improvise = DefyDangerCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
@@ -217,10 +237,10 @@ impl DefyDangerCommand {
     /// assert_eq!(improvise.name, "improvise");
     /// assert_eq!(improvise.description, "Defy danger using a stat.");
     /// assert_eq!(improvise.target, None);
-    /// assert_eq!(improvise.stat, "intelligence");
+    /// assert_eq!(improvise.stat, Stat::Intelligence);
     /// ```
     pub fn build(sentence: Vec<&str>) -> Result<DefyDangerCommand, &str> {
-        if sentence.len() < 1 {
+        if sentence.is_empty() {
             return Err("Not enough arguments for defy danger command.");
         }
         let name = sentence[0];
@@ -232,12 +252,12 @@ impl DefyDangerCommand {
                 _ => Some(String::from(sentence[1])),
             },
             stat: match name {
-                CHARM => String::from("charisma"),
-                DEFY => String::from("wisdom"),
-                DODGE => String::from("dexterity"),
-                ENDURE => String::from("constitution"),
-                IMPROVISE => String::from("intelligence"),
-                _ => String::from("dexterity"),
+                CHARM => Stat::Charisma,
+                DEFY => Stat::Wisdom,
+                DODGE => Stat::Dexterity,
+                ENDURE => Stat::Constitution,
+                IMPROVISE => Stat::Intelligence,
+                _ => Stat::Dexterity,
             },
         })
     }
@@ -277,7 +297,7 @@ impl DiscernRealitiesCommand {
     /// assert_eq!(search.target, Some(String::from("goblin")));
     /// ```
     pub fn build(sentence: Vec<&str>) -> Result<DiscernRealitiesCommand, &str> {
-        if sentence.len() < 1 {
+        if sentence.is_empty() {
             return Err("Not enough arguments for discern realities command.");
         }
         Ok(DiscernRealitiesCommand {
@@ -291,16 +311,21 @@ impl DiscernRealitiesCommand {
     }
 }
 
-create_command!(
-    /// A struct that holds the name, description, and target of a DropCommand.
-    ///
-    /// # Attributes
-    /// * `name` - A string that holds the name of the command.
-    /// * `description` - A string that holds the description of the command.
-    /// * `target` - A string that holds the target of the command.
-    DropCommand,
-    String
-);
+/// A struct that holds the name, description, count, and target of a DropCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+/// * `count` - How many of the item, such as the 5 in `drop 5 arrow`. None, or more than the
+///   hero has, drops the whole stack.
+/// * `target` - A string that holds the target of the command.
+#[derive(Clone, Debug)]
+pub struct DropCommand {
+    pub name: String,
+    pub description: String,
+    pub count: Option<u32>,
+    pub target: String,
+}
 
 impl DropCommand {
     /// Construct new DropCommand.
@@ -317,15 +342,101 @@ impl DropCommand {
     /// assert_eq!(drop.name, "drop");
     /// assert_eq!(drop.description, "Drops an item from the player's inventory.");
     /// assert_eq!(drop.target, "sword");
+    /// assert_eq!(drop.count, None);
+    ///
+    /// let sentence = vec!["drop", "5", "arrow"];
+    /// let drop = DropCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(drop.count, Some(5));
+    /// assert_eq!(drop.target, "arrow");
     /// ```
     pub fn build(sentence: Vec<&str>) -> Result<DropCommand, &str> {
         if sentence.len() < 2 {
             return Err("Not enough arguments for drop command.");
         }
+        let (count, target) = quantity(&sentence)?;
         Ok(DropCommand {
             name: String::from(DROP),
             description: String::from("Drops an item from the player's inventory."),
-            target: String::from(sentence[1]),
+            count,
+            target: String::from(target),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of an EquipCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the target of the command.
+    EquipCommand,
+    String
+);
+
+impl EquipCommand {
+    /// Construct new EquipCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::EquipCommand;
+    ///
+    /// let sentence = vec!["equip", "sword"];
+    /// let equip = EquipCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(equip.name, "equip");
+    /// assert_eq!(equip.description, "Wields a weapon from the player's inventory.");
+    /// assert_eq!(equip.target, "sword");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<EquipCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for equip command.");
+        }
+        Ok(EquipCommand {
+            name: String::from(EQUIP),
+            description: String::from("Wields a weapon from the player's inventory."),
+            target: sentence[1..].join(" "),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of an ExamineCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the target of the command.
+    ExamineCommand,
+    String
+);
+
+impl ExamineCommand {
+    /// Construct new ExamineCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::ExamineCommand;
+    ///
+    /// let sentence = vec!["x", "rusty", "sword"];
+    /// let examine = ExamineCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(examine.name, "examine");
+    /// assert_eq!(examine.description, "Examines an item or creature.");
+    /// assert_eq!(examine.target, "rusty sword");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<ExamineCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for examine command.");
+        }
+        Ok(ExamineCommand {
+            name: String::from(EXAMINE),
+            description: String::from("Examines an item or creature."),
+            target: sentence[1..].join(" "),
         })
     }
 }
@@ -335,6 +446,7 @@ impl DropCommand {
 /// # Attributes
 /// * `name` - A string that holds the name of the command.
 /// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
 pub struct ExitCommand {
     pub name: String,
     pub description: String,
@@ -359,13 +471,145 @@ impl ExitCommand {
     }
 }
 
+create_command!(
+    /// A struct that holds the name, description, and target of an ExtinguishCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the name of the light to put out.
+    ExtinguishCommand,
+    String
+);
+
+impl ExtinguishCommand {
+    /// Construct new ExtinguishCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::ExtinguishCommand;
+    ///
+    /// let sentence = vec!["douse", "torch"];
+    /// let extinguish = ExtinguishCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(extinguish.name, "extinguish");
+    /// assert_eq!(extinguish.description, "Put out a light.");
+    /// assert_eq!(extinguish.target, "torch");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<ExtinguishCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for extinguish command.");
+        }
+        Ok(ExtinguishCommand {
+            name: String::from(EXTINGUISH),
+            description: String::from("Put out a light."),
+            target: String::from(sentence[1]),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a FleeCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct FleeCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl FleeCommand {
+    /// Construct new FleeCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::FleeCommand;
+    ///
+    /// let flee = FleeCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(flee.name, "flee");
+    /// assert_eq!(flee.description, "Try to escape a fight.");
+    /// ```
+    pub fn build<'a>() -> Result<FleeCommand, &'a str> {
+        Ok(FleeCommand {
+            name: String::from(FLEE),
+            description: String::from("Try to escape a fight."),
+        })
+    }
+}
+
+/// A struct that holds the name, description, item, and target of a GiveCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+/// * `item` - A string that holds the name of the item to give.
+/// * `target` - A string that holds the name of who receives the item.
+#[derive(Clone, Debug)]
+pub struct GiveCommand {
+    pub name: String,
+    pub description: String,
+    pub item: String,
+    pub target: String,
+}
+
+impl GiveCommand {
+    /// Construct new GiveCommand. The item comes first, and the target is every word after it.
+    /// With a "to" between them, both can be more than one word. Without one, the item is the
+    /// first word.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::GiveCommand;
+    ///
+    /// let give = GiveCommand::build(vec!["give", "potion", "squire"]).unwrap();
+    /// assert_eq!(give.name, "give");
+    /// assert_eq!(give.description, "Give an item to someone.");
+    /// assert_eq!(give.item, "potion");
+    /// assert_eq!(give.target, "squire");
+    ///
+    /// let give = GiveCommand::build(vec!["give", "potion", "to", "old", "man"]).unwrap();
+    /// assert_eq!(give.target, "old man");
+    ///
+    /// let give = GiveCommand::build(vec!["give", "healing", "potion", "to", "squire"]).unwrap();
+    /// assert_eq!(give.item, "healing potion");
+    /// assert_eq!(give.target, "squire");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<GiveCommand, &str> {
+        let to = sentence
+            .iter()
+            .skip(2)
+            .position(|word| word.eq_ignore_ascii_case(TO))
+            .map(|i| i + 2);
+        let (item, target) = match to {
+            Some(to) => (&sentence[1..to], &sentence[to + 1..]),
+            None if sentence.len() > 2 => (&sentence[1..2], &sentence[2..]),
+            None => (&[][..], &[][..]),
+        };
+        if target.is_empty() {
+            return Err("Not enough arguments for give command.");
+        }
+        Ok(GiveCommand {
+            name: String::from(sentence[0]),
+            description: String::from("Give an item to someone."),
+            item: item.join(" "),
+            target: target.join(" "),
+        })
+    }
+}
+
 create_command!(
     /// A struct that holds the name, description, and target of a GoCommand.
     ///
     /// # Attributes
     /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
-    /// * `target` - A string that holds the target of the command.
+    /// * `target` - A string that holds the named exit or direction to move in.
     GoCommand,
     String
 );
@@ -378,22 +622,77 @@ impl GoCommand {
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::GoCommand;
+    /// use retribution::ret_lang::{Direction, GoCommand};
     ///
     /// let sentence = vec!["go", "north"];
     /// let go = GoCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
     /// assert_eq!(go.name, "go");
     /// assert_eq!(go.description, "Moves the player to a new location.");
     /// assert_eq!(go.target, "north");
+    /// assert_eq!(go.direction(), Some(Direction::North));
     /// ```
-    pub fn build(sentence: Vec<&str>) -> Result<GoCommand, &str> {
+    pub fn build(sentence: Vec<&str>) -> Result<GoCommand, String> {
         if sentence.len() < 2 {
-            return Err("Not enough arguments for go command.");
+            return Err(String::from("Not enough arguments for go command."));
         }
         Ok(GoCommand {
             name: String::from(GO),
             description: String::from("Moves the player to a new location."),
-            target: String::from(sentence[1]),
+            target: sentence[1..].join(" "),
+        })
+    }
+
+    /// The compass direction to move in, if the target is one.
+    ///
+    /// # Returns
+    /// * `Option<Direction>` - The direction, or None if the target isn't a direction.
+    pub fn direction(&self) -> Option<Direction> {
+        self.target.parse().ok()
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a GotoCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A tuple that holds the row and column to jump to.
+    GotoCommand,
+    (usize, usize)
+);
+
+impl GotoCommand {
+    /// Construct new GotoCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::GotoCommand;
+    ///
+    /// let sentence = vec!["goto", "0", "1"];
+    /// let goto = GotoCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(goto.name, "goto");
+    /// assert_eq!(goto.description, "Jumps to a room of the current map. Debug only.");
+    /// assert_eq!(goto.target, (0, 1));
+    /// assert!(GotoCommand::build(vec!["goto", "north", "1"]).is_err());
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<GotoCommand, &str> {
+        if sentence.len() < 3 {
+            return Err("Not enough arguments for goto command.");
+        }
+        let row = sentence[1]
+            .parse()
+            .map_err(|_| "The row and column for goto must be numbers.")?;
+        let col = sentence[2]
+            .parse()
+            .map_err(|_| "The row and column for goto must be numbers.")?;
+        Ok(GotoCommand {
+            name: String::from(GOTO),
+            description: String::from("Jumps to a room of the current map. Debug only."),
+            target: (row, col),
         })
     }
 }
@@ -442,7 +741,7 @@ impl HackAndSlashCommand {
 
 create_command!(
     /// A struct that holds the name, description, and target of a HelpCommand.
-    ///     
+    ///
     /// # Attributes
     /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
@@ -475,7 +774,7 @@ impl HelpCommand {
     /// let help = HelpCommand::build(sentence);
     /// ```
     pub fn build(sentence: Vec<&str>) -> Result<HelpCommand, &str> {
-        if sentence.len() < 1 {
+        if sentence.is_empty() {
             return Err("Not enough arguments for help command.");
         }
         Ok(HelpCommand {
@@ -529,169 +828,666 @@ impl InterfereCommand {
 }
 
 create_command!(
-    /// A struct that holds the name, description, and target of a ParleyCommand.
+    /// A struct that holds the name, description, and target of a LearnCommand.
     ///
     /// # Attributes
     /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
-    /// * `target` - A string that holds the target of the command.
-    ParleyCommand,
+    /// * `target` - A string that holds the name of the spell to learn.
+    LearnCommand,
     String
 );
 
-impl ParleyCommand {
-    /// Construct new ParleyCommand.
+impl LearnCommand {
+    /// Construct new LearnCommand.
     ///
     /// # Arguments
     /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::ParleyCommand;
+    /// use retribution::ret_lang::LearnCommand;
     ///
-    /// let sentence = vec!["parley", "goblin"];
-    /// let parley = ParleyCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
-    /// assert_eq!(parley.name, "parley");
-    /// assert_eq!(parley.description, "Parley with an enemy.");
-    /// assert_eq!(parley.target, "goblin");
+    /// let sentence = vec!["learn", "fireball"];
+    /// let learn = LearnCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(learn.name, "learn");
+    /// assert_eq!(learn.description, "Learn a spell from a spellbook.");
+    /// assert_eq!(learn.target, "fireball");
     /// ```
-    pub fn build(sentence: Vec<&str>) -> Result<ParleyCommand, &str> {
+    pub fn build(sentence: Vec<&str>) -> Result<LearnCommand, &str> {
         if sentence.len() < 2 {
-            return Err("Not enough arguments for parley command.");
+            return Err("Not enough arguments for learn command.");
         }
-        Ok(ParleyCommand {
-            name: String::from(PARLEY),
-            description: String::from("Parley with an enemy."),
+        Ok(LearnCommand {
+            name: String::from(LEARN),
+            description: String::from("Learn a spell from a spellbook."),
             target: String::from(sentence[1]),
         })
     }
 }
 
 create_command!(
-    /// A struct that holds the name, description, and target of a SayCommand.
+    /// A struct that holds the name, description, and target of a LightCommand.
     ///
     /// # Attributes
     /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
-    /// * `target` - A string that holds the value of the command.
-    SayCommand,
+    /// * `target` - A string that holds the name of the light to light.
+    LightCommand,
     String
 );
 
-impl SayCommand {
-    /// Construct new SayCommand.
+impl LightCommand {
+    /// Construct new LightCommand.
     ///
     /// # Arguments
     /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::SayCommand;
+    /// use retribution::ret_lang::LightCommand;
     ///
-    /// let sentence = vec!["say", "hello", "world"];
-    /// let say = SayCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
-    /// assert_eq!(say.name, "say");
-    /// assert_eq!(say.description, "Prints a message to the screen.");
-    /// assert_eq!(say.target, "hello world");
+    /// let sentence = vec!["light", "torch"];
+    /// let light = LightCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(light.name, "light");
+    /// assert_eq!(light.description, "Light a torch or lantern.");
+    /// assert_eq!(light.target, "torch");
     /// ```
-    pub fn build(sentence: Vec<&str>) -> Result<SayCommand, &str> {
+    pub fn build(sentence: Vec<&str>) -> Result<LightCommand, &str> {
         if sentence.len() < 2 {
-            return Err("Not enough arguments for say command.");
+            return Err("Not enough arguments for light command.");
         }
-        Ok(SayCommand {
-            name: String::from(SAY),
-            description: String::from("Prints a message to the screen."),
-            target: sentence[1..].join(" "),
+        Ok(LightCommand {
+            name: String::from(LIGHT),
+            description: String::from("Light a torch or lantern."),
+            target: String::from(sentence[1]),
         })
     }
 }
 
 create_command!(
-    /// A struct that holds the name, description, and target of a SpoutLoreCommand.
+    /// A struct that holds the name, description, and target of a LoadCommand.
     ///
     /// # Attributes
     /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
-    /// * `target` - An optional string that holds the target of the command.
-    SpoutLoreCommand,
+    /// * `target` - An optional string that holds the save slot of the command.
+    LoadCommand,
     Option<String>
 );
 
-impl SpoutLoreCommand {
-    /// Construct new SpoutLoreCommand.
+impl LoadCommand {
+    /// Construct new LoadCommand.
     ///
     /// # Arguments
     /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::SpoutLoreCommand;
+    /// use retribution::ret_lang::LoadCommand;
     ///
-    /// let sentence = vec!["consult", "wizard"];
-    /// let spout = SpoutLoreCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
-    /// assert_eq!(spout.name, "consult");
-    /// assert_eq!(spout.description, "Spout lore about a subject.");
-    /// assert_eq!(spout.target, Some(String::from("wizard")));
+    /// let sentence = vec!["load", "slot1"];
+    /// let load = LoadCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(load.name, "load");
+    /// assert_eq!(load.description, "Loads a saved game.");
+    /// assert_eq!(load.target, Some(String::from("slot1")));
     /// ```
-    pub fn build(sentence: Vec<&str>) -> Result<SpoutLoreCommand, &str> {
-        if sentence.len() < 1 {
-            return Err("Not enough arguments for spout lore command.");
+    pub fn build(sentence: Vec<&str>) -> Result<LoadCommand, &str> {
+        if sentence.is_empty() {
+            return Err("Not enough arguments for load command.");
         }
-        Ok(SpoutLoreCommand {
-            name: String::from(sentence[0]),
-            description: String::from("Spout lore about a subject."),
+        Ok(LoadCommand {
+            name: String::from(LOAD),
+            description: String::from("Loads a saved game."),
             target: match sentence.len() {
-                0..=1 => None,
+                1 => None,
                 _ => Some(String::from(sentence[1])),
             },
         })
     }
 }
 
-create_command!(
-    /// A struct that holds the name, description, and target of a TakeCommand.
-    ///
-    /// # Attributes
-    /// * `name` - A string that holds the name of the command.
-    /// * `description` - A string that holds the description of the command.
-    /// * `target` - A string that holds the target of the command.
-    TakeCommand,
-    String
-);
+/// A struct that holds the name and description of an InventoryCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct InventoryCommand {
+    pub name: String,
+    pub description: String,
+}
 
-impl TakeCommand {
-    /// Construct new TakeCommand.
-    ///
-    /// # Arguments
-    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+impl InventoryCommand {
+    /// Construct new InventoryCommand.
     ///
     /// # Examples
     /// ```
-    /// use retribution::ret_lang::TakeCommand;
+    /// use retribution::ret_lang::InventoryCommand;
     ///
-    /// let sentence = vec!["take", "sword"];
-    /// let take = TakeCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
-    /// assert_eq!(take.name, "take");
-    /// assert_eq!(take.description, "Takes an item from the current location.");
-    /// assert_eq!(take.target, "sword");
+    /// let inventory = InventoryCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(inventory.name, "inventory");
+    /// assert_eq!(inventory.description, "Lists the items the player is carrying.");
     /// ```
-    pub fn build(sentence: Vec<&str>) -> Result<TakeCommand, &str> {
-        if sentence.len() < 2 {
-            return Err("Not enough arguments for take command.");
-        }
-        Ok(TakeCommand {
-            name: String::from(TAKE),
-            description: String::from("Takes an item from the current location."),
-            target: String::from(sentence[1]),
+    pub fn build<'a>() -> Result<InventoryCommand, &'a str> {
+        Ok(InventoryCommand {
+            name: String::from(INVENTORY),
+            description: String::from("Lists the items the player is carrying."),
         })
     }
 }
 
-create_command!(
-    /// A struct that holds the name, description, and target of a VolleyCommand.
-    ///
-    /// # Attributes
-    /// * `name` - A string that holds the name of the command.
+/// A struct that holds the name and description of a LookCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct LookCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl LookCommand {
+    /// Construct new LookCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::LookCommand;
+    ///
+    /// let look = LookCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(look.name, "look");
+    /// assert_eq!(look.description, "Describes the room the player is in.");
+    /// ```
+    pub fn build<'a>() -> Result<LookCommand, &'a str> {
+        Ok(LookCommand {
+            name: String::from(LOOK),
+            description: String::from("Describes the room the player is in."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a MapCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct MapCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl MapCommand {
+    /// Construct new MapCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::MapCommand;
+    ///
+    /// let map = MapCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(map.name, "map");
+    /// assert_eq!(map.description, "Draws the parts of the map the player has explored.");
+    /// ```
+    pub fn build<'a>() -> Result<MapCommand, &'a str> {
+        Ok(MapCommand {
+            name: String::from(MAP),
+            description: String::from("Draws the parts of the map the player has explored."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a MenuCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct MenuCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl MenuCommand {
+    /// Construct new MenuCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::MenuCommand;
+    ///
+    /// let menu = MenuCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(menu.name, "menu");
+    /// assert_eq!(menu.description, "Pauses the game and opens the menu.");
+    /// ```
+    pub fn build<'a>() -> Result<MenuCommand, &'a str> {
+        Ok(MenuCommand {
+            name: String::from(MENU),
+            description: String::from("Pauses the game and opens the menu."),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a ParleyCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the target of the command.
+    ParleyCommand,
+    String
+);
+
+impl ParleyCommand {
+    /// Construct new ParleyCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::ParleyCommand;
+    ///
+    /// let sentence = vec!["parley", "goblin"];
+    /// let parley = ParleyCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(parley.name, "parley");
+    /// assert_eq!(parley.description, "Parley with an enemy.");
+    /// assert_eq!(parley.target, "goblin");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<ParleyCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for parley command.");
+        }
+        Ok(ParleyCommand {
+            name: String::from(PARLEY),
+            description: String::from("Parley with an enemy."),
+            target: String::from(sentence[1]),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a PlayCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct PlayCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl PlayCommand {
+    /// Construct new PlayCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::PlayCommand;
+    ///
+    /// let play = PlayCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(play.name, "play");
+    /// assert_eq!(play.description, "Leaves the menu and returns to the adventure.");
+    /// ```
+    pub fn build<'a>() -> Result<PlayCommand, &'a str> {
+        Ok(PlayCommand {
+            name: String::from(PLAY),
+            description: String::from("Leaves the menu and returns to the adventure."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a RestCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct RestCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl RestCommand {
+    /// Construct new RestCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::RestCommand;
+    ///
+    /// let rest = RestCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(rest.name, "rest");
+    /// assert_eq!(rest.description, "Rest to recover hit points and mana.");
+    /// ```
+    pub fn build<'a>() -> Result<RestCommand, &'a str> {
+        Ok(RestCommand {
+            name: String::from(REST),
+            description: String::from("Rest to recover hit points and mana."),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a SaveCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - An optional string that holds the save slot of the command.
+    SaveCommand,
+    Option<String>
+);
+
+impl SaveCommand {
+    /// Construct new SaveCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::SaveCommand;
+    ///
+    /// let sentence = vec!["save", "slot1"];
+    /// let save = SaveCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(save.name, "save");
+    /// assert_eq!(save.description, "Saves the game.");
+    /// assert_eq!(save.target, Some(String::from("slot1")));
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<SaveCommand, &str> {
+        if sentence.is_empty() {
+            return Err("Not enough arguments for save command.");
+        }
+        Ok(SaveCommand {
+            name: String::from(SAVE),
+            description: String::from("Saves the game."),
+            target: match sentence.len() {
+                1 => None,
+                _ => Some(String::from(sentence[1])),
+            },
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a SayCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the value of the command.
+    SayCommand,
+    String
+);
+
+impl SayCommand {
+    /// Construct new SayCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::SayCommand;
+    ///
+    /// let sentence = vec!["say", "hello", "world"];
+    /// let say = SayCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(say.name, "say");
+    /// assert_eq!(say.description, "Prints a message to the screen.");
+    /// assert_eq!(say.target, "hello world");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<SayCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for say command.");
+        }
+        Ok(SayCommand {
+            name: String::from(SAY),
+            description: String::from("Prints a message to the screen."),
+            target: sentence[1..].join(" "),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a SpoutLoreCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - An optional string that holds the target of the command.
+    SpoutLoreCommand,
+    Option<String>
+);
+
+impl SpoutLoreCommand {
+    /// Construct new SpoutLoreCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::SpoutLoreCommand;
+    ///
+    /// let sentence = vec!["consult", "wizard"];
+    /// let spout = SpoutLoreCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(spout.name, "consult");
+    /// assert_eq!(spout.description, "Spout lore about a subject.");
+    /// assert_eq!(spout.target, Some(String::from("wizard")));
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<SpoutLoreCommand, &str> {
+        if sentence.is_empty() {
+            return Err("Not enough arguments for spout lore command.");
+        }
+        Ok(SpoutLoreCommand {
+            name: String::from(sentence[0]),
+            description: String::from("Spout lore about a subject."),
+            target: match sentence.len() {
+                0..=1 => None,
+                _ => Some(String::from(sentence[1])),
+            },
+        })
+    }
+}
+
+/// A struct that holds the name and description of a SpellsCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct SpellsCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl SpellsCommand {
+    /// Construct new SpellsCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::SpellsCommand;
+    ///
+    /// let spells = SpellsCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(spells.name, "spells");
+    /// assert_eq!(spells.description, "Lists the spells the player knows.");
+    /// ```
+    pub fn build<'a>() -> Result<SpellsCommand, &'a str> {
+        Ok(SpellsCommand {
+            name: String::from(SPELLS),
+            description: String::from("Lists the spells the player knows."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a StatusCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct StatusCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl StatusCommand {
+    /// Construct new StatusCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::StatusCommand;
+    ///
+    /// let status = StatusCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(status.name, "status");
+    /// assert_eq!(status.description, "Shows the player's character sheet.");
+    /// ```
+    pub fn build<'a>() -> Result<StatusCommand, &'a str> {
+        Ok(StatusCommand {
+            name: String::from(STATUS),
+            description: String::from("Shows the player's character sheet."),
+        })
+    }
+}
+
+/// A struct that holds the name, description, count, and target of a TakeCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+/// * `count` - How many of the item, such as the 5 in `take 5 arrow`. None, or more than there
+///   are, takes the whole stack.
+/// * `target` - A string that holds the target of the command.
+#[derive(Clone, Debug)]
+pub struct TakeCommand {
+    pub name: String,
+    pub description: String,
+    pub count: Option<u32>,
+    pub target: String,
+}
+
+impl TakeCommand {
+    /// Construct new TakeCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::TakeCommand;
+    ///
+    /// let sentence = vec!["take", "sword"];
+    /// let take = TakeCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(take.name, "take");
+    /// assert_eq!(take.description, "Takes an item from the current location.");
+    /// assert_eq!(take.target, "sword");
+    /// assert_eq!(take.count, None);
+    ///
+    /// let sentence = vec!["take", "20", "arrow"];
+    /// let take = TakeCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(take.count, Some(20));
+    /// assert_eq!(take.target, "arrow");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<TakeCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for take command.");
+        }
+        let (count, target) = quantity(&sentence)?;
+        Ok(TakeCommand {
+            name: String::from(TAKE),
+            description: String::from("Takes an item from the current location."),
+            count,
+            target: String::from(target),
+        })
+    }
+}
+
+/// A struct that holds the name and description of an UnequipCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct UnequipCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl UnequipCommand {
+    /// Construct new UnequipCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::UnequipCommand;
+    ///
+    /// let unequip = UnequipCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(unequip.name, "unequip");
+    /// assert_eq!(unequip.description, "Puts the wielded weapon back in the player's inventory.");
+    /// ```
+    pub fn build<'a>() -> Result<UnequipCommand, &'a str> {
+        Ok(UnequipCommand {
+            name: String::from(UNEQUIP),
+            description: String::from("Puts the wielded weapon back in the player's inventory."),
+        })
+    }
+}
+
+/// A struct that holds the name, description, count, and target of a UseCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+/// * `count` - How many times to use the item, such as the 2 in `use 2 potion`. None uses it
+///   once, and more than the hero has is refused.
+/// * `target` - A string that holds the name of the item to use.
+#[derive(Clone, Debug)]
+pub struct UseCommand {
+    pub name: String,
+    pub description: String,
+    pub count: Option<u32>,
+    pub target: String,
+}
+
+impl UseCommand {
+    /// Construct new UseCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::UseCommand;
+    ///
+    /// let sentence = vec!["use", "potion"];
+    /// let use_command = UseCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(use_command.name, "use");
+    /// assert_eq!(use_command.description, "Use an item from the inventory.");
+    /// assert_eq!(use_command.target, "potion");
+    /// assert_eq!(use_command.count, None);
+    ///
+    /// let sentence = vec!["use", "2", "potion"];
+    /// let use_command = UseCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(use_command.count, Some(2));
+    /// assert_eq!(use_command.target, "potion");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<UseCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for use command.");
+        }
+        let (count, target) = quantity(&sentence)?;
+        Ok(UseCommand {
+            name: String::from(sentence[0]),
+            description: String::from("Use an item from the inventory."),
+            count,
+            target: String::from(target),
+        })
+    }
+}
+
+create_command!(
+    /// A struct that holds the name, description, and target of a VolleyCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
     /// * `description` - A string that holds the description of the command.
     /// * `target` - A string that holds the target of the command.
     VolleyCommand,
@@ -726,22 +1522,348 @@ impl VolleyCommand {
     }
 }
 
+create_command!(
+    /// A struct that holds the name, description, and target of a ValidateCommand.
+    ///
+    /// # Attributes
+    /// * `name` - A string that holds the name of the command.
+    /// * `description` - A string that holds the description of the command.
+    /// * `target` - A string that holds the name of the map to check.
+    ValidateCommand,
+    String
+);
+
+impl ValidateCommand {
+    /// Construct new ValidateCommand.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::ValidateCommand;
+    ///
+    /// let sentence = vec!["validate", "Test", "Area"];
+    /// let validate = ValidateCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(validate.name, "validate");
+    /// assert_eq!(validate.description, "Checks a map for problems, such as unreachable rooms.");
+    /// assert_eq!(validate.target, "Test Area");
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<ValidateCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for validate command.");
+        }
+        Ok(ValidateCommand {
+            name: String::from(VALIDATE),
+            description: String::from("Checks a map for problems, such as unreachable rooms."),
+            target: sentence[1..].join(" "),
+        })
+    }
+}
+
+/// A struct that holds the name, description, target, and room of a WarpCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+/// * `target` - A string that holds the name of the map to warp to.
+/// * `room` - The row and column of the room to warp to, or None for the map's first room.
+#[derive(Clone)]
+pub struct WarpCommand {
+    pub name: String,
+    pub description: String,
+    pub target: String,
+    pub room: Option<(usize, usize)>,
+}
+
+impl WarpCommand {
+    /// Construct new WarpCommand. The map name can be quoted, and ends with the row and column
+    /// of a room when the last two words are numbers.
+    ///
+    /// # Arguments
+    /// * `sentence` - A vector of string slices that holds the line of text to tokenize.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::WarpCommand;
+    ///
+    /// let sentence = vec!["warp", "Test", "Area", "2"];
+    /// let warp = WarpCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(warp.name, "warp");
+    /// assert_eq!(warp.description, "Jumps to a room of another map. Debug only.");
+    /// assert_eq!(warp.target, "Test Area 2");
+    /// assert_eq!(warp.room, None);
+    ///
+    /// let sentence = vec!["warp", "\"Test", "Area", "2\"", "1", "0"];
+    /// let warp = WarpCommand::build(sentence).unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(warp.target, "Test Area 2");
+    /// assert_eq!(warp.room, Some((1, 0)));
+    /// ```
+    pub fn build(sentence: Vec<&str>) -> Result<WarpCommand, &str> {
+        if sentence.len() < 2 {
+            return Err("Not enough arguments for warp command.");
+        }
+        let mut words = &sentence[1..];
+        let mut room = None;
+        if let [name @ .., row, col] = words {
+            if let (false, Ok(row), Ok(col)) = (name.is_empty(), row.parse(), col.parse()) {
+                room = Some((row, col));
+                words = name;
+            }
+        }
+        Ok(WarpCommand {
+            name: String::from(WARP),
+            description: String::from("Jumps to a room of another map. Debug only."),
+            target: words.join(" ").trim_matches('"').to_string(),
+            room,
+        })
+    }
+}
+
+/// A struct that holds the name and description of a WhereCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct WhereCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl WhereCommand {
+    /// Construct new WhereCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::WhereCommand;
+    ///
+    /// let where_command = WhereCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(where_command.name, "where");
+    /// assert_eq!(
+    ///     where_command.description,
+    ///     "Shows the map, coordinates, and name of the room the player is in."
+    /// );
+    /// ```
+    pub fn build<'a>() -> Result<WhereCommand, &'a str> {
+        Ok(WhereCommand {
+            name: String::from(WHERE),
+            description: String::from(
+                "Shows the map, coordinates, and name of the room the player is in.",
+            ),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a DumpCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct DumpCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl DumpCommand {
+    /// Construct new DumpCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::DumpCommand;
+    ///
+    /// let dump = DumpCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(dump.name, "dump");
+    /// assert_eq!(dump.description, "Shows the game state as JSON, for debugging.");
+    /// ```
+    pub fn build<'a>() -> Result<DumpCommand, &'a str> {
+        Ok(DumpCommand {
+            name: String::from(DUMP),
+            description: String::from("Shows the game state as JSON, for debugging."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a BackCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct BackCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl BackCommand {
+    /// Construct new BackCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::BackCommand;
+    ///
+    /// let back = BackCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(back.name, "back");
+    /// assert_eq!(back.description, "Returns the player to the room they came from.");
+    /// ```
+    pub fn build<'a>() -> Result<BackCommand, &'a str> {
+        Ok(BackCommand {
+            name: String::from(BACK),
+            description: String::from("Returns the player to the room they came from."),
+        })
+    }
+}
+
+/// A struct that holds the name and description of a ReloadCommand.
+///
+/// # Attributes
+/// * `name` - A string that holds the name of the command.
+/// * `description` - A string that holds the description of the command.
+#[derive(Clone)]
+pub struct ReloadCommand {
+    pub name: String,
+    pub description: String,
+}
+
+impl ReloadCommand {
+    /// Construct new ReloadCommand.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::ReloadCommand;
+    ///
+    /// let reload = ReloadCommand::build().unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(reload.name, "reload");
+    /// assert_eq!(reload.description, "Loads the current map again from the database. Debug only.");
+    /// ```
+    pub fn build<'a>() -> Result<ReloadCommand, &'a str> {
+        Ok(ReloadCommand {
+            name: String::from(RELOAD),
+            description: String::from("Loads the current map again from the database. Debug only."),
+        })
+    }
+}
+
 /// An enum that holds all of the possible commands.
+#[derive(Clone)]
 pub enum Command {
     Aid(AidCommand),
+    Back(BackCommand),
     Cast(CastCommand),
     Defend(DefendCommand),
     DefyDanger(DefyDangerCommand),
     DiscernRealities(DiscernRealitiesCommand),
     Drop(DropCommand),
+    Dump(DumpCommand),
+    Equip(EquipCommand),
+    Examine(ExamineCommand),
     Exit(ExitCommand),
+    Extinguish(ExtinguishCommand),
+    Flee(FleeCommand),
+    Give(GiveCommand),
     Go(GoCommand),
+    Goto(GotoCommand),
     HackAndSlash(HackAndSlashCommand),
     Help(HelpCommand),
     Interfere(InterfereCommand),
+    Inventory(InventoryCommand),
+    Learn(LearnCommand),
+    Light(LightCommand),
+    Load(LoadCommand),
+    Look(LookCommand),
+    Map(MapCommand),
+    Menu(MenuCommand),
     Parley(ParleyCommand),
+    Play(PlayCommand),
+    Reload(ReloadCommand),
+    Rest(RestCommand),
+    Save(SaveCommand),
     Say(SayCommand),
+    Spells(SpellsCommand),
     SpoutLore(SpoutLoreCommand),
+    Status(StatusCommand),
     Take(TakeCommand),
+    Unequip(UnequipCommand),
+    Use(UseCommand),
+    Validate(ValidateCommand),
     Volley(VolleyCommand),
+    Warp(WarpCommand),
+    Where(WhereCommand),
+}
+
+impl Command {
+    /// A function that gets the item or creature a command is aimed at, such as the goblin in
+    /// `attack goblin`. Commands aimed at a place, a map, or nothing at all have no target.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The target of the command, if it has one.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::{parse_input, Command};
+    ///
+    /// let command = parse_input("examine goblin").unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(command.target(), Some("goblin"));
+    /// let command = parse_input("go north").unwrap_or_else(|e| panic!("{}", e));
+    /// assert_eq!(command.target(), None);
+    /// ```
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            Command::Aid(c) => Some(c.target.as_str()),
+            Command::Cast(c) => c.target.as_deref(),
+            Command::Defend(c) => Some(c.target.as_str()),
+            Command::DefyDanger(c) => c.target.as_deref(),
+            Command::DiscernRealities(c) => c.target.as_deref(),
+            Command::Drop(c) => Some(c.target.as_str()),
+            Command::Equip(c) => Some(c.target.as_str()),
+            Command::Examine(c) => Some(c.target.as_str()),
+            Command::Extinguish(c) => Some(c.target.as_str()),
+            Command::Give(c) => Some(c.target.as_str()),
+            Command::HackAndSlash(c) => match c.target.as_slice() {
+                [target] => Some(target.as_str()),
+                _ => None,
+            },
+            Command::Interfere(c) => Some(c.target.as_str()),
+            Command::Light(c) => Some(c.target.as_str()),
+            Command::Parley(c) => Some(c.target.as_str()),
+            Command::SpoutLore(c) => c.target.as_deref(),
+            Command::Take(c) => Some(c.target.as_str()),
+            Command::Use(c) => Some(c.target.as_str()),
+            Command::Volley(c) => Some(c.target.as_str()),
+            _ => None,
+        }
+    }
+
+    /// A function that gets the target of a command so it can be changed, such as when "it" is
+    /// replaced by what the hero last named.
+    ///
+    /// # Returns
+    /// * `Option<&mut String>` - The target of the command, if it has one.
+    pub fn target_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Command::Aid(c) => Some(&mut c.target),
+            Command::Cast(c) => c.target.as_mut(),
+            Command::Defend(c) => Some(&mut c.target),
+            Command::DefyDanger(c) => c.target.as_mut(),
+            Command::DiscernRealities(c) => c.target.as_mut(),
+            Command::Drop(c) => Some(&mut c.target),
+            Command::Equip(c) => Some(&mut c.target),
+            Command::Examine(c) => Some(&mut c.target),
+            Command::Extinguish(c) => Some(&mut c.target),
+            Command::Give(c) => Some(&mut c.target),
+            Command::HackAndSlash(c) => match c.target.as_mut_slice() {
+                [target] => Some(target),
+                _ => None,
+            },
+            Command::Interfere(c) => Some(&mut c.target),
+            Command::Light(c) => Some(&mut c.target),
+            Command::Parley(c) => Some(&mut c.target),
+            Command::SpoutLore(c) => c.target.as_mut(),
+            Command::Take(c) => Some(&mut c.target),
+            Command::Use(c) => Some(&mut c.target),
+            Command::Volley(c) => Some(&mut c.target),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,122 @@
+//! # Direction
+//! The direction module contains the directions the player can move in.
+use std::fmt;
+use std::str::FromStr;
+
+/// An enum that represents a direction the player can move in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Direction {
+    /// Every direction, the compass points first.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::NorthEast,
+        Direction::NorthWest,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ];
+
+    /// Checks if the direction is between two of the compass points.
+    ///
+    /// # Returns
+    /// * `bool` - True if the direction is diagonal.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::Direction;
+    ///
+    /// assert!(Direction::NorthEast.is_diagonal());
+    /// assert!(!Direction::North.is_diagonal());
+    /// ```
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::NorthEast
+                | Direction::NorthWest
+                | Direction::SouthEast
+                | Direction::SouthWest
+        )
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    /// Parse a direction, ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::Direction;
+    ///
+    /// assert_eq!("North".parse(), Ok(Direction::North));
+    /// assert_eq!("ne".parse(), Ok(Direction::NorthEast));
+    /// assert_eq!("upways".parse::<Direction>(), Err(String::from("Unknown direction: upways.")));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "north" => Ok(Direction::North),
+            "south" => Ok(Direction::South),
+            "east" => Ok(Direction::East),
+            "west" => Ok(Direction::West),
+            "northeast" | "ne" => Ok(Direction::NorthEast),
+            "northwest" | "nw" => Ok(Direction::NorthWest),
+            "southeast" | "se" => Ok(Direction::SouthEast),
+            "southwest" | "sw" => Ok(Direction::SouthWest),
+            _ => Err(format!("Unknown direction: {}.", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+            Direction::NorthEast => "northeast",
+            Direction::NorthWest => "northwest",
+            Direction::SouthEast => "southeast",
+            Direction::SouthWest => "southwest",
+        };
+        write!(f, "{}", direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test parsing every direction and an unknown one.
+    #[test]
+    fn test_parse_directions() {
+        for (text, direction) in [
+            ("north", Direction::North),
+            ("SOUTH", Direction::South),
+            ("East", Direction::East),
+            ("west", Direction::West),
+            ("NorthEast", Direction::NorthEast),
+            ("southwest", Direction::SouthWest),
+        ] {
+            assert_eq!(text.parse(), Ok(direction));
+            assert_eq!(direction.to_string(), text.to_lowercase());
+        }
+        assert_eq!(
+            "upways".parse::<Direction>(),
+            Err(String::from("Unknown direction: upways."))
+        );
+    }
+}
@@ -3,15 +3,212 @@
 
 use super::*;
 
+/// A struct that represents a word of a line along with where it was found, so errors can point
+/// at it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token<'a> {
+    /// The text of the word.
+    pub text: &'a str,
+    /// The byte offset the word starts at.
+    pub start: usize,
+    /// The byte offset just past the end of the word.
+    pub end: usize,
+}
+
 /// Tokenize a line of text into a vector of words.
 ///
 /// # Arguments
 /// * `line` - A string slice that holds the line of text to tokenize.
 fn tokenize(line: &str) -> Vec<&str> {
-    line.split_whitespace().collect()
+    tokenize_spans(line).into_iter().map(|t| t.text).collect()
 }
 
-/// Parse a line of text and return the command definition.
+/// Tokenize a line of text into a vector of words, keeping the byte offsets of each word.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text to tokenize.
+///
+/// # Returns
+/// * `Vec<Token>` - The words of the line, in order.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::parser::tokenize_spans;
+///
+/// let tokens = tokenize_spans("take  apple");
+/// assert_eq!(tokens[1].text, "apple");
+/// assert_eq!(tokens[1].start, 6);
+/// ```
+pub fn tokenize_spans(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push(Token {
+                    text: &line[s..i],
+                    start: s,
+                    end: i,
+                });
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(Token {
+            text: &line[s..],
+            start: s,
+            end: line.len(),
+        });
+    }
+    tokens
+}
+
+/// The furthest a keyword can be from the player's input and still be suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Count the single character insertions, deletions, and substitutions needed to turn one word
+/// into another.
+///
+/// # Arguments
+/// * `a` - A string slice that holds the first word.
+/// * `b` - A string slice that holds the second word.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = if a_char == *b_char { 0 } else { 1 };
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Find the known keyword closest to a word the player typed.
+///
+/// # Arguments
+/// * `word` - A string slice that holds the word to find a suggestion for.
+///
+/// # Returns
+/// * `Option<&str>` - The closest keyword, or None if no keyword is close enough.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::parser::suggest;
+///
+/// assert_eq!(suggest("atack"), Some("attack"));
+/// assert_eq!(suggest("xyzzy"), None);
+/// ```
+pub fn suggest(word: &str) -> Option<&'static str> {
+    alias::alias_table()
+        .keys()
+        .map(|keyword| (levenshtein(word, keyword), *keyword))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min()
+        .map(|(_, keyword)| keyword)
+}
+
+/// Normalize a line of input before it is parsed or shown back to the player, dropping the line
+/// ending, including the carriage return of Windows, and the spaces around the command.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text as it was read.
+///
+/// # Returns
+/// * `&str` - The line without the surrounding whitespace.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::normalize;
+///
+/// assert_eq!(normalize("say hi\r\n"), "say hi");
+/// assert_eq!(normalize("  look \n"), "look");
+/// ```
+pub fn normalize(line: &str) -> &str {
+    line.trim()
+}
+
+/// The filler words dropped from a command before it is built, so `take the sword` takes the
+/// sword.
+pub const STOP_WORDS: &[&str] = &["a", "an", "the", "to"];
+
+/// Check if the words after a command should be kept as typed, because they are something said
+/// or the name of a map or save slot rather than a target.
+///
+/// # Arguments
+/// * `kind` - The kind of the command.
+///
+/// # Returns
+/// * `bool` - True if stop words shouldn't be dropped from the command.
+fn keeps_words(kind: CommandKind) -> bool {
+    matches!(
+        kind,
+        CommandKind::Say
+            | CommandKind::Goto
+            | CommandKind::Load
+            | CommandKind::Save
+            | CommandKind::Validate
+            | CommandKind::Warp
+    )
+}
+
+/// Drop the stop words in front of each argument of a command, ignoring case, so names such as
+/// "book of the dead" keep their words. When every word after the verb is a stop word, they are
+/// all kept, so an item can still be named "a". A give command has two arguments, the item and
+/// who receives it, and the item's words and the "to" after them are kept for
+/// `GiveCommand::build`.
+///
+/// # Arguments
+/// * `kind` - The kind of the command.
+/// * `tokens` - The words of the command, starting with the verb.
+/// * `stop_words` - The words to drop.
+///
+/// # Returns
+/// * `Vec<&str>` - The words of the command without the leading stop words.
+fn drop_stop_words<'a, S: AsRef<str>>(
+    kind: CommandKind,
+    tokens: Vec<&'a str>,
+    stop_words: &[S],
+) -> Vec<&'a str> {
+    let is_stop_word = |token: &&str| {
+        stop_words
+            .iter()
+            .any(|word| token.eq_ignore_ascii_case(word.as_ref()))
+    };
+    if tokens.iter().skip(1).all(is_stop_word) {
+        return tokens;
+    }
+    let mut kept = vec![tokens[0]];
+    let rest = tokens.into_iter().skip(1).skip_while(is_stop_word);
+    if kind == CommandKind::Give {
+        // The item runs up to the "to", or is one word when there is no "to".
+        let rest: Vec<&str> = rest.collect();
+        let to = rest
+            .iter()
+            .skip(1)
+            .position(|t| t.eq_ignore_ascii_case(TO))
+            .map_or(rest.len().min(1), |i| i + 2);
+        kept.extend(&rest[..to]);
+        kept.extend(rest[to..].iter().copied().skip_while(is_stop_word));
+    } else {
+        kept.extend(rest);
+    }
+    kept
+}
+
+/// Parse a line of text and return the command definition, dropping the default `STOP_WORDS`.
+/// When the command isn't known, the error suggests the closest keyword. The line is normalized
+/// first, and the verb is matched ignoring case, while the rest of the line keeps its casing.
+///
 /// # Arguments
 /// * `line` - A string slice that holds the line of text to parse.
 ///
@@ -22,78 +219,330 @@ fn tokenize(line: &str) -> Vec<&str> {
 /// let sentence = "say hello world";
 /// parse_input(sentence);
 /// ```
-pub fn parse_input(line: &str) -> Result<Command, &str> {
-    let tokens = tokenize(line);
-    let command = tokens[0];
-    match command {
-        AID | ASSIST => {
+pub fn parse_input(line: &str) -> Result<Command, String> {
+    parse_input_with(line, STOP_WORDS)
+}
+
+/// Parse a line of text and return the command definition, dropping the given stop words from
+/// every command but the ones whose words are kept as typed, such as `say`.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text to parse.
+/// * `stop_words` - The filler words to drop, such as `STOP_WORDS`. An empty slice keeps every
+///   word.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::{parse_input_with, Command};
+///
+/// match parse_input_with("take the sword", &["the"]) {
+///     Ok(Command::Take(take)) => assert_eq!(take.target, "sword"),
+///     _ => panic!("Take command expected."),
+/// }
+/// ```
+pub fn parse_input_with<S: AsRef<str>>(line: &str, stop_words: &[S]) -> Result<Command, String> {
+    let mut tokens = tokenize(normalize(line));
+    let verb = tokens.first().copied().unwrap_or_default().to_lowercase();
+    if let Some(first) = tokens.first_mut() {
+        *first = verb.as_str();
+    }
+    let kind = match alias::alias_table().get(verb.as_str()) {
+        Some(kind) => kind,
+        None => {
+            return Err(match suggest(verb.as_str()) {
+                Some(keyword) => format!("Command not found. Did you mean '{}'?", keyword),
+                None => String::from("Command not found."),
+            })
+        }
+    };
+    if !keeps_words(*kind) {
+        tokens = drop_stop_words(*kind, tokens, stop_words);
+    }
+    match kind {
+        CommandKind::Aid => {
             let command = AidCommand::build(tokens)?;
             Ok(Command::Aid(command))
         }
-        ATTACK | FIGHT | HIT => {
+        CommandKind::Back => {
+            let command = BackCommand::build()?;
+            Ok(Command::Back(command))
+        }
+        CommandKind::HackAndSlash => {
             let command = HackAndSlashCommand::build(tokens)?;
             Ok(Command::HackAndSlash(command))
         }
-        CAST => {
+        CommandKind::Cast => {
             let command = CastCommand::build(tokens)?;
             Ok(Command::Cast(command))
         }
-        CONSULT => {
+        CommandKind::SpoutLore => {
             let command = SpoutLoreCommand::build(tokens)?;
             Ok(Command::SpoutLore(command))
         }
-        CHARM | DEFY | DODGE | ENDURE | IMPROVISE => {
+        CommandKind::DefyDanger => {
             let command = DefyDangerCommand::build(tokens)?;
             Ok(Command::DefyDanger(command))
         }
-        DEFEND | PROTECT => {
+        CommandKind::Defend => {
             let command = DefendCommand::build(tokens)?;
             Ok(Command::Defend(command))
         }
-        DROP => {
+        CommandKind::Drop => {
             let command = DropCommand::build(tokens)?;
             Ok(Command::Drop(command))
         }
-        EXIT => {
+        CommandKind::Dump => {
+            let command = DumpCommand::build()?;
+            Ok(Command::Dump(command))
+        }
+        CommandKind::Equip => {
+            let command = EquipCommand::build(tokens)?;
+            Ok(Command::Equip(command))
+        }
+        CommandKind::Examine => {
+            let command = ExamineCommand::build(tokens)?;
+            Ok(Command::Examine(command))
+        }
+        CommandKind::Exit => {
             let command = ExitCommand::build()?;
             Ok(Command::Exit(command))
         }
-        GO => {
+        CommandKind::Extinguish => {
+            let command = ExtinguishCommand::build(tokens)?;
+            Ok(Command::Extinguish(command))
+        }
+        CommandKind::Flee => {
+            let command = FleeCommand::build()?;
+            Ok(Command::Flee(command))
+        }
+        CommandKind::Give => {
+            let command = GiveCommand::build(tokens)?;
+            Ok(Command::Give(command))
+        }
+        CommandKind::Go => {
             let command = GoCommand::build(tokens)?;
             Ok(Command::Go(command))
         }
-        HELP => {
+        CommandKind::Goto => {
+            let command = GotoCommand::build(tokens)?;
+            Ok(Command::Goto(command))
+        }
+        CommandKind::Help => {
             let command = HelpCommand::build(tokens)?;
             Ok(Command::Help(command))
         }
-        INTERFERE => {
+        CommandKind::Interfere => {
             let command = InterfereCommand::build(tokens)?;
             Ok(Command::Interfere(command))
         }
-        PARLEY => {
+        CommandKind::Learn => {
+            let command = LearnCommand::build(tokens)?;
+            Ok(Command::Learn(command))
+        }
+        CommandKind::Light => {
+            let command = LightCommand::build(tokens)?;
+            Ok(Command::Light(command))
+        }
+        CommandKind::Load => {
+            let command = LoadCommand::build(tokens)?;
+            Ok(Command::Load(command))
+        }
+        CommandKind::Inventory => {
+            let command = InventoryCommand::build()?;
+            Ok(Command::Inventory(command))
+        }
+        CommandKind::Look => {
+            let command = LookCommand::build()?;
+            Ok(Command::Look(command))
+        }
+        CommandKind::Map => {
+            let command = MapCommand::build()?;
+            Ok(Command::Map(command))
+        }
+        CommandKind::Menu => {
+            let command = MenuCommand::build()?;
+            Ok(Command::Menu(command))
+        }
+        CommandKind::Play => {
+            let command = PlayCommand::build()?;
+            Ok(Command::Play(command))
+        }
+        CommandKind::Reload => {
+            let command = ReloadCommand::build()?;
+            Ok(Command::Reload(command))
+        }
+        CommandKind::Rest => {
+            let command = RestCommand::build()?;
+            Ok(Command::Rest(command))
+        }
+        CommandKind::Save => {
+            let command = SaveCommand::build(tokens)?;
+            Ok(Command::Save(command))
+        }
+        CommandKind::Parley => {
             let command = ParleyCommand::build(tokens)?;
             Ok(Command::Parley(command))
         }
-        SAY => {
+        CommandKind::Say => {
             let command = SayCommand::build(tokens)?;
             Ok(Command::Say(command))
         }
-        SEARCH | STUDY => {
+        CommandKind::DiscernRealities => {
             let command = DiscernRealitiesCommand::build(tokens)?;
             Ok(Command::DiscernRealities(command))
         }
-        SHOOT | VOLLEY => {
+        CommandKind::Validate => {
+            let command = ValidateCommand::build(tokens)?;
+            Ok(Command::Validate(command))
+        }
+        CommandKind::Warp => {
+            let command = WarpCommand::build(tokens)?;
+            Ok(Command::Warp(command))
+        }
+        CommandKind::Where => {
+            let command = WhereCommand::build()?;
+            Ok(Command::Where(command))
+        }
+        CommandKind::Volley => {
             let command = VolleyCommand::build(tokens)?;
             Ok(Command::Volley(command))
         }
-        TAKE => {
+        CommandKind::Spells => {
+            let command = SpellsCommand::build()?;
+            Ok(Command::Spells(command))
+        }
+        CommandKind::Status => {
+            let command = StatusCommand::build()?;
+            Ok(Command::Status(command))
+        }
+        CommandKind::Take => {
             let command = TakeCommand::build(tokens)?;
             Ok(Command::Take(command))
         }
-        _ => Err("Command not found."),
+        CommandKind::Unequip => {
+            let command = UnequipCommand::build()?;
+            Ok(Command::Unequip(command))
+        }
+        CommandKind::Use => {
+            let command = UseCommand::build(tokens)?;
+            Ok(Command::Use(command))
+        }
     }
 }
 
+/// The characters that separate the commands of a line, such as `take sword. go north`.
+pub const SEPARATORS: &[char] = &['.', ';'];
+
+/// A struct that holds the part of a line that couldn't be parsed and why.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    /// The command that couldn't be parsed, normalized.
+    pub input: String,
+    /// The error from parsing the command.
+    pub error: String,
+}
+
+/// Check if a command holds free text, such as what is said, which runs to the end of the line
+/// and so can't be followed by another command.
+///
+/// # Arguments
+/// * `command` - A string slice that holds the command, starting with its verb.
+///
+/// # Returns
+/// * `bool` - True if the rest of the line belongs to the command.
+fn is_free_text(command: &str) -> bool {
+    let verb = command
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    alias::alias_table().get(verb.as_str()) == Some(&CommandKind::Say)
+}
+
+/// Split a line into the commands separated by `SEPARATORS`. A separator inside double quotes,
+/// such as in a map name given to warp, doesn't split the line, and neither does one in what is
+/// said, so `say Hello. Who goes there` stays whole. Empty commands are skipped.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text to split.
+///
+/// # Returns
+/// * `Vec<&str>` - The normalized commands, in order.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::parser::split_commands;
+///
+/// assert_eq!(split_commands("take sword. go north;; look\n"), vec!["take sword", "go north", "look"]);
+/// assert_eq!(split_commands("look; say Hi. Bye."), vec!["look", "say Hi. Bye."]);
+/// ```
+pub fn split_commands(line: &str) -> Vec<&str> {
+    let mut commands = vec![];
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in line.char_indices() {
+        if i == start && is_free_text(&line[start..]) {
+            break;
+        }
+        if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && SEPARATORS.contains(&c) {
+            commands.push(normalize(&line[start..i]));
+            start = i + c.len_utf8();
+        }
+    }
+    commands.push(normalize(&line[start..]));
+    commands.retain(|c| !c.is_empty());
+    commands
+}
+
+/// Parse a line that can hold several commands, dropping the default `STOP_WORDS` from each.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text to parse.
+///
+/// # Returns
+/// * `Result<Vec<Command>, ParseError>` - The commands in the order they were given, or the first
+///   command that couldn't be parsed.
+///
+/// # Examples
+/// ```
+/// use retribution::ret_lang::parse_inputs;
+///
+/// let commands = parse_inputs("take sword. go north; look").unwrap();
+/// assert_eq!(commands.len(), 3);
+/// let error = parse_inputs("take sword. xyzzy").err().unwrap();
+/// assert_eq!(error.input, "xyzzy");
+/// ```
+pub fn parse_inputs(line: &str) -> Result<Vec<Command>, ParseError> {
+    parse_inputs_with(line, STOP_WORDS)
+}
+
+/// Parse a line that can hold several commands, dropping the given stop words from each. Nothing
+/// is returned unless every command parses, so a typo doesn't leave the line half done.
+///
+/// # Arguments
+/// * `line` - A string slice that holds the line of text to parse.
+/// * `stop_words` - The filler words to drop, such as `STOP_WORDS`.
+///
+/// # Returns
+/// * `Result<Vec<Command>, ParseError>` - The commands in the order they were given, or the first
+///   command that couldn't be parsed.
+pub fn parse_inputs_with<S: AsRef<str>>(
+    line: &str,
+    stop_words: &[S],
+) -> Result<Vec<Command>, ParseError> {
+    split_commands(line)
+        .into_iter()
+        .map(|input| {
+            parse_input_with(input, stop_words).map_err(|error| ParseError {
+                input: String::from(input),
+                error,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +555,190 @@ mod tests {
         assert_eq!(tokens, vec!["say", "hello", "world"]);
     }
 
+    /// Test that filler words are dropped before the command is built.
+    #[test]
+    fn test_parse_stop_words() {
+        match parse_input("take the sword") {
+            Ok(Command::Take(take)) => assert_eq!(take.target, "sword"),
+            _ => panic!("Take command expected."),
+        }
+        match parse_input("go to north") {
+            Ok(Command::Go(go)) => assert_eq!(go.target, "north"),
+            _ => panic!("Go command expected."),
+        }
+        match parse_input("go to the top of the stairs") {
+            Ok(Command::Go(go)) => assert_eq!(go.target, "top of the stairs"),
+            _ => panic!("Go command expected."),
+        }
+        match parse_input("give The potion to the old man") {
+            Ok(Command::Give(give)) => {
+                assert_eq!(give.item, "potion");
+                assert_eq!(give.target, "old man");
+            }
+            _ => panic!("Give command expected."),
+        }
+        match parse_input("examine the book of the dead") {
+            Ok(Command::Examine(examine)) => assert_eq!(examine.target, "book of the dead"),
+            _ => panic!("Examine command expected."),
+        }
+        match parse_input("give the healing potion to the squire") {
+            Ok(Command::Give(give)) => {
+                assert_eq!(give.item, "healing potion");
+                assert_eq!(give.target, "squire");
+            }
+            _ => panic!("Give command expected."),
+        }
+        match parse_input("give the potion the squire") {
+            Ok(Command::Give(give)) => {
+                assert_eq!(give.item, "potion");
+                assert_eq!(give.target, "squire");
+            }
+            _ => panic!("Give command expected."),
+        }
+        match parse_input("take a") {
+            Ok(Command::Take(take)) => assert_eq!(take.target, "a"),
+            _ => panic!("Take command expected."),
+        }
+    }
+
+    /// Test that the words of a say command, and a configured empty list, keep every word.
+    #[test]
+    fn test_parse_stop_words_kept() {
+        match parse_input("say to the king") {
+            Ok(Command::Say(say)) => assert_eq!(say.target, "to the king"),
+            _ => panic!("Say command expected."),
+        }
+        match parse_input_with("take the sword", &[] as &[&str]) {
+            Ok(Command::Take(take)) => assert_eq!(take.target, "the"),
+            _ => panic!("Take command expected."),
+        }
+    }
+
+    /// Test that a line with several commands parses each of them in order.
+    #[test]
+    fn test_parse_inputs() {
+        let commands = parse_inputs("take the sword. go north; look").unwrap();
+        assert_eq!(commands.len(), 3);
+        match &commands[0] {
+            Command::Take(take) => assert_eq!(take.target, "sword"),
+            _ => panic!("Take command expected."),
+        }
+        match &commands[1] {
+            Command::Go(go) => assert_eq!(go.target, "north"),
+            _ => panic!("Go command expected."),
+        }
+        assert!(matches!(commands[2], Command::Look(_)));
+        assert!(parse_inputs(" ; . ").unwrap().is_empty());
+        match parse_inputs(r#"warp "St. Ives" 1 1"#).unwrap().first() {
+            Some(Command::Warp(warp)) => assert_eq!(warp.target, "St. Ives"),
+            _ => panic!("Warp command expected."),
+        }
+    }
+
+    /// Test that what is said isn't split into commands of its own.
+    #[test]
+    fn test_parse_inputs_say() {
+        let commands = parse_inputs("look; say Hello. Who goes there; look").unwrap();
+        assert_eq!(commands.len(), 2);
+        match &commands[1] {
+            Command::Say(say) => assert_eq!(say.target, "Hello. Who goes there; look"),
+            _ => panic!("Say command expected."),
+        }
+        assert_eq!(
+            split_commands("  SAY hi. go north"),
+            vec!["SAY hi. go north"]
+        );
+    }
+
+    /// Test that a command that can't be parsed stops the whole line from parsing.
+    #[test]
+    fn test_parse_inputs_failing_command() {
+        let error = match parse_inputs("take sword; xyzzy; look") {
+            Err(e) => e,
+            Ok(_) => panic!("Parse error expected."),
+        };
+        assert_eq!(error.input, "xyzzy");
+        assert_eq!(error.error, "Command not found.");
+    }
+
+    /// Test that each token keeps the byte offsets it was found at.
+    #[test]
+    fn test_tokenize_spans() {
+        let tokens = tokenize_spans(" take  red\tépée ");
+        let spans: Vec<(&str, usize, usize)> =
+            tokens.iter().map(|t| (t.text, t.start, t.end)).collect();
+        assert_eq!(
+            spans,
+            vec![("take", 1, 5), ("red", 7, 10), ("épée", 11, 17)]
+        );
+        assert!(tokenize_spans("   ").is_empty());
+        assert_eq!(tokenize(" take  red\tépée "), vec!["take", "red", "épée"]);
+    }
+
+    /// Test that a Windows line ending is dropped before parsing.
+    #[test]
+    fn test_parse_crlf() {
+        match parse_input("say hi\r\n") {
+            Ok(Command::Say(say)) => assert_eq!(say.target, "hi"),
+            _ => panic!("Say command expected."),
+        }
+        match parse_input("go north\n") {
+            Ok(Command::Go(go)) => assert_eq!(go.target, "north"),
+            _ => panic!("Go command expected."),
+        }
+    }
+
+    /// Test that verbs are matched ignoring case, and the rest of the line keeps its casing.
+    #[test]
+    fn test_parse_verb_case() {
+        match parse_input("GO north") {
+            Ok(Command::Go(go)) => assert_eq!(go.target, "north"),
+            _ => panic!("Go command expected."),
+        }
+        match parse_input("Attack Goblin") {
+            Ok(Command::HackAndSlash(attack)) => assert_eq!(attack.target, vec!["Goblin"]),
+            _ => panic!("Hack and slash command expected."),
+        }
+        assert!(matches!(parse_input("HeLp"), Ok(Command::Help(_))));
+        match parse_input("Say Hello World") {
+            Ok(Command::Say(say)) => assert_eq!(say.target, "Hello World"),
+            _ => panic!("Say command expected."),
+        }
+        match parse_input("ATAK goblin") {
+            Err(e) => assert_eq!(e, "Command not found. Did you mean 'attack'?"),
+            Ok(_) => panic!("Expected an error."),
+        }
+    }
+
+    /// Test that quit and q exit, the same as exit.
+    #[test]
+    fn test_parse_quit() {
+        for sentence in ["exit", "quit", "q", "Quit"] {
+            assert!(matches!(parse_input(sentence), Ok(Command::Exit(_))));
+        }
+    }
+
+    /// Test the parse_input function with an empty line.
+    #[test]
+    fn test_parse_empty() {
+        assert!(parse_input("   ").is_err());
+        assert!(parse_input("dance").is_err());
+    }
+
+    /// Test that unknown commands suggest the closest keyword.
+    #[test]
+    fn test_parse_suggestion() {
+        match parse_input("atack goblin") {
+            Err(e) => assert_eq!(e, "Command not found. Did you mean 'attack'?"),
+            Ok(_) => panic!("Expected an error."),
+        }
+        match parse_input("xyzzy") {
+            Err(e) => assert_eq!(e, "Command not found."),
+            Ok(_) => panic!("Expected an error."),
+        }
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
     /// Test the parse_input function with an aid command.
     #[test]
     fn test_parse_aid() {
@@ -192,7 +825,7 @@ mod tests {
                 assert_eq!(defy.name, "dodge");
                 assert_eq!(defy.description, "Defy danger using a stat.");
                 assert_eq!(defy.target, None);
-                assert_eq!(defy.stat, "dexterity");
+                assert_eq!(defy.stat, Stat::Dexterity);
             }
             _ => panic!("Defy danger command expected."),
         }
@@ -231,6 +864,65 @@ mod tests {
         }
     }
 
+    /// Test the parse_input function with counts on take and drop commands.
+    #[test]
+    fn test_parse_take_and_drop_count() {
+        match parse_input("drop 3 arrow") {
+            Ok(Command::Drop(drop)) => {
+                assert_eq!(drop.count, Some(3));
+                assert_eq!(drop.target, "arrow");
+            }
+            _ => panic!("Drop command expected."),
+        }
+        match parse_input("take sword") {
+            Ok(Command::Take(take)) => {
+                assert_eq!(take.count, None);
+                assert_eq!(take.target, "sword");
+            }
+            _ => panic!("Take command expected."),
+        }
+        // A number on its own is the name of the item, not a count.
+        match parse_input("take 3") {
+            Ok(Command::Take(take)) => {
+                assert_eq!(take.count, None);
+                assert_eq!(take.target, "3");
+            }
+            _ => panic!("Take command expected."),
+        }
+        assert!(parse_input("drop 0 arrow").is_err());
+        assert!(parse_input("take 0 arrow").is_err());
+    }
+
+    /// Test the parse_input function with equip and unequip commands.
+    #[test]
+    fn test_parse_equip() {
+        let comamnd = parse_input("equip sword").unwrap_or_else(|e| panic!("{}", e));
+        match comamnd {
+            Command::Equip(equip) => assert_eq!(equip.target, "sword"),
+            _ => panic!("Equip command expected."),
+        }
+        let comamnd = parse_input("unequip").unwrap_or_else(|e| panic!("{}", e));
+        match comamnd {
+            Command::Unequip(unequip) => assert_eq!(unequip.name, "unequip"),
+            _ => panic!("Unequip command expected."),
+        }
+    }
+
+    /// Test the parse_input function with an examine command.
+    #[test]
+    fn test_parse_examine() {
+        let sentence = "x goblin chief";
+        let comamnd = parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+        match comamnd {
+            Command::Examine(examine) => {
+                assert_eq!(examine.name, "examine");
+                assert_eq!(examine.description, "Examines an item or creature.");
+                assert_eq!(examine.target, "goblin chief");
+            }
+            _ => panic!("Examine command expected."),
+        }
+    }
+
     /// Test the parse_input function with an exit command.
     #[test]
     fn test_parse_exit() {
@@ -255,6 +947,19 @@ mod tests {
                 assert_eq!(go.name, "go");
                 assert_eq!(go.description, "Moves the player to a new location.");
                 assert_eq!(go.target, "north");
+                assert_eq!(go.direction(), Some(Direction::North));
+            }
+            _ => panic!("Go command expected."),
+        }
+    }
+
+    /// Test the parse_input function with a target that isn't a direction, such as a named exit.
+    #[test]
+    fn test_parse_go_named_exit() {
+        match parse_input("go up the ladder") {
+            Ok(Command::Go(go)) => {
+                assert_eq!(go.target, "up the ladder");
+                assert_eq!(go.direction(), None);
             }
             _ => panic!("Go command expected."),
         }
@@ -341,6 +1046,43 @@ mod tests {
         }
     }
 
+    /// Test the parse_input function with the menu commands.
+    #[test]
+    fn test_parse_menu_commands() {
+        match parse_input("save slot1").unwrap_or_else(|e| panic!("{}", e)) {
+            Command::Save(save) => assert_eq!(save.target, Some(String::from("slot1"))),
+            _ => panic!("Save command expected."),
+        }
+        match parse_input("load").unwrap_or_else(|e| panic!("{}", e)) {
+            Command::Load(load) => assert_eq!(load.target, None),
+            _ => panic!("Load command expected."),
+        }
+        for sentence in ["play", "resume"] {
+            match parse_input(sentence).unwrap_or_else(|e| panic!("{}", e)) {
+                Command::Play(play) => assert_eq!(play.name, "play"),
+                _ => panic!("Play command expected."),
+            }
+        }
+        for sentence in ["menu", "pause"] {
+            match parse_input(sentence).unwrap_or_else(|e| panic!("{}", e)) {
+                Command::Menu(menu) => assert_eq!(menu.name, "menu"),
+                _ => panic!("Menu command expected."),
+            }
+        }
+    }
+
+    /// Test the parse_input function with a status command.
+    #[test]
+    fn test_parse_status() {
+        for sentence in ["status", "stats"] {
+            let comamnd = parse_input(sentence).unwrap_or_else(|e| panic!("{}", e));
+            match comamnd {
+                Command::Status(status) => assert_eq!(status.name, "status"),
+                _ => panic!("Status command expected."),
+            }
+        }
+    }
+
     /// Test the parse_input function with a take command.
     #[test]
     fn test_parse_take() {
@@ -356,6 +1098,18 @@ mod tests {
         }
     }
 
+    /// Test the parse_input function with a give command to a multi-word target.
+    #[test]
+    fn test_parse_give() {
+        match parse_input("give potion to old man") {
+            Ok(Command::Give(give)) => {
+                assert_eq!(give.item, "potion");
+                assert_eq!(give.target, "old man");
+            }
+            _ => panic!("Expected a give command."),
+        }
+    }
+
     /// Test the parse_input function with a volley command.
     #[test]
     fn test_parse_volley() {
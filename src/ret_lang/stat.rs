@@ -0,0 +1,102 @@
+//! # Stat
+//! The stat module contains the six stats a move can be rolled with.
+use std::fmt;
+use std::str::FromStr;
+
+/// An enum that represents the six stats of an actor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stat {
+    Strength,
+    Dexterity,
+    Constitution,
+    Intelligence,
+    Wisdom,
+    Charisma,
+}
+
+impl Stat {
+    /// A function that returns the three letter abbreviation of the stat, as shown on rolls.
+    ///
+    /// # Returns
+    /// * `&'static str` - The abbreviation of the stat in capital letters.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::Stat;
+    ///
+    /// assert_eq!(Stat::Dexterity.abbreviation(), "DEX");
+    /// ```
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Stat::Strength => "STR",
+            Stat::Dexterity => "DEX",
+            Stat::Constitution => "CON",
+            Stat::Intelligence => "INT",
+            Stat::Wisdom => "WIS",
+            Stat::Charisma => "CHA",
+        }
+    }
+}
+
+impl FromStr for Stat {
+    type Err = String;
+
+    /// Parse a stat from its full name or its abbreviation, ignoring case.
+    ///
+    /// # Examples
+    /// ```
+    /// use retribution::ret_lang::Stat;
+    ///
+    /// assert_eq!("Wisdom".parse(), Ok(Stat::Wisdom));
+    /// assert_eq!("dex".parse(), Ok(Stat::Dexterity));
+    /// assert_eq!("luck".parse::<Stat>(), Err(String::from("Unknown stat: luck.")));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strength" | "str" => Ok(Stat::Strength),
+            "dexterity" | "dex" => Ok(Stat::Dexterity),
+            "constitution" | "con" => Ok(Stat::Constitution),
+            "intelligence" | "int" => Ok(Stat::Intelligence),
+            "wisdom" | "wis" => Ok(Stat::Wisdom),
+            "charisma" | "cha" => Ok(Stat::Charisma),
+            _ => Err(format!("Unknown stat: {}.", s)),
+        }
+    }
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stat = match self {
+            Stat::Strength => "strength",
+            Stat::Dexterity => "dexterity",
+            Stat::Constitution => "constitution",
+            Stat::Intelligence => "intelligence",
+            Stat::Wisdom => "wisdom",
+            Stat::Charisma => "charisma",
+        };
+        write!(f, "{}", stat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that every stat is written as the name it is parsed from.
+    #[test]
+    fn test_parse_and_format_stats() {
+        for stat in [
+            Stat::Strength,
+            Stat::Dexterity,
+            Stat::Constitution,
+            Stat::Intelligence,
+            Stat::Wisdom,
+            Stat::Charisma,
+        ] {
+            assert_eq!(stat.to_string().parse(), Ok(stat));
+            assert_eq!(stat.to_string().to_uppercase().parse(), Ok(stat));
+        }
+        assert_eq!(Stat::Constitution.to_string(), "constitution");
+        assert!("luck".parse::<Stat>().is_err());
+    }
+}
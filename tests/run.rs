@@ -0,0 +1,36 @@
+use retribution::game;
+use retribution::game::config;
+use retribution::game::map::Coord;
+use retribution::game::state;
+use retribution::migration;
+use std::io;
+
+/// Test a full session that opens in the starting room, moves the hero, and then exits,
+/// confirming the exit.
+#[test]
+fn run_go_north_then_exit() {
+    let mut reader = io::BufReader::new("go north\nexit\nexit\ngo south\n".as_bytes());
+    let mut writer = Vec::new();
+    let mut game_state = state::GameState::new();
+    game_state.map = Some(migration::map::test_area());
+    game_state.room = Some(Coord::new(1, 1));
+    let mut game_config = config::GameConfig::new();
+    game_config.autosave = false;
+
+    game::run(
+        &mut reader,
+        &mut writer,
+        &mut game_state,
+        &game_config,
+        None,
+    )
+    .unwrap_or_else(|e| panic!("{}", e));
+
+    let output = String::from_utf8(writer).unwrap();
+    assert_eq!(
+        output,
+        "Room 1: This is room 1.\nExits: north, east, west.\nWhat do you do hero?\nHero went north. This is room 4.\nWhat do you do hero?\nAre you sure? Type 'exit' again to quit.\nWhat do you do hero?\nFarewell hero.\n"
+    );
+    // The command after exit should never run.
+    assert_eq!(game_state.room, Some(Coord::new(0, 1)));
+}